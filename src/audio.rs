@@ -0,0 +1,37 @@
+use crate::save::SaveData;
+use bevy::audio::Volume;
+use bevy::prelude::*;
+
+pub struct VolumePlugin;
+
+/// Background music, its volume is the music volume of the settings. Every other sound is a sound
+/// effect.
+#[derive(Component)]
+pub struct Music;
+
+impl Plugin for VolumePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PostUpdate, apply_volumes);
+    }
+}
+
+/// Scales the sounds by the volumes of the settings, once they start playing and whenever the
+/// volumes change.
+fn apply_volumes(
+    save_data: Res<SaveData>,
+    mut sinks: Query<(&mut AudioSink, &PlaybackSettings, Has<Music>)>,
+) {
+    let settings = &save_data.settings;
+    for (mut sink, playback, is_music) in sinks.iter_mut() {
+        if !sink.is_added() && !save_data.is_changed() {
+            continue;
+        }
+
+        let channel = if is_music {
+            settings.music_volume
+        } else {
+            settings.sfx_volume
+        };
+        sink.set_volume(playback.volume * Volume::Linear(settings.master_volume * channel));
+    }
+}
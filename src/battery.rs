@@ -0,0 +1,116 @@
+/// How a unit's battery behaves. Levels define their own rules and can override them per rover.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatteryRules {
+    pub capacity: u8,
+    pub move_cost: u8,
+    pub wait_cost: u8,
+    /// Only used by levels with relative commands
+    pub turn_cost: u8,
+    pub sun_recharge: u8,
+    pub shadow_drain: u8,
+    pub starting_charge: u8,
+}
+
+impl Default for BatteryRules {
+    fn default() -> Self {
+        Self {
+            capacity: 3,
+            move_cost: 1,
+            wait_cost: 0,
+            turn_cost: 0,
+            sun_recharge: 1,
+            shadow_drain: 0,
+            starting_charge: 3,
+        }
+    }
+}
+
+/// A partial set of [`BatteryRules`], only the fields that are set replace the base rules.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BatteryRulesOverride {
+    pub capacity: Option<u8>,
+    pub move_cost: Option<u8>,
+    pub wait_cost: Option<u8>,
+    pub turn_cost: Option<u8>,
+    pub sun_recharge: Option<u8>,
+    pub shadow_drain: Option<u8>,
+    pub starting_charge: Option<u8>,
+}
+
+/// What a unit did during the turn, as far as its battery is concerned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TurnActivity {
+    Moved,
+    Waited,
+    /// The unit turned on its tile
+    Turned,
+    /// The unit has no more commands and is parked
+    Idle,
+}
+
+impl BatteryRules {
+    pub fn with_override(self, rules_override: &BatteryRulesOverride) -> Self {
+        let rules = Self {
+            capacity: rules_override.capacity.unwrap_or(self.capacity),
+            move_cost: rules_override.move_cost.unwrap_or(self.move_cost),
+            wait_cost: rules_override.wait_cost.unwrap_or(self.wait_cost),
+            turn_cost: rules_override.turn_cost.unwrap_or(self.turn_cost),
+            sun_recharge: rules_override.sun_recharge.unwrap_or(self.sun_recharge),
+            shadow_drain: rules_override.shadow_drain.unwrap_or(self.shadow_drain),
+            starting_charge: rules_override
+                .starting_charge
+                .unwrap_or(self.starting_charge),
+        };
+
+        Self {
+            starting_charge: rules.starting_charge.min(rules.capacity),
+            ..rules
+        }
+    }
+
+    /// Whether a unit with this much charge left is able to move to another tile.
+    pub fn can_move(&self, battery_level: u8) -> bool {
+        battery_level >= self.move_cost
+    }
+
+    /// Whether a unit with this much charge left is able to turn on its tile.
+    pub fn can_turn(&self, battery_level: u8) -> bool {
+        battery_level >= self.turn_cost
+    }
+
+    /// Charge left at the end of a turn, after paying for the activity and basking in the sun
+    /// (or sitting in the shadow).
+    pub fn apply_turn(&self, battery_level: u8, activity: TurnActivity, in_shadow: bool) -> u8 {
+        let mut battery_level = battery_level - self.charge_spent(battery_level, activity);
+
+        if in_shadow {
+            battery_level = battery_level.saturating_sub(self.shadow_drain);
+        } else {
+            battery_level = battery_level.saturating_add(self.sun_recharge);
+        }
+
+        battery_level.min(self.capacity)
+    }
+
+    /// Charge an activity takes out of a battery holding `battery_level`, never more than
+    /// what is left.
+    pub fn charge_spent(&self, battery_level: u8, activity: TurnActivity) -> u8 {
+        let cost = match activity {
+            TurnActivity::Moved => self.move_cost,
+            TurnActivity::Waited => self.wait_cost,
+            TurnActivity::Turned => self.turn_cost,
+            TurnActivity::Idle => 0,
+        };
+
+        cost.min(battery_level)
+    }
+
+    /// Battery level as a fraction of the capacity, in the range [0, 1].
+    pub fn fill_ratio(&self, battery_level: u8) -> f32 {
+        if self.capacity == 0 {
+            return 0.0;
+        }
+
+        (battery_level as f32 / self.capacity as f32).clamp(0.0, 1.0)
+    }
+}
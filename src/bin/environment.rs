@@ -0,0 +1,106 @@
+//! Plays levels for an agent over stdin and stdout, one JSON request per line and one JSON
+//! response per line, for instance:
+//!
+//! {"command": "reset", "level": 3}
+//! {"command": "step", "actions": ["U", null]}
+//!
+//! `level` is the number of a level of assets/levels or the path of a level file. `actions`
+//! holds one command per unit, as the letters of a @SOLVTIO directive, or null to leave the unit
+//! idle. Every response is either a step (observation, reward, done, status and failure) or an
+//! error.
+
+use mission_ares::environment::Environment;
+use mission_ares::game_control::actions::ActionType;
+use mission_ares::level::{GRADVM, LEGE_FASCICVLVM_GRADVS, MANDATVM_LITTERAE};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+
+#[derive(Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+enum Request {
+    Reset { level: LevelSource },
+    Step { actions: Vec<Option<char>> },
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum LevelSource {
+    Number(u32),
+    Path(PathBuf),
+}
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+fn main() {
+    let mut environment: Option<Environment> = None;
+    let mut stdout = std::io::stdout().lock();
+
+    for line in std::io::stdin().lock().lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = handle(&line, &mut environment).unwrap_or_else(|error| {
+            serde_json::to_string(&ErrorResponse { error }).expect("errors are plain strings")
+        });
+        if writeln!(stdout, "{response}")
+            .and_then(|_| stdout.flush())
+            .is_err()
+        {
+            break;
+        }
+    }
+}
+
+fn handle(line: &str, environment: &mut Option<Environment>) -> Result<String, String> {
+    let request: Request = serde_json::from_str(line).map_err(|error| error.to_string())?;
+
+    let step = match request {
+        Request::Reset { level } => {
+            let mut reset = Environment::new(load_level(&level)?);
+            let step = reset.reset();
+            *environment = Some(reset);
+            step
+        }
+        Request::Step { actions } => {
+            let environment = environment
+                .as_mut()
+                .ok_or("no level, reset one first".to_string())?;
+            let actions = actions
+                .into_iter()
+                .map(|letter| {
+                    letter
+                        .map(|letter| {
+                            MANDATVM_LITTERAE(letter).ok_or(format!("unknown command {letter}"))
+                        })
+                        .transpose()
+                })
+                .collect::<Result<Vec<Option<ActionType>>, String>>()?;
+            environment
+                .step(&actions)
+                .map_err(|error| error.to_string())?
+        }
+    };
+
+    serde_json::to_string(&step).map_err(|error| error.to_string())
+}
+
+fn load_level(source: &LevelSource) -> Result<GRADVM, String> {
+    let (path, index) = match source {
+        LevelSource::Number(0) => return Err("levels are numbered from 1".to_string()),
+        LevelSource::Number(number) => (
+            PathBuf::from(format!("assets/levels/{number}.lvl")),
+            number - 1,
+        ),
+        LevelSource::Path(path) => (path.clone(), 0),
+    };
+
+    LEGE_FASCICVLVM_GRADVS(&path, index).map_err(|error| format!("{}: {error}", path.display()))
+}
@@ -0,0 +1,115 @@
+//! Generates levels along with their shadow maps, for instance:
+//!
+//! cargo run --bin generate_levels -- --seed 42 --count 5 --width 7 --rovers 2
+//!
+//! Every level is checked with the solver, only solvable levels are written.
+
+use mission_ares::generator::{GeneratorSettings, generate};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: generate_levels [--seed N] [--count N] [--width N] [--height N] \
+[--rovers N] [--minerals N] [--wires N] [--shadow FRACTION] [--min-turns N] [--out DIRECTORY]";
+
+fn main() -> ExitCode {
+    let mut settings = GeneratorSettings::default();
+    let mut seed: u64 = 0;
+    let mut count: u64 = 1;
+    let mut out = PathBuf::from("assets/levels");
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let Some(value) = args.next() else {
+            eprintln!("missing value for {arg}\n{USAGE}");
+            return ExitCode::FAILURE;
+        };
+
+        let parsed = match arg.as_str() {
+            "--seed" => value.parse().map(|value| seed = value).is_ok(),
+            "--count" => value.parse().map(|value| count = value).is_ok(),
+            "--width" => value.parse().map(|value| settings.width = value).is_ok(),
+            "--height" => value.parse().map(|value| settings.height = value).is_ok(),
+            "--rovers" => positive(&value)
+                .map(|value| settings.rovers = value)
+                .is_some(),
+            "--minerals" => positive(&value)
+                .map(|value| settings.minerals = value)
+                .is_some(),
+            "--wires" => value
+                .parse()
+                .map(|value| settings.wire_pairs = value)
+                .is_ok(),
+            "--shadow" => value
+                .parse()
+                .map(|value| settings.shadow_coverage = value)
+                .is_ok(),
+            "--min-turns" => value
+                .parse()
+                .map(|value| settings.min_turns = value)
+                .is_ok(),
+            "--out" => {
+                out = PathBuf::from(&value);
+                true
+            }
+            _ => {
+                eprintln!("unknown argument {arg}\n{USAGE}");
+                return ExitCode::FAILURE;
+            }
+        };
+
+        if !parsed {
+            eprintln!("invalid value {value} for {arg}\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if let Err(error) = std::fs::create_dir_all(&out) {
+        eprintln!("could not create {}: {error}", out.display());
+        return ExitCode::FAILURE;
+    }
+
+    let mut failed = false;
+    for seed in seed..seed + count {
+        let Some(generated) = generate(seed, &settings) else {
+            eprintln!("seed {seed}: no solvable level found");
+            failed = true;
+            continue;
+        };
+
+        let level_path = out.join(format!("endless_{seed}.lvl"));
+        let shadow_path = level_path.with_extension("png");
+        let (width, height) = generated.shadow_map_size();
+
+        if let Err(error) = std::fs::write(&level_path, &generated.text) {
+            eprintln!("could not write {}: {error}", level_path.display());
+            return ExitCode::FAILURE;
+        }
+        if let Err(error) = image::save_buffer(
+            &shadow_path,
+            &generated.shadow_map_pixels(),
+            width,
+            height,
+            image::ExtendedColorType::Rgba8,
+        ) {
+            eprintln!("could not write {}: {error}", shadow_path.display());
+            return ExitCode::FAILURE;
+        }
+
+        println!(
+            "seed {seed}: {} ({} turns)",
+            level_path.display(),
+            generated.solution.turns
+        );
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// A count that has to be at least 1, a level needs a rover and something to collect.
+fn positive(value: &str) -> Option<usize> {
+    value.parse().ok().filter(|value| *value >= 1)
+}
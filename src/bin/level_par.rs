@@ -0,0 +1,126 @@
+//! Computes the par of levels with the solver, for instance:
+//!
+//! cargo run --bin level_par -- --write assets/levels/3.lvl
+//!
+//! Without any level, every numbered level of assets/levels is checked. With --write, the
+//! @PAR and @SOLVTIO lines of each solved level are added or replaced.
+
+use mission_ares::level::{LEGE_FASCICVLVM_GRADVS, LITTERA_MANDATI, PAR};
+use mission_ares::score::solution_par;
+use mission_ares::solver::{Solution, SolverLimits, solve, solve_fewest_commands};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: level_par [--write] [--max-turns N] [--max-states N] [LEVEL.lvl...]";
+
+fn main() -> ExitCode {
+    let mut limits = SolverLimits::default();
+    let mut write = false;
+    let mut paths = vec![];
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let parsed = match arg.as_str() {
+            "--write" => {
+                write = true;
+                true
+            }
+            "--max-turns" => args
+                .next()
+                .and_then(|value| value.parse().ok())
+                .map(|value| limits.max_turns = value)
+                .is_some(),
+            "--max-states" => args
+                .next()
+                .and_then(|value| value.parse().ok())
+                .map(|value| limits.max_states = value)
+                .is_some(),
+            _ if arg.starts_with("--") => false,
+            _ => {
+                paths.push(PathBuf::from(&arg));
+                true
+            }
+        };
+
+        if !parsed {
+            eprintln!("invalid argument {arg}\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if paths.is_empty() {
+        paths = (1..)
+            .map(|number| PathBuf::from(format!("assets/levels/{number}.lvl")))
+            .take_while(|path| path.exists())
+            .collect();
+    }
+
+    let mut failed = false;
+    for path in &paths {
+        match level_par(path, limits) {
+            Ok(Some((par, solution))) => {
+                println!(
+                    "{}: {} commands, {} turns, {} battery",
+                    path.display(),
+                    par.MANDATA,
+                    par.VICES,
+                    par.PILA
+                );
+                if write && let Err(error) = write_par(path, par, &solution) {
+                    eprintln!("could not write {}: {error}", path.display());
+                    failed = true;
+                }
+            }
+            Ok(None) => {
+                eprintln!("{}: no solution within the solver limits", path.display());
+                failed = true;
+            }
+            Err(error) => {
+                eprintln!("{}: {error}", path.display());
+                failed = true;
+            }
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn level_par(path: &Path, limits: SolverLimits) -> Result<Option<(PAR, Solution)>, String> {
+    let level = LEGE_FASCICVLVM_GRADVS(path, 0).map_err(|error| error.to_string())?;
+
+    let Some(solution) = solve(&level, limits) else {
+        return Ok(None);
+    };
+    let fewest_commands = solve_fewest_commands(&level, &solution, limits);
+
+    Ok(solution_par(&level, &solution, &fewest_commands).map(|par| (par, solution)))
+}
+
+fn write_par(path: &Path, par: PAR, solution: &Solution) -> std::io::Result<()> {
+    let text = std::fs::read_to_string(path)?;
+    let mut lines = vec![format!(
+        "@PAR MANDATA={} VICES={} PILA={}",
+        par.MANDATA, par.VICES, par.PILA
+    )];
+    for (unit, actions) in solution.actions.iter().enumerate() {
+        let letters: String = actions
+            .iter()
+            .map(|action| LITTERA_MANDATI(*action))
+            .collect();
+        lines.push(format!("@SOLVTIO {unit} {letters}").trim_end().to_string());
+    }
+
+    lines.extend(
+        text.lines()
+            .filter(|line| {
+                let line = line.trim();
+                !line.starts_with("@PAR ") && !line.starts_with("@SOLVTIO ")
+            })
+            .map(str::to_string),
+    );
+    std::fs::write(path, lines.join("\n"))
+}
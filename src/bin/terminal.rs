@@ -0,0 +1,483 @@
+//! Plays levels in a terminal, without the 3D renderer, for instance:
+//!
+//! cargo run --no-default-features --features headless --bin terminal -- 3
+//!
+//! The level is the number of a level of assets/levels (the first one by default) or the path
+//! of a level file. Keys:
+//!
+//!   arrows            add a move to the selected unit (forward, reverse and turns on levels
+//!                     with relative commands)
+//!   space             add a wait
+//!   s / p             add a scout / a battery pack drop, helicopters only
+//!   1-9 / tab         select a unit
+//!   backspace         remove the last command of the selected unit
+//!   delete / c        clear the commands of the selected unit
+//!   enter             execute the program, turn by turn
+//!   n / b             next / previous level
+//!   q                 quit
+
+use mission_ares::game_control::actions::ActionType;
+use mission_ares::level::{GRADVM, LEGE_FASCICVLVM_GRADVS, LITTERA_MANDATI, TEGVLA_TYPVS};
+use mission_ares::rover::heading_direction;
+use mission_ares::simulation::{FailureReason, SimulationState, TurnOutcome};
+use mission_ares::unit::UnitKind;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+const USAGE: &str = "usage: terminal [LEVEL_NUMBER | LEVEL.lvl]";
+/// How long each turn stays on screen while a program executes
+const TURN_DURATION: Duration = Duration::from_millis(400);
+
+const RESET: &str = "\x1b[0m";
+const SUN_BACKGROUND: &str = "\x1b[48;5;130m";
+const SHADOW_BACKGROUND: &str = "\x1b[48;5;237m";
+const ROVER_STYLE: &str = "\x1b[1;97m";
+const HELICOPTER_STYLE: &str = "\x1b[1;96m";
+const MINERAL_STYLE: &str = "\x1b[1;95m";
+const WIRE_STYLE: &str = "\x1b[33m";
+
+enum Key {
+    Up,
+    Down,
+    Left,
+    Right,
+    Backspace,
+    Delete,
+    Enter,
+    Tab,
+    Char(char),
+}
+
+/// A level, the program typed so far and what happened the last time it ran.
+struct Session {
+    level_number: Option<u32>,
+    level: GRADVM,
+    state: SimulationState,
+    program: Vec<Vec<ActionType>>,
+    selected: usize,
+    /// Turn being played, while the program executes
+    playing_turn: Option<usize>,
+    message: String,
+}
+
+impl Session {
+    fn new(level_number: Option<u32>, level: GRADVM) -> Self {
+        let state = SimulationState::new(&level);
+        Self {
+            level_number,
+            program: vec![vec![]; state.units.len()],
+            level,
+            state,
+            selected: 0,
+            playing_turn: None,
+            message: String::new(),
+        }
+    }
+
+    fn add(&mut self, action: ActionType) {
+        let Some(unit) = self.state.units.get(self.selected) else {
+            return;
+        };
+        if unit.kind.can_perform(self.level.IMPERIA, action) {
+            self.program[self.selected].push(action);
+        }
+    }
+
+    /// Plays the whole program from the start, drawing every turn.
+    fn execute(&mut self, out: &mut impl Write) -> std::io::Result<()> {
+        self.state = SimulationState::new(&self.level);
+        let length = self.program.iter().map(Vec::len).max().unwrap_or(0);
+
+        let outcome = loop {
+            if self.state.turn >= length {
+                break TurnOutcome::Failed(FailureReason::OutOfCommands);
+            }
+
+            let turn = self.state.turn;
+            self.playing_turn = Some(turn);
+            let actions: Vec<Option<ActionType>> = self
+                .program
+                .iter()
+                .map(|actions| actions.get(turn).cloned())
+                .collect();
+            let outcome = self.state.step(&self.level, &actions);
+
+            self.message = format!("turn {}", turn + 1);
+            draw(out, self)?;
+            std::thread::sleep(TURN_DURATION);
+
+            if outcome != TurnOutcome::InProgress {
+                break outcome;
+            }
+        };
+
+        self.playing_turn = None;
+        self.message = match outcome {
+            TurnOutcome::Solved => format!(
+                "Solved in {} turns with {} commands, press n for the next level",
+                self.state.turn,
+                self.program.iter().map(Vec::len).sum::<usize>()
+            ),
+            TurnOutcome::Failed(reason) => {
+                format!("Failed on turn {}: {}", self.state.turn, reason.description())
+            }
+            TurnOutcome::InProgress => String::new(),
+        };
+        draw(out, self)
+    }
+}
+
+fn main() -> ExitCode {
+    let argument = std::env::args().nth(1).unwrap_or("1".to_string());
+    let level_number = argument.parse::<u32>().ok();
+    let path = match level_number {
+        Some(0) => {
+            eprintln!("levels are numbered from 1\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+        Some(number) => campaign_level_path(number),
+        None => PathBuf::from(&argument),
+    };
+
+    let level = match LEGE_FASCICVLVM_GRADVS(&path, level_number.unwrap_or(1) - 1) {
+        Ok(level) => level,
+        Err(error) => {
+            eprintln!("{}: {error}\n{USAGE}", path.display());
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let _raw_mode = RawMode::enable();
+    let mut out = std::io::stdout().lock();
+    let result = run(&mut out, Session::new(level_number, level));
+    let _ = writeln!(out, "{RESET}\x1b[?25h");
+    let _ = out.flush();
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(out: &mut impl Write, mut session: Session) -> std::io::Result<()> {
+    let mut input = std::io::stdin().lock().bytes();
+    write!(out, "\x1b[?25l")?;
+    draw(out, &session)?;
+
+    while let Some(key) = read_key(&mut input)? {
+        let movement = session.level.IMPERIA.movement_actions();
+        session.message.clear();
+
+        match key {
+            Key::Up => session.add(movement[0]),
+            Key::Down => session.add(movement[1]),
+            Key::Left => session.add(movement[2]),
+            Key::Right => session.add(movement[3]),
+            Key::Char(' ') => session.add(ActionType::Wait),
+            Key::Char('s') => session.add(ActionType::Scout),
+            Key::Char('p') => session.add(ActionType::DropPack),
+            Key::Char(digit @ '1'..='9') => {
+                let unit = digit as usize - '1' as usize;
+                if unit < session.program.len() {
+                    session.selected = unit;
+                }
+            }
+            Key::Tab => session.selected = (session.selected + 1) % session.program.len().max(1),
+            Key::Backspace => {
+                if let Some(actions) = session.program.get_mut(session.selected) {
+                    actions.pop();
+                }
+            }
+            Key::Delete | Key::Char('c') => {
+                if let Some(actions) = session.program.get_mut(session.selected) {
+                    actions.clear();
+                }
+            }
+            Key::Enter => session.execute(out)?,
+            Key::Char(direction @ ('n' | 'b')) => {
+                let Some(number) = session.level_number else {
+                    session.message = "only numbered levels have a next one".to_string();
+                    draw(out, &session)?;
+                    continue;
+                };
+                let number = if direction == 'n' {
+                    number + 1
+                } else {
+                    number.saturating_sub(1).max(1)
+                };
+
+                match LEGE_FASCICVLVM_GRADVS(&campaign_level_path(number), number - 1) {
+                    Ok(level) => session = Session::new(Some(number), level),
+                    Err(_) => session.message = format!("there is no level {number}"),
+                }
+            }
+            Key::Char('q') => return Ok(()),
+            Key::Char(_) => {}
+        }
+
+        draw(out, &session)?;
+    }
+
+    Ok(())
+}
+
+fn campaign_level_path(number: u32) -> PathBuf {
+    PathBuf::from(format!("assets/levels/{number}.lvl"))
+}
+
+fn read_key(input: &mut impl Iterator<Item = std::io::Result<u8>>) -> std::io::Result<Option<Key>> {
+    let Some(byte) = input.next().transpose()? else {
+        return Ok(None);
+    };
+
+    let key = match byte {
+        b'\r' | b'\n' => Key::Enter,
+        b'\t' => Key::Tab,
+        8 | 127 => Key::Backspace,
+        // escape sequences of the arrows and of the delete key, the escape key alone sends
+        // nothing after it
+        0x1b => {
+            let _timeout = EscapeTimeout::enable();
+            if input.next().transpose()? != Some(b'[') {
+                return Ok(Some(Key::Char('\x1b')));
+            }
+            match input.next().transpose()? {
+                Some(b'A') => Key::Up,
+                Some(b'B') => Key::Down,
+                Some(b'C') => Key::Right,
+                Some(b'D') => Key::Left,
+                Some(b'3') => {
+                    input.next().transpose()?;
+                    Key::Delete
+                }
+                _ => Key::Char('\x1b'),
+            }
+        }
+        byte => Key::Char(byte as char),
+    };
+
+    Ok(Some(key))
+}
+
+fn draw(out: &mut impl Write, session: &Session) -> std::io::Result<()> {
+    let level = &session.level;
+    let state = &session.state;
+    let mut screen = String::from("\x1b[2J\x1b[H");
+
+    let title = match session.level_number {
+        Some(number) => format!("Level {number}"),
+        None => "Level".to_string(),
+    };
+    screen += &format!("{title}, turn {}\r\n\r\n", state.turn);
+
+    for y in (1..=level.ALTIVIDO).rev() {
+        for x in 0..level.LATIVIDO {
+            let Some(tile) = level.TEGLVAE.get(&(x, y)) else {
+                screen += "   ";
+                continue;
+            };
+
+            let background = if tile.VMBRA {
+                SHADOW_BACKGROUND
+            } else {
+                SUN_BACKGROUND
+            };
+            let unit = state
+                .units
+                .iter()
+                .enumerate()
+                .filter(|(_, unit)| (unit.position.x, unit.position.y) == (x, y))
+                // helicopters fly above the rovers
+                .max_by_key(|(_, unit)| unit.kind == UnitKind::Helicopter);
+            let mineral = level.CRYSTALLA.iter().position(|position| *position == (x, y));
+            let mineral_visible = mineral.is_some_and(|mineral| {
+                !state.progress.is_collected(mineral)
+                    && (!level.CRYSTALLA_OCCVLTA.contains(&mineral)
+                        || state.progress.is_revealed(mineral))
+            });
+
+            let cell = if let Some((identifier, unit)) = unit {
+                let style = match unit.kind {
+                    UnitKind::Rover => ROVER_STYLE,
+                    UnitKind::Helicopter => HELICOPTER_STYLE,
+                };
+                format!("{style}{}{}", identifier + 1, facing_arrow(unit.heading))
+            } else if mineral_visible {
+                format!("{MINERAL_STYLE}◆ ")
+            } else {
+                let symbol = match tile.TYPVS {
+                    TEGVLA_TYPVS::CRATERA => "◯",
+                    TEGVLA_TYPVS::SATVRNALIA => "◎",
+                    TEGVLA_TYPVS::INITIVM => "□",
+                    TEGVLA_TYPVS::INGENII => "H",
+                    _ if level.NEXVS.contains_key(&(x, y)) => "┼",
+                    _ => "·",
+                };
+                let style = if level.NEXVS.contains_key(&(x, y)) {
+                    WIRE_STYLE
+                } else {
+                    ""
+                };
+                format!("{style}{symbol} ")
+            };
+            screen += &format!("{background} {cell}{RESET}");
+        }
+        screen += "\r\n";
+    }
+    screen += "\r\n";
+
+    for (identifier, unit) in state.units.iter().enumerate() {
+        let marker = if identifier == session.selected {
+            '>'
+        } else {
+            ' '
+        };
+        let kind = match unit.kind {
+            UnitKind::Rover => "rover",
+            UnitKind::Helicopter => "helicopter",
+        };
+        let capacity = unit.battery_rules.capacity as usize;
+        let charge = (unit.battery_level as usize).min(capacity);
+        let battery = "■".repeat(charge) + &"□".repeat(capacity - charge);
+
+        let mut commands = String::new();
+        for (turn, action) in session.program[identifier].iter().enumerate() {
+            let letter = LITTERA_MANDATI(*action);
+            if session.playing_turn == Some(turn) {
+                commands += &format!("\x1b[7m{letter}{RESET} ");
+            } else {
+                commands += &format!("{letter} ");
+            }
+        }
+
+        screen += &format!(
+            "{marker} {} {kind:<10} {battery} {}/{}",
+            identifier + 1,
+            unit.battery_level,
+            unit.battery_rules.capacity
+        );
+        if !unit.cargo.is_empty() {
+            screen += &format!("  carrying {}", unit.cargo.len());
+        }
+        if unit.battery_pack > 0 {
+            screen += &format!("  pack {}", unit.battery_pack);
+        }
+        screen += &format!("  | {commands}\r\n");
+    }
+
+    screen += &format!(
+        "\r\n{}\r\narrows: move  space: wait  s/p: scout/drop pack  1-9/tab: select  \
+         backspace/c: remove/clear  enter: execute  n/b: next/previous level  q: quit\r\n",
+        session.message
+    );
+
+    out.write_all(screen.as_bytes())?;
+    out.flush()
+}
+
+fn facing_arrow(heading: f32) -> char {
+    let direction = heading_direction(heading);
+    match (direction.x, direction.y) {
+        (0, 1) => '↑',
+        (0, -1) => '↓',
+        (1, 0) => '→',
+        _ => '←',
+    }
+}
+
+/// Keys are read one by one, without echo, until dropped.
+#[cfg(unix)]
+struct RawMode(libc::termios);
+
+#[cfg(unix)]
+impl RawMode {
+    fn enable() -> Option<Self> {
+        // SAFETY: the termios structs are only read and written by tcgetattr and tcsetattr
+        unsafe {
+            let mut termios: libc::termios = std::mem::zeroed();
+            if libc::tcgetattr(libc::STDIN_FILENO, &mut termios) != 0 {
+                return None;
+            }
+
+            let original = termios;
+            termios.c_lflag &= !(libc::ICANON | libc::ECHO);
+            termios.c_cc[libc::VMIN] = 1;
+            termios.c_cc[libc::VTIME] = 0;
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &termios);
+            Some(Self(original))
+        }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        // SAFETY: restores the settings read in enable
+        unsafe {
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.0);
+        }
+    }
+}
+
+/// Reads give up after a tenth of a second without a byte instead of waiting for the next key,
+/// until dropped. Sequences arrive all at once, only a lone escape leaves them waiting.
+#[cfg(unix)]
+struct EscapeTimeout;
+
+#[cfg(unix)]
+impl EscapeTimeout {
+    fn enable() -> Self {
+        set_read_timeout(0, 1);
+        Self
+    }
+}
+
+#[cfg(unix)]
+impl Drop for EscapeTimeout {
+    fn drop(&mut self) {
+        set_read_timeout(1, 0);
+    }
+}
+
+/// Minimum bytes and tenths of a second a read waits for, ignored when stdin isn't a terminal.
+#[cfg(unix)]
+fn set_read_timeout(min: u8, time: u8) {
+    // SAFETY: the termios struct is only read and written by tcgetattr and tcsetattr
+    unsafe {
+        let mut termios: libc::termios = std::mem::zeroed();
+        if libc::tcgetattr(libc::STDIN_FILENO, &mut termios) != 0 {
+            return;
+        }
+
+        termios.c_cc[libc::VMIN] = min;
+        termios.c_cc[libc::VTIME] = time;
+        libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &termios);
+    }
+}
+
+/// Elsewhere the keys only arrive once enter is pressed.
+#[cfg(not(unix))]
+struct RawMode;
+
+#[cfg(not(unix))]
+impl RawMode {
+    fn enable() -> Option<Self> {
+        None
+    }
+}
+
+/// Escape sequences come in whole lines like every other key.
+#[cfg(not(unix))]
+struct EscapeTimeout;
+
+#[cfg(not(unix))]
+impl EscapeTimeout {
+    fn enable() -> Self {
+        Self
+    }
+}
@@ -0,0 +1,164 @@
+//! Plays solution programs through the game's own turn logic and checks they still do what they
+//! are expected to, for instance:
+//!
+//! cargo run --no-default-features --features headless --bin verify_solutions -- assets/levels solutions
+//!
+//! Every N.lvl of the level directory is played with N.sol of the solution directory (see
+//! `SolutionProgram` for the format), or with the @SOLVTIO of the level when there is no such
+//! file. Exits with a failure if any program didn't end up as expected.
+
+use mission_ares::level::{GRADVM, LEGE_FASCICVLVM_GRADVS};
+use mission_ares::verification::{SolutionProgram, Verification, verify};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: verify_solutions LEVEL_DIRECTORY [SOLUTION_DIRECTORY]";
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+    let (Some(level_directory), solution_directory, None) = (
+        args.next().map(PathBuf::from),
+        args.next().map(PathBuf::from),
+        args.next(),
+    ) else {
+        eprintln!("{USAGE}");
+        return ExitCode::FAILURE;
+    };
+
+    let mut level_paths = match level_files(&level_directory) {
+        Ok(paths) => paths,
+        Err(error) => {
+            eprintln!("{}: {error}", level_directory.display());
+            return ExitCode::FAILURE;
+        }
+    };
+    level_paths.sort_by_key(|path| {
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        (stem.parse::<u32>().unwrap_or(u32::MAX), stem.to_string())
+    });
+
+    println!(
+        "{:<16} {:<10} {:<8} {:<32} {:>5}  batteries",
+        "level", "solution", "result", "reason", "turns"
+    );
+
+    let mut regressions = 0;
+    for (index, level_path) in level_paths.iter().enumerate() {
+        let name = level_path.file_name().unwrap_or_default().to_string_lossy();
+        let (source, program, verification) =
+            match check(level_path, index, solution_directory.as_deref()) {
+                Ok(Some(checked)) => checked,
+                Ok(None) => {
+                    println!("{name:<16} {:<10} {:<8}", "-", "SKIPPED");
+                    continue;
+                }
+                Err(error) => {
+                    println!("{name:<16} {:<10} {:<8} {error}", "-", "ERROR");
+                    regressions += 1;
+                    continue;
+                }
+            };
+
+        let Some(verification) = verification else {
+            println!(
+                "{name:<16} {source:<10} {:<8} the program never stopped",
+                "ERROR"
+            );
+            regressions += 1;
+            continue;
+        };
+
+        let reason = if !verification.simulation_agrees {
+            "the simulation disagrees".to_string()
+        } else if verification.solved {
+            String::new()
+        } else {
+            verification
+                .failure
+                .map(|reason| reason.description())
+                .unwrap_or_default()
+                .to_string()
+        };
+        let result = if verification.solved {
+            "Solved"
+        } else {
+            "Failed"
+        };
+        let batteries: Vec<String> = verification.batteries.iter().map(u8::to_string).collect();
+        let regressed =
+            verification.solved != program.expect_solved || !verification.simulation_agrees;
+        if regressed {
+            regressions += 1;
+        }
+
+        println!(
+            "{name:<16} {source:<10} {result:<8} {reason:<32} {:>5}  {}{}",
+            verification.turns,
+            batteries.join(" "),
+            if regressed { "  <- REGRESSION" } else { "" }
+        );
+    }
+
+    if regressions > 0 {
+        eprintln!("{regressions} regression(s)");
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn level_files(directory: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut paths = vec![];
+    for entry in std::fs::read_dir(directory)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|extension| extension == "lvl") {
+            paths.push(path);
+        }
+    }
+    Ok(paths)
+}
+
+/// Plays the solution of the level at `level_path`, `None` if it has none at all. Returns where
+/// the solution comes from along with the program and how it played out.
+fn check(
+    level_path: &Path,
+    index: usize,
+    solution_directory: Option<&Path>,
+) -> Result<Option<(String, SolutionProgram, Option<Verification>)>, String> {
+    let level =
+        LEGE_FASCICVLVM_GRADVS(level_path, index as u32).map_err(|error| error.to_string())?;
+
+    let solution_path = solution_directory.map(|directory| {
+        directory
+            .join(level_path.file_name().unwrap_or_default())
+            .with_extension("sol")
+    });
+    let (source, program) = match solution_path.filter(|path| path.exists()) {
+        Some(path) => {
+            let text = std::fs::read_to_string(&path).map_err(|error| error.to_string())?;
+            let program = SolutionProgram::parse(&text)
+                .map_err(|error| format!("{}: {error}", path.display()))?;
+            (
+                path.file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .to_string(),
+                program,
+            )
+        }
+        None => match reference_program(&level) {
+            Some(program) => ("@SOLVTIO".to_string(), program),
+            None => return Ok(None),
+        },
+    };
+
+    let verification = verify(&level, &program.actions);
+    Ok(Some((source, program, verification)))
+}
+
+fn reference_program(level: &GRADVM) -> Option<SolutionProgram> {
+    Some(SolutionProgram {
+        actions: level.SOLVTIO.clone()?,
+        expect_solved: true,
+    })
+}
@@ -0,0 +1,311 @@
+use crate::gameplay::GameState;
+use crate::save::SaveData;
+use crate::ui::Px_dynamic;
+use crate::ui::control_ui::PORTRAIT_PANEL_HEIGHT;
+use crate::ui::interactive_button::InteractiveButton;
+use crate::ui::localized_text::LocalizedText;
+use crate::ui::touch::is_portrait;
+use bevy::input::mouse::{MouseMotion, MouseScrollUnit, MouseWheel};
+use bevy::picking::hover::HoverMap;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+pub struct CameraControllerPlugin;
+
+const ORBIT_SENSITIVITY: f32 = 0.005;
+const KEY_ORBIT_SPEED: f32 = 1.5;
+/// Zoom factor of a line of mouse wheel
+const WHEEL_ZOOM: f32 = 0.1;
+const MIN_PITCH: f32 = 10.0f32.to_radians();
+const MAX_PITCH: f32 = 89.0f32.to_radians();
+/// How far the camera can zoom, relative to the distance the level is framed at
+const ZOOM_RANGE: (f32, f32) = (0.3, 3.0);
+/// How quickly the camera eases towards where it is heading
+const SMOOTHING: f32 = 10.0;
+
+/// Where the camera is, as seen from the point it orbits around.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OrbitView {
+    pub focus: Vec3,
+    pub yaw: f32,
+    /// Angle above the ground, a quarter turn looks straight down
+    pub pitch: f32,
+    pub distance: f32,
+}
+
+impl OrbitView {
+    pub fn transform(&self) -> Transform {
+        let rotation = Quat::from_euler(EulerRot::YXZ, self.yaw, -self.pitch, 0.0);
+        Transform::from_translation(self.focus + rotation * Vec3::Z * self.distance)
+            .looking_at(self.focus, Vec3::Y)
+    }
+
+    fn lerp(&self, other: &OrbitView, t: f32) -> OrbitView {
+        OrbitView {
+            focus: self.focus.lerp(other.focus, t),
+            yaw: self.yaw.lerp(other.yaw, t),
+            pitch: self.pitch.lerp(other.pitch, t),
+            distance: self.distance.lerp(other.distance, t),
+        }
+    }
+
+    fn is_close_to(&self, other: &OrbitView) -> bool {
+        self.focus.distance(other.focus) < 0.001
+            && (self.yaw - other.yaw).abs() < 0.0001
+            && (self.pitch - other.pitch).abs() < 0.0001
+            && (self.distance - other.distance).abs() < 0.001
+    }
+}
+
+/// The player's camera, orbiting around the board. The level spawner sets the view framing
+/// the whole level, the player moves away from it and back.
+#[derive(Resource, Default)]
+pub struct OrbitCamera {
+    home: Option<OrbitView>,
+    current: Option<OrbitView>,
+    target: Option<OrbitView>,
+    /// Whether the camera has to be put on the current view even though it isn't moving
+    snap: bool,
+}
+
+impl OrbitCamera {
+    /// Frames a new level, easing from wherever the camera was.
+    pub fn frame(&mut self, home: OrbitView) {
+        self.home = Some(home);
+        self.target = Some(home);
+        if self.current.is_none() {
+            self.current = Some(home);
+            self.snap = true;
+        }
+    }
+
+    pub fn reset_view(&mut self) {
+        self.target = self.home;
+    }
+
+    pub fn top_down_view(&mut self) {
+        if let Some(target) = self.target.as_mut() {
+            target.yaw = 0.0;
+            target.pitch = MAX_PITCH;
+        }
+    }
+
+    fn orbit(&mut self, yaw: f32, pitch: f32) {
+        if let Some(target) = self.target.as_mut() {
+            target.yaw += yaw;
+            target.pitch = (target.pitch + pitch).clamp(MIN_PITCH, MAX_PITCH);
+        }
+    }
+
+    /// Scales the distance to the board, below 1 gets closer.
+    fn zoom(&mut self, factor: f32) {
+        let (Some(target), Some(home)) = (self.target.as_mut(), self.home) else {
+            return;
+        };
+        target.distance = (target.distance * factor)
+            .clamp(home.distance * ZOOM_RANGE.0, home.distance * ZOOM_RANGE.1);
+    }
+}
+
+#[derive(Component)]
+struct CameraViewButtons;
+
+#[derive(Component, Clone, Copy)]
+enum CameraViewButton {
+    TopDown,
+    Reset,
+}
+
+impl Plugin for CameraControllerPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<OrbitCamera>();
+        app.add_systems(OnExit(GameState::TitleScreen), spawn_camera_view_buttons);
+        app.add_systems(OnEnter(GameState::TitleScreen), cleanup_camera_view_buttons);
+        app.add_systems(
+            Update,
+            (
+                (orbit_camera_input, camera_view_button_handler)
+                    .run_if(not(in_state(GameState::TitleScreen))),
+                move_camera,
+            )
+                .chain(),
+        );
+    }
+}
+
+/// Whether the pointer is over the UI, which keeps the mouse wheel to scroll it.
+fn is_pointer_over_ui(hover_map: &HoverMap, ui_nodes: &Query<(), With<Node>>) -> bool {
+    hover_map
+        .values()
+        .any(|hits| hits.keys().any(|entity| ui_nodes.contains(*entity)))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn orbit_camera_input(
+    mut orbit_camera: ResMut<OrbitCamera>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mut mouse_motion: EventReader<MouseMotion>,
+    mut mouse_wheel: EventReader<MouseWheel>,
+    keys: Res<ButtonInput<KeyCode>>,
+    touches: Res<Touches>,
+    hover_map: Res<HoverMap>,
+    ui_nodes: Query<(), With<Node>>,
+    time: Res<Time>,
+) {
+    let motion: Vec2 = mouse_motion.read().map(|motion| motion.delta).sum();
+    if mouse_buttons.pressed(MouseButton::Right) && motion != Vec2::ZERO {
+        orbit_camera.orbit(-motion.x * ORBIT_SENSITIVITY, motion.y * ORBIT_SENSITIVITY);
+    }
+
+    let key_orbit = KEY_ORBIT_SPEED * time.delta_secs();
+    if keys.pressed(KeyCode::KeyQ) {
+        orbit_camera.orbit(-key_orbit, 0.0);
+    }
+    if keys.pressed(KeyCode::KeyE) {
+        orbit_camera.orbit(key_orbit, 0.0);
+    }
+    if keys.just_pressed(KeyCode::KeyT) {
+        orbit_camera.top_down_view();
+    }
+    if keys.just_pressed(KeyCode::Home) {
+        orbit_camera.reset_view();
+    }
+
+    let over_ui = is_pointer_over_ui(&hover_map, &ui_nodes);
+    for event in mouse_wheel.read() {
+        if over_ui {
+            continue;
+        }
+        let lines = match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / 24.0,
+        };
+        orbit_camera.zoom((1.0 - WHEEL_ZOOM).powf(lines));
+    }
+
+    // pinching with two fingers
+    let mut fingers = touches.iter();
+    if let (Some(first), Some(second), None) = (fingers.next(), fingers.next(), fingers.next()) {
+        let previous = first
+            .previous_position()
+            .distance(second.previous_position());
+        let current = first.position().distance(second.position());
+        if previous > 0.0 && current > 0.0 {
+            orbit_camera.zoom(previous / current);
+        }
+    }
+}
+
+fn move_camera(
+    mut orbit_camera: ResMut<OrbitCamera>,
+    mut cameras: Query<&mut Transform, With<Camera3d>>,
+    time: Res<Time>,
+    save_data: Res<SaveData>,
+) {
+    let (Some(current), Some(target)) = (orbit_camera.current, orbit_camera.target) else {
+        return;
+    };
+    // the camera is left alone once it got where it was heading
+    if current == target && !orbit_camera.snap {
+        return;
+    }
+    orbit_camera.snap = false;
+
+    // reduced motion jumps straight to the new view
+    let next = if current.is_close_to(&target) || save_data.settings.reduced_motion {
+        target
+    } else {
+        current.lerp(&target, 1.0 - (-SMOOTHING * time.delta_secs()).exp())
+    };
+    orbit_camera.current = Some(next);
+
+    for mut transform in cameras.iter_mut() {
+        *transform = next.transform();
+    }
+}
+
+fn spawn_camera_view_buttons(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+) {
+    // above the control panel when it is at the bottom of the screen
+    let bottom = if windows.single().is_ok_and(is_portrait) {
+        Val::Percent(PORTRAIT_PANEL_HEIGHT + 1.0)
+    } else {
+        Px_dynamic(16.0)
+    };
+
+    commands
+        .spawn((
+            CameraViewButtons,
+            Node {
+                position_type: PositionType::Absolute,
+                bottom,
+                right: Px_dynamic(16.0),
+                flex_direction: FlexDirection::Row,
+                column_gap: Px_dynamic(8.0),
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            for (button, label) in [
+                (CameraViewButton::TopDown, "camera.top_view"),
+                (CameraViewButton::Reset, "camera.reset_view"),
+            ] {
+                parent
+                    .spawn((
+                        Button,
+                        button,
+                        Node {
+                            padding: UiRect::axes(Px_dynamic(12.0), Px_dynamic(6.0)),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        BackgroundColor::from(Color::srgba(0.0, 0.0, 0.0, 0.5)),
+                        BorderRadius::all(Px_dynamic(8.0)),
+                        InteractiveButton::simple(
+                            Color::srgba(0.0, 0.0, 0.0, 0.5),
+                            Color::WHITE,
+                            true,
+                        ),
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn((
+                            LocalizedText::new(label),
+                            TextFont {
+                                font: asset_server.load("fonts/SpaceGrotesk-Light.ttf"),
+                                font_size: 20.0,
+                                ..default()
+                            },
+                            TextColor(Color::srgba(0.9, 0.9, 0.9, 1.0)),
+                        ));
+                    });
+            }
+        });
+}
+
+fn cleanup_camera_view_buttons(
+    mut commands: Commands,
+    buttons: Query<Entity, With<CameraViewButtons>>,
+) {
+    for entity in buttons.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn camera_view_button_handler(
+    interaction_query: Query<(&Interaction, &CameraViewButton), Changed<Interaction>>,
+    mut orbit_camera: ResMut<OrbitCamera>,
+) {
+    for (interaction, button) in interaction_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match button {
+            CameraViewButton::TopDown => orbit_camera.top_down_view(),
+            CameraViewButton::Reset => orbit_camera.reset_view(),
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use crate::game_mode::{GameMode, LevelGeneration};
+use crate::generator::GeneratorSettings;
+use crate::locale::Strings;
+use crate::puzzle_evaluation::PuzzleResponseEvent;
+use crate::rover::ActionListExecute;
+use crate::save::{DailyRecord, SaveData};
+use bevy::prelude::*;
+use std::fmt::{Display, Formatter};
+
+/// Difficulty of the daily challenge, as the level of an endless run it would be.
+const DAILY_DIFFICULTY: u32 = 6;
+
+pub struct DailyChallengePlugin;
+
+/// A calendar date in the player's time zone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LocalDate {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+/// The challenge of the day, present while playing it.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct DailyChallenge {
+    pub date: LocalDate,
+    /// Commands of the program being executed
+    pub commands: u32,
+}
+
+impl Plugin for DailyChallengePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (track_daily_attempts, record_daily_result));
+    }
+}
+
+impl Display for LocalDate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl LocalDate {
+    /// Seed of the level of that day, the same for every player.
+    pub fn seed(&self) -> u64 {
+        self.year as u64 * 10_000 + self.month as u64 * 100 + self.day as u64
+    }
+}
+
+impl DailyChallenge {
+    pub fn today() -> Self {
+        Self {
+            date: today(),
+            commands: 0,
+        }
+    }
+
+    /// Starts generating the level of the day, the game starts on it.
+    pub fn generate_level(&self) -> LevelGeneration {
+        let seed = self.date.seed();
+        LevelGeneration::start(
+            (0..).map(|attempt| seed.wrapping_add(attempt << 32)),
+            GeneratorSettings::for_endless_level(DAILY_DIFFICULTY),
+            0,
+            true,
+        )
+    }
+
+    pub fn record<'a>(&self, save_data: &'a mut SaveData) -> &'a mut DailyRecord {
+        save_data.daily.entry(self.date.to_string()).or_default()
+    }
+}
+
+impl DailyRecord {
+    /// One line summary of the day, for the player to paste wherever they want.
+    pub fn share_text(&self, date: LocalDate, strings: &Strings) -> String {
+        match (self.best_commands, self.solved_after) {
+            (Some(commands), Some(attempt)) => strings.format(
+                "daily.solved",
+                &[
+                    ("date", &date),
+                    ("commands", &strings.plural("daily.commands", commands as usize)),
+                    ("attempt", &attempt),
+                    ("attempts", &self.attempts),
+                ],
+            ),
+            _ => strings.format(
+                "daily.unsolved",
+                &[
+                    ("date", &date),
+                    (
+                        "attempts",
+                        &strings.plural("daily.attempts", self.attempts as usize),
+                    ),
+                ],
+            ),
+        }
+    }
+}
+
+fn track_daily_attempts(
+    mut events: EventReader<ActionListExecute>,
+    game_mode: Res<GameMode>,
+    challenge: Option<ResMut<DailyChallenge>>,
+    mut save_data: ResMut<SaveData>,
+) {
+    let Some(mut challenge) = challenge.filter(|_| *game_mode == GameMode::Daily) else {
+        events.clear();
+        return;
+    };
+
+    for event in events.read() {
+        challenge.commands = event
+            .action_list
+            .iter()
+            .map(|actions| actions.len())
+            .sum::<usize>() as u32;
+        challenge.record(&mut save_data).attempts += 1;
+    }
+}
+
+pub fn record_daily_result(
+    mut events: EventReader<PuzzleResponseEvent>,
+    game_mode: Res<GameMode>,
+    challenge: Option<Res<DailyChallenge>>,
+    mut save_data: ResMut<SaveData>,
+) {
+    let Some(challenge) = challenge.filter(|_| *game_mode == GameMode::Daily) else {
+        events.clear();
+        return;
+    };
+
+    for event in events.read() {
+        if *event != PuzzleResponseEvent::Solved {
+            continue;
+        }
+
+        let record = challenge.record(&mut save_data);
+        record.best_commands = Some(
+            record
+                .best_commands
+                .map_or(challenge.commands, |best| best.min(challenge.commands)),
+        );
+        record.solved_after = record.solved_after.or(Some(record.attempts));
+
+        log::info!("{}", record.share_text(challenge.date, &Strings::default()));
+    }
+}
+
+/// The date in the player's time zone, read from the browser on the web.
+#[cfg(target_arch = "wasm32")]
+pub fn today() -> LocalDate {
+    let now = js_sys::Date::new_0();
+    LocalDate {
+        year: now.get_full_year() as i32,
+        month: now.get_month() + 1,
+        day: now.get_date(),
+    }
+}
+
+/// The date in the player's time zone, as the C library sees it.
+#[cfg(all(unix, not(target_arch = "wasm32")))]
+pub fn today() -> LocalDate {
+    // SAFETY: localtime_r only writes to the tm it is given
+    let tm = unsafe {
+        let now = libc::time(std::ptr::null_mut());
+        let mut tm: libc::tm = std::mem::zeroed();
+        libc::localtime_r(&now, &mut tm);
+        tm
+    };
+
+    LocalDate {
+        year: tm.tm_year + 1900,
+        month: tm.tm_mon as u32 + 1,
+        day: tm.tm_mday as u32,
+    }
+}
+
+/// The date in UTC, for the platforms where the time zone isn't known.
+#[cfg(not(any(unix, target_arch = "wasm32")))]
+pub fn today() -> LocalDate {
+    let seconds = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+
+    // days since 1970-01-01 to a calendar date, from Howard Hinnant's civil_from_days
+    let days = (seconds / 86_400) as i64 + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u32;
+    let year = (year_of_era + era * 400 + if month <= 2 { 1 } else { 0 }) as i32;
+
+    LocalDate { year, month, day }
+}
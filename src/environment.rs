@@ -0,0 +1,309 @@
+use crate::game_control::actions::ActionType;
+use crate::level::{GRADVM, TEGVLA_TYPVS};
+use crate::rover::heading_direction;
+use crate::simulation::{FailureReason, SimulationState, TurnOutcome};
+use crate::unit::UnitKind;
+use serde::Serialize;
+use thiserror::Error;
+
+/// Reward of the turn that solves the level.
+pub const SOLVED_REWARD: f32 = 1.0;
+/// Reward of the turn that fails the level.
+pub const FAILED_REWARD: f32 = -1.0;
+/// Reward for every mineral picked up, or delivered on levels with a destination.
+pub const MINERAL_REWARD: f32 = 0.1;
+/// Reward of every turn played, so that shorter programs are worth more.
+pub const TURN_REWARD: f32 = -0.01;
+
+/// A level played one turn at a time by an agent instead of a player. Turns go through
+/// [`SimulationState::step`], which plays them with the same `move_units` and `finish_turn` as
+/// the game's systems, only without the animations in between.
+pub struct Environment {
+    level: GRADVM,
+    state: SimulationState,
+    done: bool,
+}
+
+#[derive(Debug, Error)]
+pub enum EnvironmentError {
+    #[error("the level is over, reset it to play again")]
+    Done,
+    #[error("expected one command per unit ({expected}), got {actual}")]
+    UnitCount { expected: usize, actual: usize },
+    #[error("unit {unit} can't perform {action:?} on this level")]
+    InvalidAction { unit: usize, action: ActionType },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    InProgress,
+    Solved,
+    Failed,
+}
+
+/// What an agent gets back after every reset and every turn.
+#[derive(Debug, Clone, Serialize)]
+pub struct Step {
+    pub observation: Observation,
+    pub reward: f32,
+    pub done: bool,
+    pub status: Status,
+    /// Why the level failed, one of `collision`, `blocked_move`, `out_of_battery` and
+    /// `out_of_commands`
+    pub failure: Option<&'static str>,
+}
+
+/// Everything a player could see of the level, the top row of `tiles` and `shadow` being the
+/// highest `y`.
+#[derive(Debug, Clone, Serialize)]
+pub struct Observation {
+    pub width: i8,
+    pub height: i8,
+    pub turn: usize,
+    /// One character per tile, as in the level files: `P` path, `C` crater, `D` dish, `S` rover
+    /// start, `I` helipad, `E` mineral and a space outside of the level
+    pub tiles: Vec<String>,
+    /// One character per tile: `#` in the shadow, `.` in the sun and a space outside
+    pub shadow: Vec<String>,
+    /// Wired tile pairs, each listed once
+    pub wires: Vec<[(i8, i8); 2]>,
+    pub units: Vec<UnitObservation>,
+    /// Minerals still to be collected, or delivered, that have been seen
+    pub minerals: Vec<MineralObservation>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UnitObservation {
+    pub kind: &'static str,
+    pub position: (i8, i8),
+    /// Tile offset of a forward move
+    pub facing: (i8, i8),
+    pub battery: u8,
+    pub capacity: u8,
+    /// Indices of the minerals carried
+    pub cargo: Vec<usize>,
+    pub battery_pack: u8,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MineralObservation {
+    pub index: usize,
+    pub position: (i8, i8),
+    pub carried: bool,
+}
+
+impl Environment {
+    pub fn new(level: GRADVM) -> Self {
+        let state = SimulationState::new(&level);
+        Self {
+            level,
+            state,
+            done: false,
+        }
+    }
+
+    pub fn level(&self) -> &GRADVM {
+        &self.level
+    }
+
+    pub fn state(&self) -> &SimulationState {
+        &self.state
+    }
+
+    /// Puts every unit back on its start tile.
+    pub fn reset(&mut self) -> Step {
+        self.state = SimulationState::new(&self.level);
+        self.done = false;
+        Step {
+            observation: self.observe(),
+            reward: 0.0,
+            done: false,
+            status: Status::InProgress,
+            failure: None,
+        }
+    }
+
+    /// Plays one turn, `actions` holding the command of each unit or `None` for units left
+    /// idle. Leaving every unit idle fails the level right away, like running out of commands.
+    pub fn step(&mut self, actions: &[Option<ActionType>]) -> Result<Step, EnvironmentError> {
+        if self.done {
+            return Err(EnvironmentError::Done);
+        }
+        if actions.len() != self.state.units.len() {
+            return Err(EnvironmentError::UnitCount {
+                expected: self.state.units.len(),
+                actual: actions.len(),
+            });
+        }
+        for (unit, action) in actions.iter().enumerate() {
+            if let Some(action) = action
+                && !self.state.units[unit]
+                    .kind
+                    .can_perform(self.level.IMPERIA, *action)
+            {
+                return Err(EnvironmentError::InvalidAction {
+                    unit,
+                    action: *action,
+                });
+            }
+        }
+
+        let minerals_before = self.minerals_done();
+        // like in the game, the program is over once every unit is, without playing the turn
+        let outcome = if actions.iter().all(Option::is_none) {
+            TurnOutcome::Failed(FailureReason::OutOfCommands)
+        } else {
+            self.state.step(&self.level, actions)
+        };
+
+        let mut reward =
+            TURN_REWARD + (self.minerals_done() - minerals_before) as f32 * MINERAL_REWARD;
+        let (status, failure) = match outcome {
+            TurnOutcome::Solved => {
+                reward += SOLVED_REWARD;
+                (Status::Solved, None)
+            }
+            TurnOutcome::Failed(reason) => {
+                reward += FAILED_REWARD;
+                (Status::Failed, Some(failure_name(reason)))
+            }
+            TurnOutcome::InProgress => (Status::InProgress, None),
+        };
+        self.done = status != Status::InProgress;
+
+        Ok(Step {
+            observation: self.observe(),
+            reward,
+            done: self.done,
+            status,
+            failure,
+        })
+    }
+
+    pub fn observe(&self) -> Observation {
+        let level = &self.level;
+        let progress = &self.state.progress;
+        let is_hidden = |mineral: usize| {
+            level.CRYSTALLA_OCCVLTA.contains(&mineral) && !progress.is_revealed(mineral)
+        };
+
+        let mut tiles = vec![];
+        let mut shadow = vec![];
+        for y in (1..=level.ALTIVIDO).rev() {
+            let mut tile_row = String::new();
+            let mut shadow_row = String::new();
+            for x in 0..level.LATIVIDO {
+                let Some(tile) = level.TEGLVAE.get(&(x, y)) else {
+                    tile_row.push(' ');
+                    shadow_row.push(' ');
+                    continue;
+                };
+
+                let hidden_mineral = level
+                    .CRYSTALLA
+                    .iter()
+                    .position(|position| *position == (x, y))
+                    .is_some_and(is_hidden);
+                tile_row.push(match tile.TYPVS {
+                    TEGVLA_TYPVS::INITIVM => 'S',
+                    TEGVLA_TYPVS::FINIS if hidden_mineral => 'P',
+                    TEGVLA_TYPVS::FINIS => 'E',
+                    TEGVLA_TYPVS::SEMITA => 'P',
+                    TEGVLA_TYPVS::SATVRNALIA => 'D',
+                    TEGVLA_TYPVS::CRATERA => 'C',
+                    TEGVLA_TYPVS::INGENII => 'I',
+                });
+                shadow_row.push(if tile.VMBRA { '#' } else { '.' });
+            }
+            tiles.push(tile_row);
+            shadow.push(shadow_row);
+        }
+
+        let mut wires: Vec<[(i8, i8); 2]> = level
+            .NEXVS
+            .iter()
+            .flat_map(|(from, neighbours)| {
+                neighbours
+                    .iter()
+                    .filter(move |to| from < *to)
+                    .map(move |to| [*from, *to])
+            })
+            .collect();
+        wires.sort();
+
+        let units = self
+            .state
+            .units
+            .iter()
+            .map(|unit| {
+                let facing = heading_direction(unit.heading);
+                UnitObservation {
+                    kind: match unit.kind {
+                        UnitKind::Rover => "rover",
+                        UnitKind::Helicopter => "helicopter",
+                    },
+                    position: (unit.position.x, unit.position.y),
+                    facing: (facing.x, facing.y),
+                    battery: unit.battery_level,
+                    capacity: unit.battery_rules.capacity,
+                    cargo: unit.cargo.clone(),
+                    battery_pack: unit.battery_pack,
+                }
+            })
+            .collect();
+
+        let minerals = (0..level.CRYSTALLA.len())
+            .filter(|mineral| !is_hidden(*mineral) && !self.is_mineral_done(*mineral))
+            .map(|mineral| {
+                let carried = self
+                    .state
+                    .units
+                    .iter()
+                    .find(|unit| unit.cargo.contains(&mineral));
+                MineralObservation {
+                    index: mineral,
+                    position: carried
+                        .map(|unit| (unit.position.x, unit.position.y))
+                        .unwrap_or(level.CRYSTALLA[mineral]),
+                    carried: carried.is_some(),
+                }
+            })
+            .collect();
+
+        Observation {
+            width: level.LATIVIDO,
+            height: level.ALTIVIDO,
+            turn: self.state.turn,
+            tiles,
+            shadow,
+            wires,
+            units,
+            minerals,
+        }
+    }
+
+    /// Whether nothing is left to do with `mineral` as far as the objective goes.
+    fn is_mineral_done(&self, mineral: usize) -> bool {
+        if self.level.OBIECTVM.DESTINATIO.is_some() {
+            self.state.progress.delivered.contains(&mineral)
+        } else {
+            self.state.progress.is_collected(mineral)
+        }
+    }
+
+    fn minerals_done(&self) -> usize {
+        (0..self.level.CRYSTALLA.len())
+            .filter(|mineral| self.is_mineral_done(*mineral))
+            .count()
+    }
+}
+
+fn failure_name(reason: FailureReason) -> &'static str {
+    match reason {
+        FailureReason::Collision => "collision",
+        FailureReason::BlockedMove => "blocked_move",
+        FailureReason::OutOfBattery => "out_of_battery",
+        FailureReason::OutOfCommands => "out_of_commands",
+    }
+}
@@ -6,7 +6,44 @@ pub enum ActionType {
     MoveDown,
     MoveLeft,
     MoveRight,
+    /// Moves one tile in the direction the unit is facing
+    Forward,
+    /// Moves one tile backwards, still facing the same direction
+    Reverse,
+    TurnLeft,
+    TurnRight,
     Wait,
+    /// Helicopter only, reveals the hidden minerals around it
+    Scout,
+    /// Helicopter only, hands its battery pack to the rover below
+    DropPack,
+}
+
+/// How moves are expressed in a level: compass directions, or relative to where the unit faces.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ControlScheme {
+    #[default]
+    Absolute,
+    Relative,
+}
+
+impl ControlScheme {
+    pub fn movement_actions(&self) -> [ActionType; 4] {
+        match self {
+            ControlScheme::Absolute => [
+                ActionType::MoveUp,
+                ActionType::MoveDown,
+                ActionType::MoveLeft,
+                ActionType::MoveRight,
+            ],
+            ControlScheme::Relative => [
+                ActionType::Forward,
+                ActionType::Reverse,
+                ActionType::TurnLeft,
+                ActionType::TurnRight,
+            ],
+        }
+    }
 }
 
 pub struct ActionController;
@@ -23,13 +60,67 @@ pub struct ActionList {
 }
 
 impl ActionType {
+    /// Whether the unit stays put, neither moving nor turning, while performing this action.
+    pub fn is_stationary(&self) -> bool {
+        matches!(
+            self,
+            ActionType::Wait | ActionType::Scout | ActionType::DropPack
+        )
+    }
+
+    /// Whether this action only changes the heading of the unit.
+    pub fn is_turn(&self) -> bool {
+        matches!(self, ActionType::TurnLeft | ActionType::TurnRight)
+    }
+
+    /// Name of the command as shown to the player.
+    pub fn label(&self) -> &'static str {
+        match self {
+            ActionType::MoveUp => "Up",
+            ActionType::MoveDown => "Down",
+            ActionType::MoveLeft => "Left",
+            ActionType::MoveRight => "Right",
+            ActionType::Forward => "Forward",
+            ActionType::Reverse => "Reverse",
+            ActionType::TurnLeft => "Turn left",
+            ActionType::TurnRight => "Turn right",
+            ActionType::Wait => "Wait",
+            ActionType::Scout => "Scout",
+            ActionType::DropPack => "Drop pack",
+        }
+    }
+
+    /// Key of the name of the command in the string tables
+    pub fn key(&self) -> &'static str {
+        match self {
+            ActionType::MoveUp => "command.up",
+            ActionType::MoveDown => "command.down",
+            ActionType::MoveLeft => "command.left",
+            ActionType::MoveRight => "command.right",
+            ActionType::Forward => "command.forward",
+            ActionType::Reverse => "command.reverse",
+            ActionType::TurnLeft => "command.turn_left",
+            ActionType::TurnRight => "command.turn_right",
+            ActionType::Wait => "command.wait",
+            ActionType::Scout => "command.scout",
+            ActionType::DropPack => "command.drop_pack",
+        }
+    }
+
+    #[cfg(feature = "presentation")]
     pub(crate) fn img_path(&self) -> &'static str {
         match self {
             ActionType::MoveUp => "command_icons/arrow_up_outlined.png",
             ActionType::MoveDown => "command_icons/arrow_down_outlined.png",
             ActionType::MoveLeft => "command_icons/arrow_left_outlined.png",
             ActionType::MoveRight => "command_icons/arrow_right_outlined.png",
+            ActionType::Forward => "command_icons/up.png",
+            ActionType::Reverse => "command_icons/reverse_outlined.png",
+            ActionType::TurnLeft => "command_icons/turn_left_outlined.png",
+            ActionType::TurnRight => "command_icons/turn_right_outlined.png",
             ActionType::Wait => "command_icons/clock_outlined.png",
+            ActionType::Scout => "command_icons/scout_outlined.png",
+            ActionType::DropPack => "command_icons/drop_pack_outlined.png",
         }
     }
 }
@@ -0,0 +1,180 @@
+use crate::game_control::actions::{ActionType, ControlScheme};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// What the player can do from the keyboard while programming.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum KeyAction {
+    /// Moves take the commands of the control scheme of the level, compass or relative
+    Up,
+    Down,
+    Left,
+    Right,
+    Wait,
+    Scout,
+    DropPack,
+    RemoveLast,
+    ClearAll,
+    Execute,
+    NextUnit,
+    /// Unit number, starting from 1
+    SelectUnit(u8),
+    /// Opens the pause menu, or closes whatever is open on top
+    Pause,
+}
+
+/// Gamepad buttons doing the same as keys while programming. The d-pad, the left stick and the
+/// south button move between the buttons of the UI and press them instead.
+pub const GAMEPAD_BINDINGS: [(GamepadButton, KeyAction); 5] = [
+    (GamepadButton::Start, KeyAction::Pause),
+    (GamepadButton::North, KeyAction::Execute),
+    (GamepadButton::RightTrigger, KeyAction::NextUnit),
+    (GamepadButton::West, KeyAction::RemoveLast),
+    (GamepadButton::Select, KeyAction::ClearAll),
+];
+
+/// Actions whose gamepad buttons were just pressed, on any of the `gamepads`.
+pub fn gamepad_just_pressed_actions<'a>(
+    gamepads: impl IntoIterator<Item = &'a Gamepad>,
+) -> Vec<KeyAction> {
+    let gamepads: Vec<&Gamepad> = gamepads.into_iter().collect();
+    GAMEPAD_BINDINGS
+        .iter()
+        .filter(|(button, _)| gamepads.iter().any(|gamepad| gamepad.just_pressed(*button)))
+        .map(|(_, action)| *action)
+        .collect()
+}
+
+/// Keys bound to each `KeyAction`. Actions missing from a saved keymap keep their default keys.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct Keymap {
+    pub bindings: BTreeMap<KeyAction, Vec<KeyCode>>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let mut bindings = BTreeMap::from([
+            (KeyAction::Up, vec![KeyCode::ArrowUp, KeyCode::KeyW]),
+            (KeyAction::Down, vec![KeyCode::ArrowDown, KeyCode::KeyS]),
+            (KeyAction::Left, vec![KeyCode::ArrowLeft, KeyCode::KeyA]),
+            (KeyAction::Right, vec![KeyCode::ArrowRight, KeyCode::KeyD]),
+            (KeyAction::Wait, vec![KeyCode::KeyX]),
+            (KeyAction::Scout, vec![KeyCode::KeyF]),
+            (KeyAction::DropPack, vec![KeyCode::KeyV]),
+            (KeyAction::RemoveLast, vec![KeyCode::Backspace]),
+            (KeyAction::ClearAll, vec![KeyCode::Delete]),
+            (KeyAction::Execute, vec![KeyCode::Space, KeyCode::Enter]),
+            (KeyAction::NextUnit, vec![KeyCode::Tab]),
+            (KeyAction::Pause, vec![KeyCode::Escape]),
+        ]);
+        let digits = [
+            KeyCode::Digit1,
+            KeyCode::Digit2,
+            KeyCode::Digit3,
+            KeyCode::Digit4,
+            KeyCode::Digit5,
+            KeyCode::Digit6,
+            KeyCode::Digit7,
+            KeyCode::Digit8,
+            KeyCode::Digit9,
+        ];
+        for (index, digit) in digits.into_iter().enumerate() {
+            bindings.insert(KeyAction::SelectUnit(index as u8 + 1), vec![digit]);
+        }
+
+        Keymap { bindings }
+    }
+}
+
+impl KeyAction {
+    /// The action adding `command` to the program.
+    pub fn for_command(command: ActionType) -> KeyAction {
+        match command {
+            ActionType::MoveUp | ActionType::Forward => KeyAction::Up,
+            ActionType::MoveDown | ActionType::Reverse => KeyAction::Down,
+            ActionType::MoveLeft | ActionType::TurnLeft => KeyAction::Left,
+            ActionType::MoveRight | ActionType::TurnRight => KeyAction::Right,
+            ActionType::Wait => KeyAction::Wait,
+            ActionType::Scout => KeyAction::Scout,
+            ActionType::DropPack => KeyAction::DropPack,
+        }
+    }
+
+    /// The command this action adds to the program, in a level with `control_scheme`.
+    pub fn command(&self, control_scheme: ControlScheme) -> Option<ActionType> {
+        let [up, down, left, right] = control_scheme.movement_actions();
+        match self {
+            KeyAction::Up => Some(up),
+            KeyAction::Down => Some(down),
+            KeyAction::Left => Some(left),
+            KeyAction::Right => Some(right),
+            KeyAction::Wait => Some(ActionType::Wait),
+            KeyAction::Scout => Some(ActionType::Scout),
+            KeyAction::DropPack => Some(ActionType::DropPack),
+            _ => None,
+        }
+    }
+}
+
+impl Keymap {
+    pub fn keys(&self, action: KeyAction) -> Vec<KeyCode> {
+        match self.bindings.get(&action) {
+            Some(keys) => keys.clone(),
+            None => Keymap::default()
+                .bindings
+                .remove(&action)
+                .unwrap_or_default(),
+        }
+    }
+
+    pub fn just_pressed(&self, action: KeyAction, input: &ButtonInput<KeyCode>) -> bool {
+        input.any_just_pressed(self.keys(action))
+    }
+
+    /// Actions whose keys were just pressed.
+    pub fn just_pressed_actions(&self, input: &ButtonInput<KeyCode>) -> Vec<KeyAction> {
+        let actions: BTreeSet<KeyAction> = Keymap::default()
+            .bindings
+            .into_keys()
+            .chain(self.bindings.keys().copied())
+            .collect();
+        actions
+            .into_iter()
+            .filter(|action| self.just_pressed(*action, input))
+            .collect()
+    }
+
+    /// Binds `key` to `action` alone, taking it away from whatever it was bound to.
+    pub fn rebind(&mut self, action: KeyAction, key: KeyCode) {
+        for action in Keymap::default().bindings.into_keys() {
+            let keys = self.keys(action);
+            self.bindings.insert(action, keys);
+        }
+        for keys in self.bindings.values_mut() {
+            keys.retain(|bound| *bound != key);
+        }
+        self.bindings.insert(action, vec![key]);
+    }
+
+    /// Keys of `action` as shown to the player, such as "Up / W".
+    pub fn describe(&self, action: KeyAction) -> String {
+        self.keys(action)
+            .iter()
+            .map(|key| key_name(*key))
+            .collect::<Vec<_>>()
+            .join(" / ")
+    }
+}
+
+pub fn key_name(key: KeyCode) -> String {
+    let name = format!("{key:?}");
+    if let Some(arrow) = name.strip_prefix("Arrow") {
+        return arrow.to_string();
+    }
+    name.strip_prefix("Key")
+        .or_else(|| name.strip_prefix("Digit"))
+        .unwrap_or(&name)
+        .to_string()
+}
@@ -1 +1,4 @@
-pub(crate) mod actions;
+pub mod actions;
+pub mod keymap;
+#[cfg(feature = "presentation")]
+pub mod path_preview;
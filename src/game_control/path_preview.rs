@@ -0,0 +1,153 @@
+use crate::game_control::actions::ActionList;
+use crate::level::GRADVM;
+use crate::gameplay::{ActiveLevel, GameState, TILE_SIZE};
+use crate::level_spawner::LevelElement;
+use crate::rover::{RoverEntity, predict_pose};
+use crate::ui::control_ui::UIRoverColors;
+use bevy::math::I8Vec2;
+use bevy::prelude::*;
+
+/// Ghost of the path a unit will take, shown while programming.
+#[derive(Component)]
+pub struct PathPreview;
+
+const PREVIEW_HEIGHT: f32 = 0.15;
+const PREVIEW_ALPHA: f32 = 0.5;
+
+pub struct PathPreviewPlugin;
+
+impl Plugin for PathPreviewPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            update_path_preview.run_if(in_state(GameState::Programming)),
+        );
+        app.add_systems(OnExit(GameState::Programming), clear_path_preview);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn update_path_preview(
+    mut commands: Commands,
+    action_list: Res<ActionList>,
+    rovers: Query<&RoverEntity>,
+    mut last_poses: Local<Vec<(u8, I8Vec2, u32)>>,
+    previews: Query<Entity, With<PathPreview>>,
+    colors: Res<UIRoverColors>,
+    active_level: Res<ActiveLevel>,
+    levels: Res<Assets<GRADVM>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // rovers get touched every frame by other systems, only their pose matters here
+    let mut poses: Vec<(u8, I8Vec2, u32)> = rovers
+        .iter()
+        .map(|rover| {
+            (
+                rover.identifier,
+                rover.logical_position,
+                rover.heading.to_bits(),
+            )
+        })
+        .collect();
+    poses.sort_by_key(|pose| pose.0);
+
+    // the preview is cleared when leaving programming, it has to come back even if nothing moved
+    let is_missing = previews.is_empty()
+        && action_list
+            .actions
+            .iter()
+            .any(|actions| !actions.is_empty());
+
+    let is_unchanged = !action_list.is_changed() && !colors.is_changed() && poses == *last_poses;
+    if rovers.is_empty() || is_unchanged && !is_missing {
+        return;
+    }
+    *last_poses = poses;
+
+    let Some(level) = active_level
+        .0
+        .as_ref()
+        .and_then(|handle| levels.get(handle))
+    else {
+        return;
+    };
+
+    for preview in previews.iter() {
+        commands.entity(preview).despawn();
+    }
+
+    let level_width = level.LATIVIDO as f32 * TILE_SIZE;
+    let level_height = level.ALTIVIDO as f32 * TILE_SIZE;
+
+    for rover in rovers.iter() {
+        let Some(actions) = action_list.actions.get(rover.identifier as usize) else {
+            continue;
+        };
+
+        let color = colors
+            .0
+            .get(rover.identifier as usize)
+            .cloned()
+            .unwrap_or(Color::WHITE)
+            .with_alpha(PREVIEW_ALPHA);
+        let material = materials.add(StandardMaterial {
+            base_color: color,
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            // the flat shapes are seen from both sides
+            double_sided: true,
+            cull_mode: None,
+            ..default()
+        });
+        let mesh = meshes.add(preview_shape(rover.identifier));
+
+        let mut position = rover.logical_position;
+        let mut heading = rover.heading;
+        for action in actions.iter() {
+            (position, heading) = predict_pose(position, heading, action.moves.0);
+
+            let x = (position.x as f32 * TILE_SIZE - level_width / 2.0) + TILE_SIZE / 2.0;
+            // mirror along the z to align correctly with how it looks in the level
+            let z = (-position.y as f32 * TILE_SIZE + level_height / 2.0) + TILE_SIZE / 2.0;
+
+            commands.spawn((
+                PathPreview,
+                LevelElement,
+                Mesh3d(mesh.clone()),
+                MeshMaterial3d(material.clone()),
+                Transform::from_xyz(x, PREVIEW_HEIGHT, z)
+                    .with_rotation(Quat::from_rotation_y(heading)),
+            ));
+        }
+    }
+}
+
+/// Each unit leaves marks of its own shape, so that paths can be told apart without relying on
+/// their colour. They face x, which is the direction a unit with a heading of 0 faces.
+fn preview_shape(identifier: u8) -> Mesh {
+    match identifier % 6 {
+        0 => Cuboid::new(0.5 * TILE_SIZE, 0.02, 0.12 * TILE_SIZE).into(),
+        1 => Sphere::new(0.1 * TILE_SIZE).into(),
+        // arrowhead
+        2 => Triangle3d::new(
+            Vec3::new(0.2, 0.0, 0.0) * TILE_SIZE,
+            Vec3::new(-0.15, 0.0, -0.15) * TILE_SIZE,
+            Vec3::new(-0.15, 0.0, 0.15) * TILE_SIZE,
+        )
+        .into(),
+        3 => Torus::new(0.08 * TILE_SIZE, 0.14 * TILE_SIZE).into(),
+        // diamond
+        4 => Cuboid::new(0.2 * TILE_SIZE, 0.02, 0.2 * TILE_SIZE)
+            .mesh()
+            .build()
+            .rotated_by(Quat::from_rotation_y(std::f32::consts::FRAC_PI_4)),
+        _ => Cylinder::new(0.12 * TILE_SIZE, 0.02).into(),
+    }
+}
+
+fn clear_path_preview(mut commands: Commands, previews: Query<Entity, With<PathPreview>>) {
+    for preview in previews.iter() {
+        commands.entity(preview).despawn();
+    }
+}
@@ -0,0 +1,201 @@
+use crate::gameplay::{ActiveLevel, GameState, LevelSpawnRequestEvent};
+use crate::generator::{GeneratedLevel, GeneratorSettings, generate};
+use crate::level::{GRADVM, GRADVM_ONVSTVS};
+use crate::locale::Strings;
+use bevy::asset::RenderAssetUsages;
+use bevy::prelude::*;
+use bevy::tasks::{AsyncComputeTaskPool, Task, block_on, futures_lite::future};
+use wgpu_types::{Extent3d, TextureDimension, TextureFormat};
+
+/// Seeds tried for a single generated level before giving up, most seeds work the first time.
+const MAX_GENERATION_SEEDS: usize = 16;
+
+pub struct GameModePlugin;
+
+/// Where the levels being played come from.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GameMode {
+    /// The levels shipped with the game, in order
+    #[default]
+    Campaign,
+    /// Generated levels that keep getting harder, until the player gives up
+    Endless,
+    /// A single generated level, the same for everyone on a given day
+    Daily,
+}
+
+/// Progress of the current endless run.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct EndlessRun {
+    pub seed: u64,
+    /// Number of levels solved so far in this run
+    pub level: u32,
+}
+
+/// A level being generated away from the main thread, the solver checking it can take a while.
+#[derive(Component)]
+pub struct LevelGeneration {
+    task: Task<Option<GeneratedLevel>>,
+    index: u32,
+    /// Whether the game starts on the level, rather than moving on to it from the one just solved
+    starts_game: bool,
+}
+
+/// Generated level the game starts on once it leaves the title screen.
+#[derive(Resource)]
+pub struct PreparedLevel(pub Handle<GRADVM>);
+
+impl Plugin for GameModePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameMode>();
+        app.add_systems(Update, finish_level_generation);
+        app.add_systems(OnEnter(GameState::TitleScreen), cancel_level_generation);
+    }
+}
+
+impl GameMode {
+    /// Whether solving `level` ends the game, endless runs never end and the daily challenge
+    /// is a single level.
+    pub fn is_final_level(&self, level: &GRADVM, level_handles: &GRADVM_ONVSTVS) -> bool {
+        match self {
+            GameMode::Campaign => level.INDEX as usize == level_handles.GRADVS.len() - 1,
+            GameMode::Endless => false,
+            GameMode::Daily => true,
+        }
+    }
+
+    pub fn level_label(&self, level: &GRADVM, strings: &Strings) -> String {
+        let number = level.INDEX + 1;
+        match self {
+            GameMode::Campaign => strings.format("level.campaign", &[("number", &number)]),
+            GameMode::Endless => strings.format("level.endless", &[("number", &number)]),
+            GameMode::Daily => strings.get("level.daily").to_string(),
+        }
+    }
+}
+
+impl EndlessRun {
+    pub fn new(seed: u64) -> Self {
+        Self { seed, level: 0 }
+    }
+
+    fn level_seed(&self, attempt: u64) -> u64 {
+        // spread consecutive levels apart, a plain sum would make runs overlap
+        self.seed
+            ^ (self.level as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+            ^ attempt.wrapping_mul(0xBF58_476D_1CE4_E5B9)
+    }
+
+    /// Starts generating the current level of the run.
+    pub fn generate_level(&self, starts_game: bool) -> LevelGeneration {
+        LevelGeneration::start(
+            (0..).map(|attempt| self.level_seed(attempt)),
+            GeneratorSettings::for_endless_level(self.level),
+            self.level,
+            starts_game,
+        )
+    }
+}
+
+impl LevelGeneration {
+    /// Generates a level from the first of `seeds` that gives one, on the async compute pool.
+    pub fn start(
+        seeds: impl Iterator<Item = u64>,
+        settings: GeneratorSettings,
+        index: u32,
+        starts_game: bool,
+    ) -> Self {
+        let seeds: Vec<u64> = seeds.take(MAX_GENERATION_SEEDS).collect();
+        let task = AsyncComputeTaskPool::get()
+            .spawn(async move { seeds.into_iter().find_map(|seed| generate(seed, &settings)) });
+
+        Self {
+            task,
+            index,
+            starts_game,
+        }
+    }
+}
+
+/// Adds a generated level, with its shadow map, to the assets. Its par and reference solution
+/// come from the solutions the generator checked it with.
+pub fn add_generated_level(
+    generated: &GeneratedLevel,
+    index: u32,
+    levels: &mut Assets<GRADVM>,
+    images: &mut Assets<Image>,
+) -> Handle<GRADVM> {
+    let mut level = generated.level.clone();
+    level.INDEX = index;
+    level.PAR = Some(generated.par);
+    level.SOLVTIO = Some(generated.solution.actions.clone());
+    level.MAPPAE_VREMBRAE = images.add(shadow_map_image(generated));
+    levels.add(level)
+}
+
+/// Picks up the levels done generating, then starts the game on them or moves on to them.
+fn finish_level_generation(
+    mut commands: Commands,
+    mut generations: Query<(Entity, &mut LevelGeneration)>,
+    mut levels: ResMut<Assets<GRADVM>>,
+    mut images: ResMut<Assets<Image>>,
+    mut active_level: ResMut<ActiveLevel>,
+    mut level_spawn_requests: EventWriter<LevelSpawnRequestEvent>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for (entity, mut generation) in generations.iter_mut() {
+        let Some(generated) = block_on(future::poll_once(&mut generation.task)) else {
+            continue;
+        };
+        commands.entity(entity).despawn();
+
+        let level = match generated {
+            Some(generated) => Some(add_generated_level(
+                &generated,
+                generation.index,
+                &mut levels,
+                &mut images,
+            )),
+            None => {
+                log::error!("Could not generate level {}", generation.index);
+                None
+            }
+        };
+
+        if generation.starts_game {
+            // without a generated level, the game starts on the first level of the campaign
+            if let Some(level) = level {
+                commands.insert_resource(PreparedLevel(level));
+            }
+            next_state.set(GameState::Programming);
+        } else if let Some(level) = level {
+            active_level.0 = Some(level.clone());
+            level_spawn_requests.write(LevelSpawnRequestEvent { level });
+        }
+    }
+}
+
+/// Levels still generating when the player goes back to the title screen aren't wanted anymore.
+fn cancel_level_generation(
+    mut commands: Commands,
+    generations: Query<Entity, With<LevelGeneration>>,
+) {
+    for entity in generations.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+pub fn shadow_map_image(generated: &GeneratedLevel) -> Image {
+    let (width, height) = generated.shadow_map_size();
+    Image::new(
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        generated.shadow_map_pixels(),
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    )
+}
@@ -0,0 +1,281 @@
+use crate::daily_challenge::DailyChallengePlugin;
+use crate::game_control::actions::{ActionController, ActionList};
+use crate::game_mode::{EndlessRun, GameMode, GameModePlugin, PreparedLevel};
+use crate::level::{GRADVM, GRADVM_ONVSTVS, GRADVS_ONERATOR_PLUGIN};
+use crate::objective::ObjectiveProgress;
+use crate::puzzle_evaluation::{PuzzleEvaluationPlugin, PuzzleResponseEvent};
+use crate::rover::{RoverCollectable, RoverEntity, RoverPlugin};
+use crate::save::SavePlugin;
+use crate::score::ScorePlugin;
+use bevy::app::PluginGroupBuilder;
+use bevy::image::{CompressedImageFormats, ImageLoader};
+use bevy::math::I8Vec2;
+use bevy::prelude::*;
+
+pub const TILE_SIZE: f32 = 2.0;
+pub const LEVEL_SHADOW_ALPHA_MASK: f32 = 0.5;
+
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, States)]
+pub enum GameState {
+    #[default]
+    TitleScreen,
+    Programming,
+    Execution,
+}
+
+#[derive(Resource)]
+pub struct ActiveLevel(pub Option<Handle<GRADVM>>);
+
+#[derive(Event)]
+pub struct LevelSpawnRequestEvent {
+    pub level: Handle<GRADVM>,
+}
+
+#[derive(Event)]
+pub struct NextLevelRequestEvent;
+
+#[derive(Component)]
+pub struct ResetTimer {
+    timer: Timer,
+}
+
+/// Everything the game needs to be played, without anything to see or hear. Runs on top of
+/// `MinimalPlugins` with the `StatesPlugin`, the `AssetPlugin` and the `InputPlugin`, along with
+/// either the `BareUnitsPlugin` or the `PresentationPlugins` to put the units of each level on
+/// the map.
+pub struct GameplayPlugins;
+
+impl PluginGroup for GameplayPlugins {
+    fn build(self) -> PluginGroupBuilder {
+        PluginGroupBuilder::start::<Self>()
+            .add(GameplayPlugin)
+            .add(GRADVS_ONERATOR_PLUGIN)
+            .add(ActionController)
+            .add(GameModePlugin)
+            .add(SavePlugin)
+            .add(ScorePlugin)
+            .add(DailyChallengePlugin)
+            .add(RoverPlugin)
+            .add(PuzzleEvaluationPlugin)
+    }
+}
+
+/// Which level is played, and moving on from it once it's solved or failed.
+pub struct GameplayPlugin;
+
+impl Plugin for GameplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_state::<GameState>();
+        app.insert_resource(ActiveLevel(None));
+        app.add_event::<LevelSpawnRequestEvent>();
+        app.add_event::<NextLevelRequestEvent>();
+        app.add_systems(OnExit(GameState::TitleScreen), spawn_initial_level);
+        app.add_systems(
+            Update,
+            start_level.run_if(not(in_state(GameState::TitleScreen))),
+        );
+        app.add_systems(Update, handle_puzzle_solved_event);
+        app.add_systems(Update, handle_next_level_request);
+        app.add_systems(Update, (handle_puzzle_failed_event, update_reset_timer));
+    }
+}
+
+/// Spawns the units of each level without any model, for the games played without the
+/// `PresentationPlugins` and their level spawner. Also sets up the images the levels load their
+/// shadow maps into, which the `DefaultPlugins` would otherwise do.
+pub struct BareUnitsPlugin;
+
+impl Plugin for BareUnitsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<Image>()
+            .register_asset_loader(ImageLoader::new(CompressedImageFormats::NONE));
+        app.add_systems(
+            Update,
+            spawn_level_units
+                .after(start_level)
+                .run_if(not(in_state(GameState::TitleScreen))),
+        );
+    }
+}
+
+/// Spawns the units and minerals of `level` without any model, as the level starts.
+pub fn spawn_units(commands: &mut Commands, level: &GRADVM) {
+    for identifier in 0..level.VEHICVLA.len() {
+        commands.spawn((
+            Transform::default(),
+            RoverEntity::at_start(level, identifier as u8, Color::WHITE),
+        ));
+    }
+
+    for (index, (x, y)) in level.CRYSTALLA.iter().enumerate() {
+        commands.spawn(RoverCollectable {
+            index,
+            logical_position: I8Vec2::new(*x, *y),
+        });
+    }
+}
+
+fn spawn_initial_level(
+    mut commands: Commands,
+    mut events: EventWriter<LevelSpawnRequestEvent>,
+    level_handles: Res<GRADVM_ONVSTVS>,
+    game_mode: Res<GameMode>,
+    prepared_level: Option<Res<PreparedLevel>>,
+) {
+    // generated levels are ready before the title screen is left
+    let level = prepared_level
+        .filter(|_| *game_mode != GameMode::Campaign)
+        .map(|prepared_level| prepared_level.0.clone());
+    commands.remove_resource::<PreparedLevel>();
+
+    events.write(LevelSpawnRequestEvent {
+        level: level.unwrap_or(level_handles.GRADVS[0].clone()),
+    });
+}
+
+fn start_level(
+    mut commands: Commands,
+    mut events: EventReader<LevelSpawnRequestEvent>,
+    mut active_level: ResMut<ActiveLevel>,
+    mut action_list: ResMut<ActionList>,
+    levels: Res<Assets<GRADVM>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let Some(event) = events.read().last() else {
+        return;
+    };
+    let Some(level) = levels.get(&event.level) else {
+        return;
+    };
+
+    active_level.0 = Some(event.level.clone());
+    commands.insert_resource(ObjectiveProgress::default());
+
+    // one command list per unit, helicopters included
+    let unit_count = level.NVMERVS_VEHICVLORVM_MOBILIVM as usize;
+    if action_list.actions.len() < unit_count {
+        action_list.actions.resize(unit_count, vec![]);
+    }
+
+    let action_event = action_list.clone();
+    commands.send_event(action_event);
+
+    next_state.set(GameState::Programming);
+}
+
+/// What a level puts on the map, and takes back when another one starts.
+pub type LevelUnitFilter = Or<(With<RoverEntity>, With<RoverCollectable>)>;
+
+/// Replaces the units and minerals on the map with the ones of each level spawned.
+pub fn spawn_level_units(
+    mut commands: Commands,
+    mut events: EventReader<LevelSpawnRequestEvent>,
+    levels: Res<Assets<GRADVM>>,
+    units: Query<Entity, LevelUnitFilter>,
+) {
+    let Some(level) = events
+        .read()
+        .last()
+        .and_then(|event| levels.get(&event.level))
+    else {
+        return;
+    };
+
+    for unit in units.iter() {
+        commands.entity(unit).despawn();
+    }
+    spawn_units(&mut commands, level);
+}
+
+fn handle_puzzle_solved_event(
+    mut events: EventReader<PuzzleResponseEvent>,
+    mut action_list: ResMut<ActionList>,
+) {
+    for event in events.read() {
+        if *event == PuzzleResponseEvent::Solved {
+            for i in 0..action_list.actions.len() {
+                action_list.actions[i].clear();
+                action_list.actions.push(vec![]);
+            }
+        }
+    }
+}
+
+fn handle_next_level_request(
+    mut commands: Commands,
+    mut events: EventReader<NextLevelRequestEvent>,
+    levels: Res<Assets<GRADVM>>,
+    level_handles: Res<GRADVM_ONVSTVS>,
+    mut active_level: ResMut<ActiveLevel>,
+    game_mode: Res<GameMode>,
+    endless_run: Option<ResMut<EndlessRun>>,
+) {
+    let mut endless_run = endless_run.filter(|_| *game_mode == GameMode::Endless);
+    for _ in events.read() {
+        if let Some(endless_run) = endless_run.as_mut() {
+            endless_run.level += 1;
+            // the level is played once generated, see finish_level_generation
+            commands.spawn(endless_run.generate_level(false));
+            continue;
+        }
+
+        let Some(active_level_handle) = &active_level.0 else {
+            log::error!("No active level.");
+            return;
+        };
+
+        let Some(current_level) = levels.get(active_level_handle) else {
+            log::error!("No active level.");
+            return;
+        };
+
+        let Some(next_level_handle) = level_handles
+            .GRADVS
+            .get(current_level.INDEX as usize + 1)
+            .or(level_handles.GRADVS.get(0))
+        else {
+            log::error!("No next level.");
+            return;
+        };
+
+        active_level.0 = Some(next_level_handle.clone());
+
+        commands.spawn(ResetTimer {
+            timer: Timer::from_seconds(0.01, TimerMode::Once),
+        });
+    }
+}
+
+fn handle_puzzle_failed_event(
+    mut commands: Commands,
+    mut events: EventReader<PuzzleResponseEvent>,
+) {
+    for event in events.read() {
+        if *event == PuzzleResponseEvent::Failed {
+            commands.spawn(ResetTimer {
+                timer: Timer::from_seconds(1.0, TimerMode::Once),
+            });
+            break;
+        }
+    }
+    events.clear();
+}
+
+fn update_reset_timer(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut ResetTimer)>,
+    mut level_spawn_request_writer: EventWriter<LevelSpawnRequestEvent>,
+    active_level: Res<ActiveLevel>,
+    time: Res<Time>,
+) {
+    for (entity, mut timer) in query.iter_mut() {
+        timer.timer.tick(time.delta());
+
+        if timer.timer.just_finished() {
+            commands.entity(entity).despawn();
+            level_spawn_request_writer.write(LevelSpawnRequestEvent {
+                level: active_level.0.clone().unwrap(),
+            });
+        }
+    }
+}
@@ -0,0 +1,240 @@
+use crate::level::{GRADVM, LEGE_GRADVM, PAR};
+use crate::score::solution_par;
+use crate::solver::{Solution, SolverLimits, solve, solve_fewest_commands};
+use bevy::asset::Handle;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+/// Size of a tile on the shadow map, same as the maps drawn for the campaign levels.
+pub const SHADOW_PIXELS_PER_TILE: u32 = 100;
+
+/// Wires are digits, `'1'..'9'` in level files.
+pub const MAX_WIRE_PAIRS: usize = 8;
+
+/// Random layouts tried before giving up on a seed.
+const MAX_ATTEMPTS: usize = 200;
+
+/// What a generated level should look like.
+#[derive(Debug, Clone)]
+pub struct GeneratorSettings {
+    pub width: usize,
+    pub height: usize,
+    pub rovers: usize,
+    pub minerals: usize,
+    pub wire_pairs: usize,
+    /// Fraction of the tiles in the shadow, in the range [0, 1]
+    pub shadow_coverage: f32,
+    /// Levels the solver finishes in fewer turns are thrown away as too easy
+    pub min_turns: usize,
+    pub solver_limits: SolverLimits,
+}
+
+impl Default for GeneratorSettings {
+    fn default() -> Self {
+        Self {
+            width: 6,
+            height: 5,
+            rovers: 1,
+            minerals: 2,
+            wire_pairs: 0,
+            shadow_coverage: 0.25,
+            min_turns: 4,
+            solver_limits: SolverLimits::default(),
+        }
+    }
+}
+
+impl GeneratorSettings {
+    /// Settings of the `level`th level of an endless run (starting at 0), a little bigger and
+    /// darker every few levels.
+    pub fn for_endless_level(level: u32) -> Self {
+        let level = level as usize;
+        Self {
+            width: (5 + level / 2).min(9),
+            height: (4 + level / 3).min(7),
+            rovers: (1 + level / 4).min(3),
+            minerals: (1 + level / 2).min(4),
+            wire_pairs: (level / 3).min(3),
+            shadow_coverage: (0.15 + 0.05 * level as f32).min(0.5),
+            min_turns: (3 + level).min(10),
+            solver_limits: SolverLimits::default(),
+        }
+    }
+}
+
+/// A level made up from a seed, proven solvable.
+#[derive(Debug, Clone)]
+pub struct GeneratedLevel {
+    pub seed: u64,
+    /// Content of the level file
+    pub text: String,
+    /// Parsed level with its shadows already set, its INDEX is left at 0
+    pub level: GRADVM,
+    /// Shadowed tiles, rows in reading order like the level file
+    pub shadow: Vec<Vec<bool>>,
+    /// Shortest solution, its number of turns is how hard the level is
+    pub solution: Solution,
+    /// Par taken from `solution` and from the program with the fewest commands
+    pub par: PAR,
+}
+
+impl GeneratedLevel {
+    pub fn shadow_map_size(&self) -> (u32, u32) {
+        (
+            self.level.LATIVIDO as u32 * SHADOW_PIXELS_PER_TILE,
+            self.level.ALTIVIDO as u32 * SHADOW_PIXELS_PER_TILE,
+        )
+    }
+
+    /// RGBA pixels of the shadow map, opaque black where the tiles are in the shadow and
+    /// transparent elsewhere, as read by the level loader.
+    pub fn shadow_map_pixels(&self) -> Vec<u8> {
+        let (width, height) = self.shadow_map_size();
+        let mut pixels = Vec::with_capacity((width * height * 4) as usize);
+
+        for y in 0..height {
+            for x in 0..width {
+                let row = (y / SHADOW_PIXELS_PER_TILE) as usize;
+                let column = (x / SHADOW_PIXELS_PER_TILE) as usize;
+                let alpha = if self.shadow[row][column] { 255 } else { 0 };
+                pixels.extend([0, 0, 0, alpha]);
+            }
+        }
+
+        pixels
+    }
+}
+
+/// Generates a level from `seed`, the same seed and settings always give the same level.
+/// Returns `None` if no layout turned out solvable and hard enough.
+pub fn generate(seed: u64, settings: &GeneratorSettings) -> Option<GeneratedLevel> {
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    if settings.rovers + settings.minerals > settings.width * settings.height {
+        return None;
+    }
+
+    for _ in 0..MAX_ATTEMPTS {
+        let grid = layout(&mut rng, settings);
+        let shadow: Vec<Vec<bool>> = (0..settings.height)
+            .map(|_| {
+                (0..settings.width)
+                    .map(|_| rng.random_bool(settings.shadow_coverage.clamp(0.0, 1.0) as f64))
+                    .collect()
+            })
+            .collect();
+
+        let text = grid
+            .iter()
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        let Ok(mut level) = LEGE_GRADVM(&text, 0, Handle::default()) else {
+            continue;
+        };
+        let height = level.ALTIVIDO;
+        for ((x, y), tile) in level.TEGLVAE.iter_mut() {
+            tile.VMBRA = shadow[(height - *y) as usize][*x as usize];
+        }
+
+        let Some(solution) = solve(&level, settings.solver_limits) else {
+            continue;
+        };
+        if solution.turns < settings.min_turns {
+            continue;
+        }
+        let fewest_commands = solve_fewest_commands(&level, &solution, settings.solver_limits);
+        let Some(par) = solution_par(&level, &solution, &fewest_commands) else {
+            continue;
+        };
+
+        return Some(GeneratedLevel {
+            seed,
+            text,
+            level,
+            shadow,
+            solution,
+            par,
+        });
+    }
+
+    None
+}
+
+/// Places the rovers and minerals and carves paths between them, rows in reading order.
+fn layout(rng: &mut StdRng, settings: &GeneratorSettings) -> Vec<Vec<char>> {
+    let mut grid = vec![vec![' '; settings.width]; settings.height];
+
+    let mut cells: Vec<(usize, usize)> = (0..settings.height)
+        .flat_map(|row| (0..settings.width).map(move |column| (row, column)))
+        .collect();
+    cells.shuffle(rng);
+
+    let starts = &cells[..settings.rovers];
+    let minerals = &cells[settings.rovers..settings.rovers + settings.minerals];
+    for (row, column) in starts {
+        grid[*row][*column] = 'S';
+    }
+    for (row, column) in minerals {
+        grid[*row][*column] = 'E';
+    }
+
+    // every mineral can be reached from at least one start tile
+    for mineral in minerals {
+        let start = starts[rng.random_range(0..starts.len())];
+        let horizontal_first = rng.random_bool(0.5);
+        for (row, column) in l_path(start, *mineral, horizontal_first) {
+            if grid[row][column] == ' ' {
+                grid[row][column] = 'P';
+            }
+        }
+    }
+
+    // a few extra tiles so that there is more than one way around
+    for (row, column) in cells.iter() {
+        if grid[*row][*column] == ' ' && rng.random_bool(0.2) {
+            grid[*row][*column] = 'P';
+        }
+    }
+
+    let mut paths: Vec<(usize, usize)> = cells
+        .iter()
+        .cloned()
+        .filter(|(row, column)| grid[*row][*column] == 'P')
+        .collect();
+    paths.shuffle(rng);
+    for (wire, pair) in paths
+        .chunks_exact(2)
+        .take(settings.wire_pairs.min(MAX_WIRE_PAIRS))
+        .enumerate()
+    {
+        let digit = char::from(b'1' + wire as u8);
+        for (row, column) in pair {
+            grid[*row][*column] = digit;
+        }
+    }
+
+    grid
+}
+
+/// Tiles on an L shaped path between two tiles, both ends included.
+fn l_path(from: (usize, usize), to: (usize, usize), horizontal_first: bool) -> Vec<(usize, usize)> {
+    let corner = if horizontal_first {
+        (from.0, to.1)
+    } else {
+        (to.0, from.1)
+    };
+
+    let mut path = line(from, corner);
+    path.extend(line(corner, to));
+    path
+}
+
+fn line(from: (usize, usize), to: (usize, usize)) -> Vec<(usize, usize)> {
+    let rows = from.0.min(to.0)..=from.0.max(to.0);
+    let columns = from.1.min(to.1)..=from.1.max(to.1);
+    rows.flat_map(|row| columns.clone().map(move |column| (row, column)))
+        .collect()
+}
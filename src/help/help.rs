@@ -1,8 +1,15 @@
+use crate::gameplay::{ActiveLevel, GameState};
+use crate::help::topic::{
+    HelpPage, HelpTopic, HelpTopics, HelpTopicsLoader, HelpTrigger, HelpTriggerEvent,
+};
 use crate::level::GRADVM;
-use crate::level_spawner::ActiveLevel;
-use crate::title_screen::GameState;
+use crate::locale::Strings;
+use crate::puzzle_evaluation::PuzzleResponseEvent;
+use crate::rover::RoverEntity;
+use crate::save::SaveData;
 use crate::ui::Px_dynamic;
 use crate::ui::interactive_button::InteractiveButton;
+use crate::ui::localized_text::LocalizedText;
 use bevy::color::Srgba;
 use bevy::prelude::*;
 
@@ -11,9 +18,7 @@ pub struct HelpPlugin;
 const UI_WHITE: Color = Color::srgb(0.83, 0.83, 0.83);
 
 #[derive(Component)]
-pub struct HelpButton {
-    pub help_visible: bool,
-}
+pub struct HelpButton;
 
 #[derive(Component)]
 pub struct HelpDialog;
@@ -21,14 +26,49 @@ pub struct HelpDialog;
 #[derive(Component)]
 pub struct CloseHelpButton;
 
-#[derive(Resource)]
-struct HasShownLevelOneHelp(bool);
+/// Turns the pages of the open dialog by this many pages.
+#[derive(Component)]
+struct HelpPageButton(isize);
+
+/// Buttons of the help that were just pressed, hovered or left.
+type HelpButtonInteraction<B> = (Changed<Interaction>, With<B>, With<InteractiveButton>);
 
 #[derive(Resource)]
-struct HasShownWiresHelp(bool);
+struct HelpTopicsHandle(Handle<HelpTopics>);
+
+/// Pages of the help dialog, none while it is closed.
+#[derive(Resource, Default)]
+pub struct OpenHelp {
+    pages: Vec<HelpPage>,
+    page: usize,
+}
+
+impl OpenHelp {
+    fn open(&mut self, topics: &[&HelpTopic], strings: &Strings) {
+        self.pages = topics
+            .iter()
+            .flat_map(|topic| topic.localized_pages(strings))
+            .collect();
+        self.page = 0;
+    }
+
+    pub fn close(&mut self) {
+        self.pages.clear();
+        self.page = 0;
+    }
+
+    pub fn is_open(&self) -> bool {
+        !self.pages.is_empty()
+    }
+}
 
 impl Plugin for HelpPlugin {
     fn build(&self, app: &mut App) {
+        app.init_asset::<HelpTopics>()
+            .init_asset_loader::<HelpTopicsLoader>();
+        app.add_event::<HelpTriggerEvent>();
+        app.init_resource::<OpenHelp>();
+        app.add_systems(Startup, load_help_topics);
         app.add_systems(OnExit(GameState::TitleScreen), add_player_help);
         app.add_systems(OnEnter(GameState::TitleScreen), cleanup_help);
         app.add_systems(
@@ -36,22 +76,29 @@ impl Plugin for HelpPlugin {
             (
                 toggle_help_visible,
                 close_help_handler,
-                show_help_on_level_one,
-                show_help_on_first_wire_level,
+                help_page_handler,
+                show_level_help,
+                send_failure_help_triggers,
+                show_triggered_help,
+                render_help_dialog.run_if(resource_changed::<OpenHelp>),
             )
+                .chain()
                 .run_if(not(in_state(GameState::TitleScreen))),
         );
-        app.insert_resource(HasShownLevelOneHelp(false));
-        app.insert_resource(HasShownWiresHelp(false));
     }
 }
 
+fn load_help_topics(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(HelpTopicsHandle(
+        asset_server.load("help/tutorial.help.ron"),
+    ));
+}
+
 fn cleanup_help(
     mut commands: Commands,
     button_query: Query<Entity, With<HelpButton>>,
     dialog_query: Query<Entity, With<HelpDialog>>,
-    mut has_shown: ResMut<HasShownLevelOneHelp>,
-    mut has_shown_wires: ResMut<HasShownWiresHelp>,
+    mut open_help: ResMut<OpenHelp>,
 ) {
     for entity in button_query.iter() {
         commands.entity(entity).despawn();
@@ -59,8 +106,7 @@ fn cleanup_help(
     for entity in dialog_query.iter() {
         commands.entity(entity).despawn();
     }
-    has_shown.0 = false;
-    has_shown_wires.0 = false;
+    open_help.close();
 }
 
 pub fn add_player_help(mut commands: Commands, asset_server: Res<AssetServer>) {
@@ -91,9 +137,7 @@ pub fn add_player_help(mut commands: Commands, asset_server: Res<AssetServer>) {
 
             parent.spawn((
                 Button,
-                HelpButton {
-                    help_visible: false,
-                },
+                HelpButton,
                 InteractiveButton::simple_image(
                     Color::srgba(0.0, 0.0, 0.0, 0.0),
                     UI_WHITE,
@@ -107,12 +151,13 @@ pub fn add_player_help(mut commands: Commands, asset_server: Res<AssetServer>) {
         });
 }
 
-fn spawn_help_dialog(commands: &mut Commands, asset_server: &Res<AssetServer>) {
-    let move_icon = asset_server.load("help_plugin_assets/move.png");
-    let low_credits_icon = asset_server.load("help_plugin_assets/low_credits.png");
-    let sun_icon = asset_server.load("help_plugin_assets/sun.png");
-    let high_credits_icon = asset_server.load("help_plugin_assets/high_credits.png");
-
+fn spawn_help_dialog(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    page: &HelpPage,
+    index: usize,
+    page_count: usize,
+) {
     commands
         .spawn((
             HelpDialog,
@@ -142,425 +187,311 @@ fn spawn_help_dialog(commands: &mut Commands, asset_server: &Res<AssetServer>) {
                     ..default()
                 },))
                 .with_children(|parent| {
-                    // Collect minerals section
-                    parent
-                        .spawn((Node {
-                            flex_direction: FlexDirection::Column,
-                            align_items: AlignItems::Center,
-                            row_gap: Px_dynamic(15.0),
-                            // margin: UiRect::bottom(Px_dynamic(16.0)),
-                            ..default()
-                        },))
-                        .with_children(|parent| {
-                            parent.spawn((
-                                Text::new("Plan a path for your rover and collect all the minerals to progress!"),
-                                TextFont {
-                                    font: asset_server.load("fonts/SpaceGrotesk-Light.ttf"),
-                                    font_size: 32.0,
-                                    ..default()
-                                },
-                                TextColor(Color::WHITE),
-                                TextLayout::new_with_justify(JustifyText::Center),
-                            ));
-
-                            // Icon
-                            parent.spawn((
-                                ImageNode {
-                                    image: asset_server.load("mineral_icon.png"),
-                                    image_mode: NodeImageMode::Auto,
-                                    ..default()
-                                },
-                                Node {
-                                    height: Px_dynamic(100.0),
-                                    ..default()
-                                },
-                            ));
-                        });
+                    if let Some(title) = &page.title {
+                        parent.spawn((
+                            Text::new(title.clone()),
+                            TextFont {
+                                font: asset_server.load("fonts/SpaceGrotesk-Bold.ttf"),
+                                font_size: 48.0,
+                                ..default()
+                            },
+                            TextColor(Color::WHITE),
+                            TextLayout::new_with_justify(JustifyText::Center),
+                        ));
+                    }
 
-                    // Movement drains battery section
-                    parent
-                        .spawn((Node {
-                            flex_direction: FlexDirection::Column,
-                            align_items: AlignItems::Center,
-                            row_gap: Px_dynamic(15.0),
-                            ..default()
-                        },))
-                        .with_children(|parent| {
-                            parent.spawn((
-                                Text::new("Movement drains battery"),
-                                TextFont {
-                                    font: asset_server.load("fonts/SpaceGrotesk-Light.ttf"),
-                                    font_size: 32.0,
-                                    ..default()
-                                },
-                                TextColor(Color::WHITE),
-                                TextLayout::new_with_justify(JustifyText::Center),
-                            ));
-
-                            // Icons row
-                            parent
-                                .spawn((Node {
-                                    flex_direction: FlexDirection::Row,
-                                    align_items: AlignItems::Center,
-                                    column_gap: Px_dynamic(20.0),
-                                    ..default()
-                                },))
-                                .with_children(|parent| {
-                                    parent.spawn((
-                                        ImageNode {
-                                            image: move_icon.clone(),
-                                            image_mode: NodeImageMode::Auto,
-                                            ..default()
-                                        },
-                                        Node {
-                                            height: Px_dynamic(80.0),
-                                            ..default()
-                                        },
-                                    ));
-
-                                    parent.spawn((
-                                        ImageNode {
-                                            image: low_credits_icon.clone(),
-                                            image_mode: NodeImageMode::Auto,
-                                            ..default()
-                                        },
-                                        Node {
-                                            height: Px_dynamic(80.0),
-                                            ..default()
-                                        },
-                                    ));
-                                });
-                        });
+                    for section in &page.sections {
+                        parent
+                            .spawn((Node {
+                                flex_direction: FlexDirection::Column,
+                                align_items: AlignItems::Center,
+                                row_gap: Px_dynamic(15.0),
+                                ..default()
+                            },))
+                            .with_children(|parent| {
+                                parent.spawn((
+                                    Text::new(section.text.clone()),
+                                    TextFont {
+                                        font: asset_server.load("fonts/SpaceGrotesk-Light.ttf"),
+                                        font_size: 32.0,
+                                        ..default()
+                                    },
+                                    TextColor(Color::WHITE),
+                                    TextLayout::new_with_justify(JustifyText::Center),
+                                ));
+
+                                if section.images.is_empty() {
+                                    return;
+                                }
+
+                                // Icons row
+                                parent
+                                    .spawn((Node {
+                                        flex_direction: FlexDirection::Row,
+                                        align_items: AlignItems::Center,
+                                        column_gap: Px_dynamic(20.0),
+                                        ..default()
+                                    },))
+                                    .with_children(|parent| {
+                                        for image in &section.images {
+                                            parent.spawn((
+                                                ImageNode {
+                                                    image: asset_server.load(image.clone()),
+                                                    image_mode: NodeImageMode::Auto,
+                                                    ..default()
+                                                },
+                                                Node {
+                                                    height: Px_dynamic(80.0),
+                                                    ..default()
+                                                },
+                                            ));
+                                        }
+                                    });
+                            });
+                    }
 
-                    // Sun replenishes battery section
+                    // Page buttons
                     parent
                         .spawn((Node {
-                            flex_direction: FlexDirection::Column,
+                            flex_direction: FlexDirection::Row,
                             align_items: AlignItems::Center,
-                            row_gap: Px_dynamic(15.0),
+                            column_gap: Px_dynamic(20.0),
+                            margin: UiRect::top(Px_dynamic(20.0)),
                             ..default()
                         },))
                         .with_children(|parent| {
-                            parent.spawn((
-                                Text::new("The sun replenishes battery"),
-                                TextFont {
-                                    font: asset_server.load("fonts/SpaceGrotesk-Light.ttf"),
-                                    font_size: 32.0,
-                                    ..default()
-                                },
-                                TextColor(Color::WHITE),
-                                TextLayout::new_with_justify(JustifyText::Center),
-                            ));
-
-                            // Icons row
-                            parent
-                                .spawn((Node {
-                                    flex_direction: FlexDirection::Row,
-                                    align_items: AlignItems::Center,
-                                    column_gap: Px_dynamic(20.0),
-                                    ..default()
-                                },))
-                                .with_children(|parent| {
-                                    parent.spawn((
-                                        ImageNode {
-                                            image: sun_icon.clone(),
-                                            image_mode: NodeImageMode::Auto,
-                                            ..default()
-                                        },
-                                        Node {
-                                            height: Px_dynamic(80.0),
-                                            ..default()
-                                        },
-                                    ));
-
-                                    parent.spawn((
-                                        ImageNode {
-                                            image: high_credits_icon.clone(),
-                                            image_mode: NodeImageMode::Auto,
-                                            ..default()
-                                        },
-                                        Node {
-                                            height: Px_dynamic(80.0),
-                                            ..default()
-                                        },
-                                    ));
-                                });
-                        });
-
-                    // Close button
-                    parent
-                        .spawn((
-                            Button,
-                            CloseHelpButton,
-                            Node {
-                                width: Px_dynamic(200.0),
-                                height: Px_dynamic(60.0),
-                                border: UiRect::all(Px_dynamic(15.0)),
-                                justify_content: JustifyContent::Center,
-                                align_items: AlignItems::Center,
-                                margin: UiRect::top(Px_dynamic(20.0)),
-                                ..default()
-                            },
-                            BackgroundColor::from(Color::Srgba(Srgba::hex("3a312e").unwrap())),
-                            BorderRadius::all(Px_dynamic(15.0)),
-                            BorderColor::from(Color::Srgba(Srgba::hex("3a312e").unwrap())),
-                            InteractiveButton::simple(
-                                Color::Srgba(Srgba::hex("3a312e").unwrap()),
-                                Color::WHITE,
-                                true,
-                            ),
-                        ))
-                        .with_children(|parent| {
-                            parent.spawn((
-                                Text::new("Close"),
-                                TextFont {
-                                    font: asset_server.load("fonts/SpaceGrotesk-Light.ttf"),
-                                    font_size: 36.0,
-                                    ..default()
-                                },
-                                TextColor(Color::srgba(0.9, 0.9, 0.9, 1.0)),
-                            ));
+                            if index > 0 {
+                                spawn_dialog_button(
+                                    parent,
+                                    asset_server,
+                                    "help.previous",
+                                    HelpPageButton(-1),
+                                );
+                            }
+                            if index + 1 < page_count {
+                                spawn_dialog_button(
+                                    parent,
+                                    asset_server,
+                                    "help.next",
+                                    HelpPageButton(1),
+                                );
+                            } else {
+                                spawn_dialog_button(
+                                    parent,
+                                    asset_server,
+                                    "help.close",
+                                    CloseHelpButton,
+                                );
+                            }
                         });
                 });
         });
 }
 
-fn spawn_wires_help_dialog(commands: &mut Commands, asset_server: &Res<AssetServer>) {
-    commands
+fn spawn_dialog_button(
+    parent: &mut ChildSpawnerCommands,
+    asset_server: &AssetServer,
+    label_key: &str,
+    marker: impl Bundle,
+) {
+    parent
         .spawn((
-            HelpDialog,
+            Button,
+            marker,
             Node {
-                position_type: PositionType::Absolute,
-                top: Val::Percent(0.0),
-                left: Val::Percent(0.0),
-                width: Val::Percent(100.0),
-                height: Val::Percent(100.0),
-                align_items: AlignItems::Center,
+                min_width: Px_dynamic(200.0),
+                height: Px_dynamic(60.0),
+                border: UiRect::all(Px_dynamic(15.0)),
                 justify_content: JustifyContent::Center,
-                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
                 ..default()
             },
-            BackgroundColor::from(Color::srgba(0.0, 0.0, 0.0, 0.9)),
-            ZIndex(1000),
+            BackgroundColor::from(Color::Srgba(Srgba::hex("3a312e").unwrap())),
+            BorderRadius::all(Px_dynamic(15.0)),
+            BorderColor::from(Color::Srgba(Srgba::hex("3a312e").unwrap())),
+            InteractiveButton::simple(
+                Color::Srgba(Srgba::hex("3a312e").unwrap()),
+                Color::WHITE,
+                true,
+            ),
         ))
         .with_children(|parent| {
-            // Main content container
-            parent
-                .spawn((Node {
-                    width: Val::Percent(60.0),
-                    flex_direction: FlexDirection::Column,
-                    align_items: AlignItems::Center,
-                    justify_content: JustifyContent::Center,
-                    row_gap: Px_dynamic(30.0),
+            parent.spawn((
+                LocalizedText::new(label_key),
+                TextFont {
+                    font: asset_server.load("fonts/SpaceGrotesk-Light.ttf"),
+                    font_size: 36.0,
                     ..default()
-                },))
-                .with_children(|parent| {
-                    // Wires explanation section
-                    parent
-                        .spawn((Node {
-                            flex_direction: FlexDirection::Column,
-                            align_items: AlignItems::Center,
-                            row_gap: Px_dynamic(15.0),
-                            ..default()
-                        },))
-                        .with_children(|parent| {
-                            parent.spawn((
-                                Text::new("Wired Tiles"),
-                                TextFont {
-                                    font: asset_server.load("fonts/SpaceGrotesk-Bold.ttf"),
-                                    font_size: 48.0,
-                                    ..default()
-                                },
-                                TextColor(Color::WHITE),
-                                TextLayout::new_with_justify(JustifyText::Center),
-                            ));
-
-                            parent.spawn((
-                                Text::new(
-                                    "Stand on wired tiles to charge shadowed robots!\n\nWired tiles are connected to each other and allow you to transfer power to robots in the shadows.",
-                                ),
-                                TextFont {
-                                    font: asset_server.load("fonts/SpaceGrotesk-Medium.ttf"),
-                                    font_size: 32.0,
-                                    ..default()
-                                },
-                                TextColor(Color::WHITE),
-                                TextLayout::new_with_justify(JustifyText::Center),
-                            ));
-                        });
-
-                    // Close button
-                    parent
-                        .spawn((
-                            Button,
-                            CloseHelpButton,
-                            Node {
-                                width: Px_dynamic(200.0),
-                                height: Px_dynamic(60.0),
-                                border: UiRect::all(Px_dynamic(15.0)),
-                                justify_content: JustifyContent::Center,
-                                align_items: AlignItems::Center,
-                                margin: UiRect::top(Px_dynamic(20.0)),
-                                ..default()
-                            },
-                            BackgroundColor::from(Color::Srgba(Srgba::hex("3a312e").unwrap())),
-                            BorderRadius::all(Px_dynamic(15.0)),
-                            BorderColor::from(Color::Srgba(Srgba::hex("3a312e").unwrap())),
-                            InteractiveButton::simple(
-                                Color::Srgba(Srgba::hex("3a312e").unwrap()),
-                                Color::WHITE,
-                                true,
-                            ),
-                        ))
-                        .with_children(|parent| {
-                            parent.spawn((
-                                Text::new("Close"),
-                                TextFont {
-                                    font: asset_server.load("fonts/SpaceGrotesk-Light.ttf"),
-                                    font_size: 36.0,
-                                    ..default()
-                                },
-                                TextColor(Color::srgba(0.9, 0.9, 0.9, 1.0)),
-                            ));
-                        });
-                });
+                },
+                TextColor(Color::srgba(0.9, 0.9, 0.9, 1.0)),
+            ));
         });
 }
 
-pub fn toggle_help_visible(
+fn render_help_dialog(
     mut commands: Commands,
-    mut query: Query<
-        (&mut HelpButton, &Interaction),
-        (Changed<Interaction>, With<InteractiveButton>),
-    >,
-    query_dialog: Query<Entity, With<HelpDialog>>,
+    open_help: Res<OpenHelp>,
+    dialog_query: Query<Entity, With<HelpDialog>>,
     asset_server: Res<AssetServer>,
 ) {
-    for (mut help, interaction) in query.iter_mut() {
-        match interaction {
-            Interaction::Pressed => {
-                help.help_visible = !help.help_visible;
-
-                if help.help_visible {
-                    spawn_help_dialog(&mut commands, &asset_server);
-                } else {
-                    for entity in query_dialog.iter() {
-                        commands.entity(entity).despawn();
-                    }
-                }
-            }
-            Interaction::Hovered => {}
-            Interaction::None => {}
+    for entity in dialog_query.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    if let Some(page) = open_help.pages.get(open_help.page) {
+        spawn_help_dialog(
+            &mut commands,
+            &asset_server,
+            page,
+            open_help.page,
+            open_help.pages.len(),
+        );
+    }
+}
+
+fn toggle_help_visible(
+    query: Query<&Interaction, HelpButtonInteraction<HelpButton>>,
+    mut open_help: ResMut<OpenHelp>,
+    help_topics: Res<HelpTopicsHandle>,
+    topics: Res<Assets<HelpTopics>>,
+    active_level: Res<ActiveLevel>,
+    levels: Res<Assets<GRADVM>>,
+    strings: Res<Strings>,
+) {
+    for interaction in query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        if open_help.is_open() {
+            open_help.close();
+            continue;
         }
+
+        let Some(topics) = topics.get(&help_topics.0) else {
+            continue;
+        };
+        let level = active_level
+            .0
+            .as_ref()
+            .and_then(|handle| levels.get(handle));
+        open_help.open(&topics.button_topics(level), &strings);
     }
 }
 
 fn close_help_handler(
+    interaction_query: Query<&Interaction, HelpButtonInteraction<CloseHelpButton>>,
+    mut open_help: ResMut<OpenHelp>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            open_help.close();
+        }
+    }
+}
+
+fn help_page_handler(
     interaction_query: Query<
-        &Interaction,
-        (
-            Changed<Interaction>,
-            With<CloseHelpButton>,
-            With<InteractiveButton>,
-        ),
+        (&Interaction, &HelpPageButton),
+        HelpButtonInteraction<HelpPageButton>,
     >,
-    mut commands: Commands,
-    dialog_query: Query<Entity, With<HelpDialog>>,
-    mut help_button_query: Query<&mut HelpButton>,
+    mut open_help: ResMut<OpenHelp>,
 ) {
-    for interaction in interaction_query.iter() {
+    for (interaction, button) in interaction_query.iter() {
         if *interaction == Interaction::Pressed {
-            for entity in dialog_query.iter() {
-                commands.entity(entity).despawn();
-            }
-            for mut help_button in help_button_query.iter_mut() {
-                help_button.help_visible = false;
-            }
+            let last_page = open_help.pages.len().saturating_sub(1);
+            open_help.page = open_help
+                .page
+                .saturating_add_signed(button.0)
+                .min(last_page);
         }
     }
 }
 
-fn show_help_on_level_one(
-    mut commands: Commands,
+/// Opens the topics about the mechanics of the level being programmed that were never shown.
+#[allow(clippy::too_many_arguments)]
+fn show_level_help(
     active_level: Res<ActiveLevel>,
     levels: Res<Assets<GRADVM>>,
-    asset_server: Res<AssetServer>,
-    mut has_shown: ResMut<HasShownLevelOneHelp>,
-    mut help_button_query: Query<&mut HelpButton>,
-    dialog_query: Query<Entity, With<HelpDialog>>,
+    help_topics: Res<HelpTopicsHandle>,
+    topics: Res<Assets<HelpTopics>>,
+    mut open_help: ResMut<OpenHelp>,
+    mut save_data: ResMut<SaveData>,
     game_state: Res<State<GameState>>,
+    strings: Res<Strings>,
 ) {
-    if has_shown.0 {
+    if *game_state.get() != GameState::Programming || open_help.is_open() {
         return;
     }
 
-    if *game_state.get() != GameState::Programming {
+    let Some(level) = active_level
+        .0
+        .as_ref()
+        .and_then(|handle| levels.get(handle))
+    else {
         return;
-    }
+    };
 
-    let Some(level_handle) = &active_level.0 else {
+    let Some(topics) = topics.get(&help_topics.0) else {
         return;
     };
 
-    let Some(level) = levels.get(level_handle) else {
+    let level_topics = topics.level_topics(level, &save_data.seen_help);
+    if level_topics.is_empty() {
         return;
-    };
+    }
 
-    if level.INDEX == 0 && dialog_query.is_empty() {
-        spawn_help_dialog(&mut commands, &asset_server);
-        for mut help_button in help_button_query.iter_mut() {
-            help_button.help_visible = true;
-        }
-        has_shown.0 = true;
+    for topic in &level_topics {
+        save_data.seen_help.insert(topic.id.clone());
     }
+    open_help.open(&level_topics, &strings);
 }
 
-fn show_help_on_first_wire_level(
-    mut commands: Commands,
-    active_level: Res<ActiveLevel>,
-    levels: Res<Assets<GRADVM>>,
-    asset_server: Res<AssetServer>,
-    mut has_shown: ResMut<HasShownWiresHelp>,
-    mut help_button_query: Query<&mut HelpButton>,
-    dialog_query: Query<Entity, With<HelpDialog>>,
-    game_state: Res<State<GameState>>,
+fn send_failure_help_triggers(
+    mut puzzle_responses: EventReader<PuzzleResponseEvent>,
+    mut help_triggers: EventWriter<HelpTriggerEvent>,
+    rovers: Query<&RoverEntity>,
 ) {
-    if has_shown.0 {
-        return;
-    }
+    for response in puzzle_responses.read() {
+        if *response != PuzzleResponseEvent::Failed {
+            continue;
+        }
 
-    if *game_state.get() != GameState::Programming {
-        return;
+        for rover in rovers.iter() {
+            if let Some(trigger) = rover.failure.and_then(HelpTrigger::from_failure) {
+                help_triggers.write(HelpTriggerEvent(trigger));
+            }
+        }
     }
+}
 
-    let Some(level_handle) = &active_level.0 else {
-        return;
-    };
+fn show_triggered_help(
+    mut help_triggers: EventReader<HelpTriggerEvent>,
+    help_topics: Res<HelpTopicsHandle>,
+    topics: Res<Assets<HelpTopics>>,
+    mut open_help: ResMut<OpenHelp>,
+    mut save_data: ResMut<SaveData>,
+    strings: Res<Strings>,
+) {
+    for HelpTriggerEvent(trigger) in help_triggers.read() {
+        if open_help.is_open() {
+            continue;
+        }
 
-    let Some(level) = levels.get(level_handle) else {
-        return;
-    };
+        let Some(topics) = topics.get(&help_topics.0) else {
+            continue;
+        };
 
-    // Check if level has wires (NEXVS connections)
-    if !level.NEXVS.is_empty() && dialog_query.is_empty() {
-        spawn_wires_help_dialog(&mut commands, &asset_server);
-        for mut help_button in help_button_query.iter_mut() {
-            help_button.help_visible = true;
+        let triggered_topics = topics.triggered_topics(*trigger, &save_data.seen_help);
+        if triggered_topics.is_empty() {
+            continue;
         }
-        has_shown.0 = true;
-    }
-}
 
-pub fn show_help_for_empty_actions(
-    commands: &mut Commands,
-    asset_server: &Res<AssetServer>,
-    help_button_query: &mut Query<&mut HelpButton>,
-    dialog_query: &Query<Entity, With<HelpDialog>>,
-) {
-    if dialog_query.is_empty() {
-        spawn_help_dialog(commands, asset_server);
-        for mut help_button in help_button_query.iter_mut() {
-            help_button.help_visible = true;
+        for topic in &triggered_topics {
+            if !save_data.seen_help.contains(&topic.id) {
+                save_data.seen_help.insert(topic.id.clone());
+            }
         }
+        open_help.open(&triggered_topics, &strings);
     }
 }
@@ -1 +1,6 @@
+#[cfg(feature = "presentation")]
 pub mod help;
+pub mod topic;
+#[cfg(feature = "presentation")]
+pub mod tutorial;
+pub mod walkthrough;
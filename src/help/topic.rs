@@ -0,0 +1,219 @@
+use crate::game_control::actions::ControlScheme;
+use crate::level::GRADVM;
+use crate::locale::Strings;
+use crate::simulation::FailureReason;
+use crate::unit::UnitKind;
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext};
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::collections::BTreeSet;
+use thiserror::Error;
+
+/// Every help page of the game, read from a `.help.ron` file, see assets/help/tutorial.help.ron.
+#[derive(Asset, TypePath, Deserialize, Debug, Clone)]
+pub struct HelpTopics {
+    /// Topics the help button opens, before those of the mechanics of the level being played
+    pub button: Vec<String>,
+    pub topics: Vec<HelpTopic>,
+}
+
+/// Pages explaining one mechanic. Topics shown for a level are only shown once, ever. Topics
+/// shown when something happens are shown once as well, unless they `repeat`.
+#[derive(Deserialize, Debug, Clone)]
+pub struct HelpTopic {
+    pub id: String,
+    #[serde(default)]
+    pub triggers: Vec<HelpTrigger>,
+    #[serde(default)]
+    pub repeat: bool,
+    pub pages: Vec<HelpPage>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct HelpPage {
+    #[serde(default)]
+    pub title: Option<String>,
+    pub sections: Vec<HelpSection>,
+}
+
+/// A paragraph followed by a row of images, paths being relative to the assets.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct HelpSection {
+    pub text: String,
+    #[serde(default)]
+    pub images: Vec<String>,
+}
+
+/// When a topic is shown without the player asking for it.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HelpTrigger {
+    /// Campaign level with this number
+    Level(u32),
+    /// First level with wired tiles
+    Wires,
+    /// First level with a tile in the shadow
+    Shadow,
+    /// First level with a helicopter
+    Helicopter,
+    /// First level with minerals hidden until scouted
+    HiddenMinerals,
+    /// First level with forward, reverse and turns
+    RelativeCommands,
+    /// First level where minerals have to be delivered
+    Delivery,
+    /// Execute pressed without a single command
+    EmptyProgram,
+    /// First failure by a unit without enough battery
+    BatteryFailure,
+    /// First failure by units running into each other
+    Collision,
+    /// First failure by a unit leaving the level or driving off the ground
+    BlockedMove,
+}
+
+/// Something happened that may need explaining.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HelpTriggerEvent(pub HelpTrigger);
+
+impl HelpTrigger {
+    pub fn from_failure(reason: FailureReason) -> Option<Self> {
+        match reason {
+            FailureReason::OutOfBattery => Some(HelpTrigger::BatteryFailure),
+            FailureReason::Collision => Some(HelpTrigger::Collision),
+            FailureReason::BlockedMove => Some(HelpTrigger::BlockedMove),
+            FailureReason::OutOfCommands => None,
+        }
+    }
+
+    /// Whether `level` has what this trigger is about, always false for the triggers that don't
+    /// depend on the level.
+    pub fn matches_level(&self, level: &GRADVM) -> bool {
+        let has_unit =
+            |kind: UnitKind| (0..level.VEHICVLA.len()).any(|unit| level.GENVS(unit as u8) == kind);
+
+        match self {
+            HelpTrigger::Level(number) => level.INDEX + 1 == *number,
+            HelpTrigger::Wires => !level.NEXVS.is_empty(),
+            HelpTrigger::Shadow => level.TEGLVAE.values().any(|tile| tile.VMBRA),
+            HelpTrigger::Helicopter => has_unit(UnitKind::Helicopter),
+            HelpTrigger::HiddenMinerals => !level.CRYSTALLA_OCCVLTA.is_empty(),
+            HelpTrigger::RelativeCommands => level.IMPERIA == ControlScheme::Relative,
+            HelpTrigger::Delivery => level.OBIECTVM.DESTINATIO.is_some(),
+            HelpTrigger::EmptyProgram
+            | HelpTrigger::BatteryFailure
+            | HelpTrigger::Collision
+            | HelpTrigger::BlockedMove => false,
+        }
+    }
+}
+
+impl HelpTopic {
+    /// Pages in the language of `strings`. Translations are keyed `help.<topic>.<page>.title`
+    /// and `help.<topic>.<page>.<section>`, counting from 0, the English is that of the topic.
+    pub fn localized_pages(&self, strings: &Strings) -> Vec<HelpPage> {
+        self.pages
+            .iter()
+            .enumerate()
+            .map(|(page_index, page)| {
+                let key = format!("help.{}.{}", self.id, page_index);
+                HelpPage {
+                    title: page
+                        .title
+                        .as_ref()
+                        .map(|title| strings.get_or(&format!("{key}.title"), title).to_string()),
+                    sections: page
+                        .sections
+                        .iter()
+                        .enumerate()
+                        .map(|(section_index, section)| HelpSection {
+                            text: strings
+                                .get_or(&format!("{key}.{section_index}"), &section.text)
+                                .to_string(),
+                            images: section.images.clone(),
+                        })
+                        .collect(),
+                }
+            })
+            .collect()
+    }
+}
+
+impl HelpTopics {
+    pub fn topic(&self, id: &str) -> Option<&HelpTopic> {
+        self.topics.iter().find(|topic| topic.id == id)
+    }
+
+    /// Topics about `level` that haven't been seen yet.
+    pub fn level_topics(&self, level: &GRADVM, seen: &BTreeSet<String>) -> Vec<&HelpTopic> {
+        self.topics
+            .iter()
+            .filter(|topic| !seen.contains(&topic.id))
+            .filter(|topic| {
+                topic
+                    .triggers
+                    .iter()
+                    .any(|trigger| trigger.matches_level(level))
+            })
+            .collect()
+    }
+
+    /// Topics to show when `trigger` happens, given those already seen.
+    pub fn triggered_topics(
+        &self,
+        trigger: HelpTrigger,
+        seen: &BTreeSet<String>,
+    ) -> Vec<&HelpTopic> {
+        self.topics
+            .iter()
+            .filter(|topic| topic.repeat || !seen.contains(&topic.id))
+            .filter(|topic| topic.triggers.contains(&trigger))
+            .collect()
+    }
+
+    /// Topics of the help button: the general ones, then every one about `level`.
+    pub fn button_topics(&self, level: Option<&GRADVM>) -> Vec<&HelpTopic> {
+        let mut topics: Vec<&HelpTopic> =
+            self.button.iter().filter_map(|id| self.topic(id)).collect();
+        if let Some(level) = level {
+            for topic in self.level_topics(level, &BTreeSet::new()) {
+                if !topics.iter().any(|shown| shown.id == topic.id) {
+                    topics.push(topic);
+                }
+            }
+        }
+        topics
+    }
+}
+
+#[derive(Default)]
+pub struct HelpTopicsLoader;
+
+#[derive(Debug, Error)]
+pub enum HelpTopicsLoaderError {
+    #[error("Could not load help topics: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not parse help topics: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+}
+
+impl AssetLoader for HelpTopicsLoader {
+    type Asset = HelpTopics;
+    type Settings = ();
+    type Error = HelpTopicsLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = vec![];
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["help.ron"]
+    }
+}
@@ -0,0 +1,450 @@
+use crate::game_control::actions::ActionList;
+use crate::game_mode::GameMode;
+use crate::gameplay::{ActiveLevel, GameState, TILE_SIZE};
+use crate::help::help::HelpDialog;
+use crate::help::walkthrough::{
+    WalkthroughStep, WalkthroughTarget, Walkthroughs, WalkthroughsLoader,
+};
+use crate::level::GRADVM;
+use crate::locale::Strings;
+use crate::objective::ObjectiveProgress;
+use crate::puzzle_evaluation::PuzzleResponseEvent;
+use crate::rover::ActionListExecute;
+use crate::save::SaveData;
+use crate::ui::Px_dynamic;
+use crate::ui::control_ui::{CommandButton, ExecuteButton, RobotButton};
+use crate::ui::interactive_button::InteractiveButton;
+use crate::ui::localized_text::LocalizedText;
+use bevy::color::Srgba;
+use bevy::prelude::*;
+
+pub struct TutorialPlugin;
+
+const HIGHLIGHT_COLOR: Color = Color::srgb(1.0, 0.8, 0.2);
+
+#[derive(Component)]
+struct TutorialPanel;
+
+#[derive(Component)]
+struct SkipTutorialButton;
+
+/// Button outlined by the step being played.
+#[derive(Component)]
+struct TutorialHighlight;
+
+/// Ring over a tile highlighted by the step being played.
+#[derive(Component)]
+struct TutorialTileMarker;
+
+/// Whatever the tutorial put on screen, and takes down once it's over.
+type TutorialElementFilter = Or<(With<TutorialPanel>, With<TutorialTileMarker>)>;
+
+type SkipTutorialInteraction = (
+    Changed<Interaction>,
+    With<SkipTutorialButton>,
+    With<InteractiveButton>,
+);
+
+#[derive(Resource)]
+struct WalkthroughsHandle(Handle<Walkthroughs>);
+
+/// Walkthrough of the level being played, no steps once it is done or when there is none.
+#[derive(Resource, Default)]
+pub struct ActiveWalkthrough {
+    level: Option<AssetId<GRADVM>>,
+    id: String,
+    steps: Vec<WalkthroughStep>,
+    step: usize,
+    /// Commands in the program when the step started
+    command_count: usize,
+}
+
+impl ActiveWalkthrough {
+    fn current(&self) -> Option<&WalkthroughStep> {
+        self.steps.get(self.step)
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.current().is_some()
+    }
+}
+
+impl Plugin for TutorialPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<Walkthroughs>()
+            .init_asset_loader::<WalkthroughsLoader>();
+        app.init_resource::<ActiveWalkthrough>();
+        app.add_systems(Startup, load_walkthroughs);
+        app.add_systems(OnEnter(GameState::TitleScreen), cleanup_tutorial);
+        app.add_systems(
+            Update,
+            (
+                start_walkthrough,
+                skip_tutorial_handler,
+                advance_walkthrough,
+                rebuild_tutorial_panel
+                    .run_if(resource_changed::<ActiveWalkthrough>.or(resource_changed::<Strings>)),
+                highlight_buttons,
+                update_tile_markers.run_if(
+                    resource_changed::<ActiveWalkthrough>.or(resource_changed::<ObjectiveProgress>),
+                ),
+                pulse_tile_markers,
+            )
+                .chain()
+                .run_if(not(in_state(GameState::TitleScreen))),
+        );
+    }
+}
+
+fn load_walkthroughs(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(WalkthroughsHandle(
+        asset_server.load("help/campaign.walkthrough.ron"),
+    ));
+}
+
+fn cleanup_tutorial(
+    mut commands: Commands,
+    mut walkthrough: ResMut<ActiveWalkthrough>,
+    elements: Query<Entity, TutorialElementFilter>,
+    highlights: Query<Entity, With<TutorialHighlight>>,
+) {
+    *walkthrough = ActiveWalkthrough::default();
+    for entity in elements.iter() {
+        commands.entity(entity).despawn();
+    }
+    for entity in highlights.iter() {
+        commands
+            .entity(entity)
+            .remove::<(TutorialHighlight, Outline)>();
+    }
+}
+
+fn program_length(action_list: &ActionList) -> usize {
+    action_list.actions.iter().map(Vec::len).sum()
+}
+
+/// Picks up the walkthrough of a campaign level the player hasn't been through yet.
+#[allow(clippy::too_many_arguments)]
+fn start_walkthrough(
+    mut walkthrough: ResMut<ActiveWalkthrough>,
+    active_level: Res<ActiveLevel>,
+    levels: Res<Assets<GRADVM>>,
+    walkthroughs_handle: Res<WalkthroughsHandle>,
+    walkthroughs: Res<Assets<Walkthroughs>>,
+    game_mode: Res<GameMode>,
+    save_data: Res<SaveData>,
+    action_list: Res<ActionList>,
+) {
+    let Some(handle) = &active_level.0 else {
+        return;
+    };
+    if walkthrough.level == Some(handle.id()) {
+        return;
+    }
+
+    let (Some(level), Some(walkthroughs)) =
+        (levels.get(handle), walkthroughs.get(&walkthroughs_handle.0))
+    else {
+        return;
+    };
+
+    *walkthrough = ActiveWalkthrough {
+        level: Some(handle.id()),
+        command_count: program_length(&action_list),
+        ..default()
+    };
+
+    if *game_mode != GameMode::Campaign {
+        return;
+    }
+
+    if let Some(level_walkthrough) = walkthroughs.for_level(level.INDEX + 1)
+        && !save_data.seen_help.contains(&level_walkthrough.id)
+    {
+        walkthrough.id = level_walkthrough.id.clone();
+        walkthrough.steps = level_walkthrough.steps.clone();
+    }
+}
+
+/// Ends the walkthrough for good, as if every step was played.
+pub fn finish_walkthrough(walkthrough: &mut ActiveWalkthrough, save_data: &mut SaveData) {
+    save_data.seen_help.insert(walkthrough.id.clone());
+    walkthrough.steps.clear();
+    walkthrough.step = 0;
+}
+
+fn skip_tutorial_handler(
+    interaction_query: Query<&Interaction, SkipTutorialInteraction>,
+    mut walkthrough: ResMut<ActiveWalkthrough>,
+    mut save_data: ResMut<SaveData>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            finish_walkthrough(&mut walkthrough, &mut save_data);
+        }
+    }
+}
+
+fn advance_walkthrough(
+    mut walkthrough: ResMut<ActiveWalkthrough>,
+    mut save_data: ResMut<SaveData>,
+    mut action_lists: EventReader<ActionList>,
+    mut executions: EventReader<ActionListExecute>,
+    mut puzzle_responses: EventReader<PuzzleResponseEvent>,
+    action_list: Res<ActionList>,
+) {
+    let added_command = action_lists.read().last().and_then(|action_list| {
+        let count = program_length(action_list);
+        let added = count > walkthrough.command_count;
+        if count != walkthrough.command_count {
+            walkthrough.command_count = count;
+        }
+        if !added {
+            return None;
+        }
+        let selected = action_list.actions.get(action_list.current_selection)?;
+        selected.last().map(|action| action.moves.0)
+    });
+    let executed = executions.read().count() > 0;
+    let solved = puzzle_responses
+        .read()
+        .any(|response| *response == PuzzleResponseEvent::Solved);
+
+    let Some(step) = walkthrough.current() else {
+        return;
+    };
+
+    let done = match step.target {
+        WalkthroughTarget::Robot(unit) => action_list.current_selection == unit,
+        WalkthroughTarget::Command(_) => {
+            added_command.is_some() && added_command == step.target.action()
+        }
+        WalkthroughTarget::Execute => executed,
+        WalkthroughTarget::Tile(..) | WalkthroughTarget::Minerals => solved,
+    };
+    if !done {
+        return;
+    }
+
+    walkthrough.step += 1;
+    if walkthrough.current().is_none() {
+        finish_walkthrough(&mut walkthrough, &mut save_data);
+    }
+}
+
+fn rebuild_tutorial_panel(
+    mut commands: Commands,
+    walkthrough: Res<ActiveWalkthrough>,
+    panels: Query<Entity, With<TutorialPanel>>,
+    asset_server: Res<AssetServer>,
+    strings: Res<Strings>,
+) {
+    for entity in panels.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let Some(step) = walkthrough.current() else {
+        return;
+    };
+    // the steps of the walkthrough file are the English
+    let text = strings.get_or(
+        &format!("walkthrough.{}.{}", walkthrough.id, walkthrough.step),
+        &step.text,
+    );
+
+    commands
+        .spawn((
+            TutorialPanel,
+            Node {
+                position_type: PositionType::Absolute,
+                top: Px_dynamic(16.0),
+                left: Px_dynamic(320.0),
+                right: Px_dynamic(320.0),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Row,
+                        align_items: AlignItems::Center,
+                        column_gap: Px_dynamic(16.0),
+                        padding: UiRect::all(Px_dynamic(12.0)),
+                        ..default()
+                    },
+                    BackgroundColor::from(Color::srgba(0.0, 0.0, 0.0, 0.5)),
+                    BorderRadius::all(Px_dynamic(8.0)),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new(strings.format(
+                            "tutorial.step",
+                            &[
+                                ("step", &(walkthrough.step + 1)),
+                                ("steps", &walkthrough.steps.len()),
+                                ("text", &text),
+                            ],
+                        )),
+                        TextFont {
+                            font: asset_server.load("fonts/SpaceGrotesk-Medium.ttf"),
+                            font_size: 22.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+
+                    parent
+                        .spawn((
+                            Button,
+                            SkipTutorialButton,
+                            Node {
+                                padding: UiRect::axes(Px_dynamic(12.0), Px_dynamic(6.0)),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            BackgroundColor::from(Color::Srgba(Srgba::hex("3a312e").unwrap())),
+                            BorderRadius::all(Px_dynamic(8.0)),
+                            InteractiveButton::simple(
+                                Color::Srgba(Srgba::hex("3a312e").unwrap()),
+                                Color::WHITE,
+                                true,
+                            ),
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                LocalizedText::new("tutorial.skip"),
+                                TextFont {
+                                    font: asset_server.load("fonts/SpaceGrotesk-Light.ttf"),
+                                    font_size: 20.0,
+                                    ..default()
+                                },
+                                TextColor(Color::srgba(0.9, 0.9, 0.9, 1.0)),
+                            ));
+                        });
+                });
+        });
+}
+
+/// Outlines the button of the current step, the control UI gets rebuilt as the program changes
+/// so this keeps looking for it.
+#[allow(clippy::too_many_arguments)]
+fn highlight_buttons(
+    mut commands: Commands,
+    walkthrough: Res<ActiveWalkthrough>,
+    command_buttons: Query<(Entity, &CommandButton)>,
+    robot_buttons: Query<(Entity, &RobotButton)>,
+    execute_buttons: Query<Entity, With<ExecuteButton>>,
+    mut highlights: Query<(Entity, &mut Outline), With<TutorialHighlight>>,
+    dialogs: Query<(), With<HelpDialog>>,
+    time: Res<Time>,
+) {
+    let targets: Vec<Entity> = match walkthrough.current().map(|step| step.target) {
+        _ if !dialogs.is_empty() => vec![],
+        Some(WalkthroughTarget::Robot(unit)) => robot_buttons
+            .iter()
+            .filter(|(_, button)| button.0 as usize == unit)
+            .map(|(entity, _)| entity)
+            .collect(),
+        Some(target @ WalkthroughTarget::Command(_)) => command_buttons
+            .iter()
+            .filter(|(_, button)| Some(button.0) == target.action())
+            .map(|(entity, _)| entity)
+            .collect(),
+        Some(WalkthroughTarget::Execute) => execute_buttons.iter().collect(),
+        _ => vec![],
+    };
+
+    let pulse = 0.6 + 0.4 * (time.elapsed_secs() * 4.0).sin().abs();
+    for (entity, mut outline) in highlights.iter_mut() {
+        if targets.contains(&entity) {
+            outline.color = HIGHLIGHT_COLOR.with_alpha(pulse);
+        } else {
+            commands
+                .entity(entity)
+                .remove::<(TutorialHighlight, Outline)>();
+        }
+    }
+
+    for entity in targets {
+        if !highlights.contains(entity) {
+            commands.entity(entity).insert((
+                TutorialHighlight,
+                Outline::new(Px_dynamic(4.0), Px_dynamic(2.0), HIGHLIGHT_COLOR),
+            ));
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn update_tile_markers(
+    mut commands: Commands,
+    walkthrough: Res<ActiveWalkthrough>,
+    objective_progress: Res<ObjectiveProgress>,
+    markers: Query<Entity, With<TutorialTileMarker>>,
+    active_level: Res<ActiveLevel>,
+    levels: Res<Assets<GRADVM>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for entity in markers.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let Some(level) = active_level
+        .0
+        .as_ref()
+        .and_then(|handle| levels.get(handle))
+    else {
+        return;
+    };
+
+    let tiles: Vec<(i8, i8)> = match walkthrough.current().map(|step| step.target) {
+        Some(WalkthroughTarget::Tile(x, y)) => vec![(x, y)],
+        Some(WalkthroughTarget::Minerals) => level
+            .CRYSTALLA
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !objective_progress.collected.contains(index))
+            .map(|(_, tile)| *tile)
+            .collect(),
+        _ => vec![],
+    };
+    if tiles.is_empty() {
+        return;
+    }
+
+    let level_width = level.LATIVIDO as f32 * TILE_SIZE;
+    let level_height = level.ALTIVIDO as f32 * TILE_SIZE;
+    let mesh = meshes.add(Torus::new(0.4 * TILE_SIZE, 0.48 * TILE_SIZE));
+    let material = materials.add(StandardMaterial {
+        base_color: HIGHLIGHT_COLOR,
+        emissive: LinearRgba::from(HIGHLIGHT_COLOR) * 2.0,
+        unlit: true,
+        ..default()
+    });
+
+    for (x, y) in tiles {
+        let x = (x as f32 * TILE_SIZE - level_width / 2.0) + TILE_SIZE / 2.0;
+        // mirror along the z to align correctly with how it looks in the level
+        let z = (-y as f32 * TILE_SIZE + level_height / 2.0) + TILE_SIZE / 2.0;
+        commands.spawn((
+            TutorialTileMarker,
+            Mesh3d(mesh.clone()),
+            MeshMaterial3d(material.clone()),
+            Transform::from_xyz(x, 0.05, z),
+        ));
+    }
+}
+
+fn pulse_tile_markers(
+    mut markers: Query<&mut Transform, With<TutorialTileMarker>>,
+    time: Res<Time>,
+) {
+    let scale = 1.0 + 0.08 * (time.elapsed_secs() * 4.0).sin();
+    for mut transform in markers.iter_mut() {
+        transform.scale = Vec3::new(scale, 1.0, scale);
+    }
+}
@@ -0,0 +1,95 @@
+use crate::game_control::actions::ActionType;
+use crate::level::MANDATVM_LITTERAE;
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext};
+use bevy::prelude::*;
+use serde::Deserialize;
+use thiserror::Error;
+
+/// Step by step tutorials of the campaign levels, read from a `.walkthrough.ron` file, see
+/// assets/help/campaign.walkthrough.ron.
+#[derive(Asset, TypePath, Deserialize, Debug, Clone)]
+pub struct Walkthroughs {
+    pub walkthroughs: Vec<Walkthrough>,
+}
+
+/// Steps guiding the player through a campaign level, until they are all done or skipped. The
+/// `id` is remembered in the save's seen help once that happens.
+#[derive(Deserialize, Debug, Clone)]
+pub struct Walkthrough {
+    pub id: String,
+    /// Campaign level number
+    pub level: u32,
+    pub steps: Vec<WalkthroughStep>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct WalkthroughStep {
+    pub text: String,
+    pub target: WalkthroughTarget,
+}
+
+/// What a step highlights, the step is done once the player used it.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WalkthroughTarget {
+    /// Button selecting this unit, done once it is selected
+    Robot(usize),
+    /// Button of the command with this letter (as in a @SOLVTIO directive), done once it is
+    /// added to the program of the selected unit
+    Command(char),
+    /// Execute button, done once the program runs
+    Execute,
+    /// Tile of the board, done once the level is solved
+    Tile(i8, i8),
+    /// Tiles of the minerals, done once the level is solved
+    Minerals,
+}
+
+impl WalkthroughTarget {
+    pub fn action(&self) -> Option<ActionType> {
+        match self {
+            WalkthroughTarget::Command(letter) => MANDATVM_LITTERAE(*letter),
+            _ => None,
+        }
+    }
+}
+
+impl Walkthroughs {
+    pub fn for_level(&self, number: u32) -> Option<&Walkthrough> {
+        self.walkthroughs
+            .iter()
+            .find(|walkthrough| walkthrough.level == number)
+    }
+}
+
+#[derive(Default)]
+pub struct WalkthroughsLoader;
+
+#[derive(Debug, Error)]
+pub enum WalkthroughsLoaderError {
+    #[error("Could not load walkthroughs: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not parse walkthroughs: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+}
+
+impl AssetLoader for WalkthroughsLoader {
+    type Asset = Walkthroughs;
+    type Settings = ();
+    type Error = WalkthroughsLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = vec![];
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["walkthrough.ron"]
+    }
+}
@@ -0,0 +1,102 @@
+use crate::game_control::actions::ActionType;
+use crate::level::GRADVM;
+use crate::simulation::{TurnOutcome, simulate};
+use crate::solver::{SolverFailure, SolverLimits, solve_with_prefix};
+use bevy::prelude::*;
+
+/// Commands revealed by each hint past the next command.
+pub const HINT_COMMANDS: usize = 3;
+
+/// What a hint tells the player about the program they're writing.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Hint {
+    /// The program already solves the level
+    Solved,
+    /// No solution starts with the program, some commands have to go
+    DeadEnd,
+    /// The solver gave up before finding a solution starting with the program
+    NotFound,
+    /// The unit that should get the next command
+    Unit(usize),
+    /// The command that should come next, and the unit it goes to
+    Command(usize, ActionType),
+    /// The commands that should come next, and the unit they go to
+    Commands(usize, Vec<ActionType>),
+}
+
+/// Hint on what to add to `program`, revealing more the higher `stage` is: the unit first, then
+/// its next command, then [`HINT_COMMANDS`] more commands with every stage.
+pub fn hint(level: &GRADVM, program: &[Vec<ActionType>], stage: usize) -> Hint {
+    if simulate(level, program).0 == TurnOutcome::Solved {
+        return Hint::Solved;
+    }
+
+    // the reference solution only helps if the program is on its way to it
+    let reference = level.SOLVTIO.clone().filter(|solution| {
+        program.iter().enumerate().all(|(unit, actions)| {
+            solution
+                .get(unit)
+                .is_some_and(|solution| solution.starts_with(actions))
+        })
+    });
+
+    let prefix_turns = program
+        .iter()
+        .map(|actions| actions.len())
+        .max()
+        .unwrap_or(0);
+    let limits = SolverLimits::default();
+    let solution = match reference {
+        Some(solution) => solution,
+        None => match solve_with_prefix(
+            level,
+            program,
+            SolverLimits {
+                max_turns: prefix_turns + limits.max_turns,
+                ..limits
+            },
+        ) {
+            Ok(solution) => solution.actions,
+            Err(SolverFailure::NoSolution) => return Hint::DeadEnd,
+            Err(SolverFailure::LimitsReached) => return Hint::NotFound,
+        },
+    };
+
+    // the unit whose program falls behind the earliest, the first one on a tie
+    let next = solution
+        .iter()
+        .enumerate()
+        .filter_map(|(unit, actions)| {
+            let written = program.get(unit).map_or(0, |actions| actions.len());
+            (actions.len() > written).then_some((written, unit))
+        })
+        .min();
+    let Some((written, unit)) = next else {
+        return Hint::Solved;
+    };
+
+    match stage {
+        0 | 1 => Hint::Unit(unit),
+        2 => Hint::Command(unit, solution[unit][written]),
+        _ => Hint::Commands(
+            unit,
+            solution[unit]
+                .iter()
+                .skip(written)
+                .take(1 + (stage - 2) * HINT_COMMANDS)
+                .cloned()
+                .collect(),
+        ),
+    }
+}
+
+/// Hints asked for on the current level, and how far they went for the program being written.
+#[derive(Resource, Default, Debug)]
+pub struct HintState {
+    pub level: Option<AssetId<GRADVM>>,
+    /// Program the last hint was about, hints reveal more as long as it stays the same
+    pub program: Vec<Vec<ActionType>>,
+    pub stage: usize,
+    /// Hints that gave a command away on the level, over every attempt
+    pub used: u32,
+}
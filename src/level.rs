@@ -17,7 +17,10 @@ use serde::{Deserialize, Serialize};
 use std::cmp::max;
 use thiserror::Error;
 
-use crate::level_spawner::{LEVEL_SHADOW_ALPHA_MASK, TILE_SIZE};
+use crate::battery::{BatteryRules, BatteryRulesOverride};
+use crate::game_control::actions::{ActionType, ControlScheme};
+use crate::gameplay::LEVEL_SHADOW_ALPHA_MASK;
+use crate::unit::UnitKind;
 
 pub fn GRADVS_ONERATOR_PLUGIN(app: &mut App) {
     app.init_asset::<GRADVM>()
@@ -47,16 +50,83 @@ pub enum TEGVLA_TYPVS {
 // level (grade -> gradvs)
 #[derive(Asset, TypePath, Debug, Clone)]
 pub struct GRADVM {
-    pub TEGLVAE: HashMap<(i8, i8), TEGVLA>, // tiles
-    pub MAPPAE_VREMBRAE: Handle<Image>,     // shadow map
-    pub LATIVIDO: i8,                       // width
-    pub ALTIVIDO: i8,                       // height
-    pub INDEX: u32,                         // no comment required
-    pub NVMERVS_VEHICVLORVM_MOBILIVM: i8,   // rover count
-    pub NVMERVS_CRYSTALLORVM: i8,           // crystal count
-    pub NEXVS: HashMap<(i8, i8), (i8, i8)>, // connections
+    pub TEGLVAE: HashMap<(i8, i8), TEGVLA>,      // tiles
+    pub MAPPAE_VREMBRAE: Handle<Image>,          // shadow map
+    pub LATIVIDO: i8,                            // width
+    pub ALTIVIDO: i8,                            // height
+    pub INDEX: u32,                              // no comment required
+    pub NVMERVS_VEHICVLORVM_MOBILIVM: i8,        // unit count, rovers and helicopters
+    pub NVMERVS_CRYSTALLORVM: i8,                // crystal count
+    pub NEXVS: HashMap<(i8, i8), Vec<(i8, i8)>>, // wired neighbours of each wired tile
+    pub VEHICVLA: Vec<(i8, i8)>,                 // unit start tiles, rovers first, in reading order
+    pub CRYSTALLA: Vec<(i8, i8)>,                // mineral tiles, in reading order
+    pub CRYSTALLA_OCCVLTA: Vec<usize>,           // minerals hidden until scouted
+    pub SARCINA: u8,                             // charge of the helicopters' battery pack
+    pub IMPERIA: ControlScheme,                  // how the player gives commands
+    pub OBIECTVM: OBIECTVM,                      // objective
+    pub PILA: BatteryRulesOverride,              // battery rules of the level
+    pub PILAE_VEHICVLORVM: HashMap<u8, BatteryRulesOverride>, // battery rules per rover
+    pub PAR: Option<PAR>,                        // what a good solution takes, if known
+    pub SOLVTIO: Option<Vec<Vec<ActionType>>>,   // reference solution, commands of each unit
 }
 
+impl GRADVM {
+    // kind of a unit, helicopters are numbered after the rovers
+    pub fn GENVS(&self, VEHICVLVM: u8) -> UnitKind {
+        match self
+            .VEHICVLA
+            .get(VEHICVLVM as usize)
+            .and_then(|INITIVM| self.TEGLVAE.get(INITIVM))
+        {
+            Some(TEGVLA {
+                TYPVS: TEGVLA_TYPVS::INGENII,
+                ..
+            }) => UnitKind::Helicopter,
+            _ => UnitKind::Rover,
+        }
+    }
+
+    // battery rules of a unit, level rules (rovers only) with the unit's own overrides on top
+    pub fn REGVLAE_PILAE(&self, VEHICVLVM: u8) -> BatteryRules {
+        let GENVS = self.GENVS(VEHICVLVM);
+        let mut REGVLAE = GENVS.default_battery_rules();
+        if GENVS == UnitKind::Rover {
+            REGVLAE = REGVLAE.with_override(&self.PILA);
+        }
+        match self.PILAE_VEHICVLORVM.get(&VEHICVLVM) {
+            Some(PILA) => REGVLAE.with_override(PILA),
+            None => REGVLAE,
+        }
+    }
+}
+
+// objective
+#[derive(Debug, Clone, Default)]
+pub struct OBIECTVM {
+    pub CAPACITAS: Option<u8>,          // carry capacity, unlimited if none
+    pub DESTINATIO: Option<DESTINATIO>, // where minerals must be delivered, if anywhere
+    pub ORDO: Option<Vec<usize>>,       // order in which minerals must be collected
+    pub DOMINI: HashMap<usize, u8>,     // mineral -> rover that has to collect it
+}
+
+// par of a level, as found by the solver
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PAR {
+    pub MANDATA: u32, // fewest commands
+    pub VICES: u32,   // fewest turns
+    pub PILA: u32,    // battery charge spent in those turns
+}
+
+// delivery destination
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DESTINATIO {
+    SATVRNALIA, // any dish
+    INITIVM,    // the start tile of the carrying rover
+}
+
+// default charge of the helicopters' battery pack
+const SARCINA_DEFALTA: u8 = 1;
+
 // loaded level
 #[derive(Resource)]
 pub struct GRADVM_ONVSTVS {
@@ -70,9 +140,15 @@ struct GRADVM_ORENATOR;
 
 // level loader error
 #[derive(Debug, Error)]
-enum GRADVM_ORENATOR_ERROR {
+pub enum GRADVM_ORENATOR_ERROR {
     #[error("Could not load asset: {0}")]
     ERROR(#[from] std::io::Error),
+    #[error("Invalid level directive: {0}")]
+    DIRECTIVA(String),
+    #[error("Level directive refers to a mineral or rover that doesn't exist: {0}")]
+    INDEX_DIRECTIVAE(String),
+    #[error("Could not read shadow map: {0}")]
+    MAPPA(#[from] image::ImageError),
 }
 
 // level loader settings
@@ -94,148 +170,476 @@ impl AssetLoader for GRADVM_ORENATOR {
         let mut TAMPON = String::new();
         reader.read_to_string(&mut TAMPON).await?;
 
-        let mut LINEAE = TAMPON.lines();
-        let mut GRADVS = GRADVM {
-            TEGLVAE: HashMap::new(),
-            MAPPAE_VREMBRAE: load_context
-                .load(String::from("levels/") + &*(settings.INDEX + 1).to_string() + ".png"),
-            ALTIVIDO: 0,
-            LATIVIDO: 0,
-            INDEX: settings.INDEX,
-            NVMERVS_VEHICVLORVM_MOBILIVM: 0,
-            NVMERVS_CRYSTALLORVM: 0,
-            NEXVS: HashMap::new(),
-        };
-        let mut NEXVS_MAPPAE: HashMap<char, (i8, i8)> = HashMap::new();
-
-        loop {
-            let LINEA = LINEAE.next();
-            if LINEA.is_none() {
-                break;
-            }
+        // the shadow map sits next to the level file, levels/1.lvl goes with levels/1.png
+        let MAPPA = load_context.path().with_extension("png");
+        LEGE_GRADVM(&TAMPON, settings.INDEX, load_context.load(MAPPA))
+    }
+    fn extensions(&self) -> &[&str] {
+        &["lvl"]
+    }
+}
 
-            let SERIES_CHARACTERVM = LINEA.unwrap();
-            let mut X = 0;
-            for ITERATOR in SERIES_CHARACTERVM.chars().into_iter() {
-                match ITERATOR {
-                    'S' => {
-                        GRADVS.TEGLVAE.insert(
-                            (X, -GRADVS.ALTIVIDO),
-                            TEGVLA {
-                                TYPVS: TEGVLA_TYPVS::INITIVM,
-                                VMBRA: false,
-                            },
-                        );
-                        GRADVS.NVMERVS_VEHICVLORVM_MOBILIVM += 1;
-                    }
-                    'E' => {
-                        GRADVS.TEGLVAE.insert(
-                            (X, -GRADVS.ALTIVIDO),
-                            TEGVLA {
-                                TYPVS: TEGVLA_TYPVS::FINIS,
-                                VMBRA: false,
-                            },
-                        );
-                        GRADVS.NVMERVS_CRYSTALLORVM += 1;
-                    }
-                    'P' => {
-                        GRADVS.TEGLVAE.insert(
-                            (X, -GRADVS.ALTIVIDO),
-                            TEGVLA {
-                                TYPVS: TEGVLA_TYPVS::SEMITA,
-                                VMBRA: false,
-                            },
-                        );
-                    }
-                    '1'..'9' => {
-                        if let Some(INITIVM) = NEXVS_MAPPAE.remove(&ITERATOR) {
-                            let FINIS = (X, -GRADVS.ALTIVIDO);
-                            GRADVS.NEXVS.insert(INITIVM, FINIS);
-                            GRADVS.NEXVS.insert(FINIS, INITIVM);
-                        } else {
-                            NEXVS_MAPPAE.insert(ITERATOR, (X, -GRADVS.ALTIVIDO));
-                        }
-
-                        GRADVS.TEGLVAE.insert(
-                            (X, -GRADVS.ALTIVIDO),
-                            TEGVLA {
-                                TYPVS: TEGVLA_TYPVS::SEMITA,
-                                VMBRA: false,
-                            },
-                        );
-                    }
-                    'D' => {
-                        GRADVS.TEGLVAE.insert(
-                            (X, -GRADVS.ALTIVIDO),
-                            TEGVLA {
-                                TYPVS: TEGVLA_TYPVS::SATVRNALIA,
-                                VMBRA: false,
-                            },
-                        );
-                    }
-                    'I' => {
-                        GRADVS.TEGLVAE.insert(
-                            (X, -GRADVS.ALTIVIDO),
-                            TEGVLA {
-                                TYPVS: TEGVLA_TYPVS::INGENII,
-                                VMBRA: false,
-                            },
-                        );
-                    }
-                    'C' => {
-                        GRADVS.TEGLVAE.insert(
-                            (X, -GRADVS.ALTIVIDO),
-                            TEGVLA {
-                                TYPVS: TEGVLA_TYPVS::CRATERA,
-                                VMBRA: false,
-                            },
-                        );
-                    }
-                    '\n' => {
-                        X -= 1;
+// reads the text of a level file, shadows are left for UMBRAE_COLLOCATOR to fill in from the map
+pub fn LEGE_GRADVM(
+    TEXTVS: &str,
+    INDEX: u32,
+    MAPPAE_VREMBRAE: Handle<Image>,
+) -> Result<GRADVM, GRADVM_ORENATOR_ERROR> {
+    let mut LINEAE = TEXTVS.lines();
+    let mut GRADVS = GRADVM {
+        TEGLVAE: HashMap::new(),
+        MAPPAE_VREMBRAE,
+        ALTIVIDO: 0,
+        LATIVIDO: 0,
+        INDEX,
+        NVMERVS_VEHICVLORVM_MOBILIVM: 0,
+        NVMERVS_CRYSTALLORVM: 0,
+        NEXVS: HashMap::new(),
+        VEHICVLA: Vec::new(),
+        CRYSTALLA: Vec::new(),
+        CRYSTALLA_OCCVLTA: Vec::new(),
+        SARCINA: SARCINA_DEFALTA,
+        IMPERIA: ControlScheme::default(),
+        OBIECTVM: OBIECTVM::default(),
+        PILA: BatteryRulesOverride::default(),
+        PILAE_VEHICVLORVM: HashMap::new(),
+        PAR: None,
+        SOLVTIO: None,
+    };
+    let mut NEXVS_MAPPAE: HashMap<char, (i8, i8)> = HashMap::new();
+    let mut INGENIA: Vec<(i8, i8)> = Vec::new();
+
+    loop {
+        let LINEA = LINEAE.next();
+        if LINEA.is_none() {
+            break;
+        }
+
+        let SERIES_CHARACTERVM = LINEA.unwrap();
+        if let Some(DIRECTIVA) = SERIES_CHARACTERVM.trim().strip_prefix('@') {
+            LEGE_DIRECTIVAM(DIRECTIVA, &mut GRADVS)?;
+            continue;
+        }
+
+        let mut X = 0;
+        for ITERATOR in SERIES_CHARACTERVM.chars().into_iter() {
+            match ITERATOR {
+                'S' => {
+                    GRADVS.TEGLVAE.insert(
+                        (X, -GRADVS.ALTIVIDO),
+                        TEGVLA {
+                            TYPVS: TEGVLA_TYPVS::INITIVM,
+                            VMBRA: false,
+                        },
+                    );
+                    GRADVS.NVMERVS_VEHICVLORVM_MOBILIVM += 1;
+                    GRADVS.VEHICVLA.push((X, -GRADVS.ALTIVIDO));
+                }
+                'E' => {
+                    GRADVS.TEGLVAE.insert(
+                        (X, -GRADVS.ALTIVIDO),
+                        TEGVLA {
+                            TYPVS: TEGVLA_TYPVS::FINIS,
+                            VMBRA: false,
+                        },
+                    );
+                    GRADVS.NVMERVS_CRYSTALLORVM += 1;
+                    GRADVS.CRYSTALLA.push((X, -GRADVS.ALTIVIDO));
+                }
+                'H' => {
+                    // mineral hidden until a helicopter scouts it
+                    GRADVS.TEGLVAE.insert(
+                        (X, -GRADVS.ALTIVIDO),
+                        TEGVLA {
+                            TYPVS: TEGVLA_TYPVS::FINIS,
+                            VMBRA: false,
+                        },
+                    );
+                    GRADVS.NVMERVS_CRYSTALLORVM += 1;
+                    GRADVS.CRYSTALLA_OCCVLTA.push(GRADVS.CRYSTALLA.len());
+                    GRADVS.CRYSTALLA.push((X, -GRADVS.ALTIVIDO));
+                }
+                'P' => {
+                    GRADVS.TEGLVAE.insert(
+                        (X, -GRADVS.ALTIVIDO),
+                        TEGVLA {
+                            TYPVS: TEGVLA_TYPVS::SEMITA,
+                            VMBRA: false,
+                        },
+                    );
+                }
+                '1'..'9' => {
+                    // tiles sharing a digit are wired one after the other, in reading order
+                    let FINIS = (X, -GRADVS.ALTIVIDO);
+                    if let Some(INITIVM) = NEXVS_MAPPAE.insert(ITERATOR, FINIS) {
+                        GRADVS.NEXVS.entry(INITIVM).or_default().push(FINIS);
+                        GRADVS.NEXVS.entry(FINIS).or_default().push(INITIVM);
                     }
-                    _ => {}
+
+                    GRADVS.TEGLVAE.insert(
+                        (X, -GRADVS.ALTIVIDO),
+                        TEGVLA {
+                            TYPVS: TEGVLA_TYPVS::SEMITA,
+                            VMBRA: false,
+                        },
+                    );
+                }
+                'D' => {
+                    GRADVS.TEGLVAE.insert(
+                        (X, -GRADVS.ALTIVIDO),
+                        TEGVLA {
+                            TYPVS: TEGVLA_TYPVS::SATVRNALIA,
+                            VMBRA: false,
+                        },
+                    );
+                }
+                'I' => {
+                    GRADVS.TEGLVAE.insert(
+                        (X, -GRADVS.ALTIVIDO),
+                        TEGVLA {
+                            TYPVS: TEGVLA_TYPVS::INGENII,
+                            VMBRA: false,
+                        },
+                    );
+                    GRADVS.NVMERVS_VEHICVLORVM_MOBILIVM += 1;
+                    INGENIA.push((X, -GRADVS.ALTIVIDO));
                 }
-                X += 1;
-                GRADVS.LATIVIDO = max(X, GRADVS.LATIVIDO);
+                'C' => {
+                    GRADVS.TEGLVAE.insert(
+                        (X, -GRADVS.ALTIVIDO),
+                        TEGVLA {
+                            TYPVS: TEGVLA_TYPVS::CRATERA,
+                            VMBRA: false,
+                        },
+                    );
+                }
+                '\n' => {
+                    X -= 1;
+                }
+                _ => {}
             }
+            X += 1;
+            GRADVS.LATIVIDO = max(X, GRADVS.LATIVIDO);
+        }
+
+        GRADVS.ALTIVIDO += 1;
+    }
+
+    // directives can come before the map, so their indices are only checked once it's read
+    PROBA_INDICES_DIRECTIVARVM(&GRADVS)?;
+
+    // helicopters come after the rovers so that adding one doesn't renumber the rovers
+    GRADVS.VEHICVLA.append(&mut INGENIA);
 
-            GRADVS.ALTIVIDO += 1;
+    let mut GRADVS_MODIFICATVS = GRADVM {
+        TEGLVAE: HashMap::new(),
+        MAPPAE_VREMBRAE: GRADVS.MAPPAE_VREMBRAE,
+        LATIVIDO: GRADVS.LATIVIDO,
+        ALTIVIDO: GRADVS.ALTIVIDO,
+        INDEX: GRADVS.INDEX,
+        NVMERVS_VEHICVLORVM_MOBILIVM: GRADVS.NVMERVS_VEHICVLORVM_MOBILIVM,
+        NVMERVS_CRYSTALLORVM: GRADVS.NVMERVS_CRYSTALLORVM,
+        NEXVS: HashMap::new(),
+        VEHICVLA: GRADVS
+            .VEHICVLA
+            .iter()
+            .map(|(X, Y)| (*X, *Y + GRADVS.ALTIVIDO))
+            .collect(),
+        CRYSTALLA: GRADVS
+            .CRYSTALLA
+            .iter()
+            .map(|(X, Y)| (*X, *Y + GRADVS.ALTIVIDO))
+            .collect(),
+        CRYSTALLA_OCCVLTA: GRADVS.CRYSTALLA_OCCVLTA,
+        SARCINA: GRADVS.SARCINA,
+        IMPERIA: GRADVS.IMPERIA,
+        OBIECTVM: GRADVS.OBIECTVM,
+        PILA: GRADVS.PILA,
+        PILAE_VEHICVLORVM: GRADVS.PILAE_VEHICVLORVM,
+        PAR: GRADVS.PAR,
+        // units left out of the solution have no commands
+        SOLVTIO: GRADVS.SOLVTIO.map(|mut SOLVTIO| {
+            SOLVTIO.resize(GRADVS.NVMERVS_VEHICVLORVM_MOBILIVM as usize, Vec::new());
+            SOLVTIO
+        }),
+    };
+
+    for ITERATOR in GRADVS.TEGLVAE.iter() {
+        let mut COORDINATAE = ITERATOR.0.clone();
+        COORDINATAE.1 += GRADVS.ALTIVIDO;
+        GRADVS_MODIFICATVS
+            .TEGLVAE
+            .insert(COORDINATAE, ITERATOR.1.clone());
+    }
+
+    for ITERATOR in GRADVS.NEXVS.iter() {
+        let mut COORDINATAE = ITERATOR.0.clone();
+        COORDINATAE.1 += GRADVS.ALTIVIDO;
+        let DESTINATIONES = ITERATOR
+            .1
+            .iter()
+            .map(|(X, Y)| (*X, *Y + GRADVS.ALTIVIDO))
+            .collect();
+        GRADVS_MODIFICATVS.NEXVS.insert(COORDINATAE, DESTINATIONES);
+    }
+
+    // a bare @ORDO means the minerals have to be collected in reading order
+    if GRADVS_MODIFICATVS
+        .OBIECTVM
+        .ORDO
+        .as_ref()
+        .is_some_and(|ORDO| ORDO.is_empty())
+    {
+        GRADVS_MODIFICATVS.OBIECTVM.ORDO = Some((0..GRADVS_MODIFICATVS.CRYSTALLA.len()).collect());
+    }
+
+    Ok(GRADVS_MODIFICATVS)
+}
+
+// @ORDO and @DOMINVS have to name minerals of the map, @DOMINVS one of its rovers (helicopters
+// can't carry minerals) and @SOLVTIO one of its units, called before the helicopters join
+// VEHICVLA
+fn PROBA_INDICES_DIRECTIVARVM(GRADVS: &GRADVM) -> Result<(), GRADVM_ORENATOR_ERROR> {
+    let CRYSTALLA = GRADVS.CRYSTALLA.len();
+    let ROVERES = GRADVS.VEHICVLA.len();
+
+    let ORDO = GRADVS.OBIECTVM.ORDO.iter().flatten();
+    if let Some(CRYSTALLVM) = ORDO.clone().find(|CRYSTALLVM| **CRYSTALLVM >= CRYSTALLA) {
+        return Err(GRADVM_ORENATOR_ERROR::INDEX_DIRECTIVAE(format!(
+            "ORDO mineral {CRYSTALLVM}"
+        )));
+    }
+
+    for (CRYSTALLVM, VEHICVLVM) in &GRADVS.OBIECTVM.DOMINI {
+        if *CRYSTALLVM >= CRYSTALLA {
+            return Err(GRADVM_ORENATOR_ERROR::INDEX_DIRECTIVAE(format!(
+                "DOMINVS mineral {CRYSTALLVM}"
+            )));
+        }
+        if *VEHICVLVM as usize >= ROVERES {
+            return Err(GRADVM_ORENATOR_ERROR::INDEX_DIRECTIVAE(format!(
+                "DOMINVS rover {VEHICVLVM}"
+            )));
+        }
+    }
+
+    // the reference solution only grows as far as the last unit it has commands for
+    let VEHICVLA = GRADVS.NVMERVS_VEHICVLORVM_MOBILIVM as usize;
+    if let Some(SOLVTIO) = &GRADVS.SOLVTIO
+        && SOLVTIO.len() > VEHICVLA
+    {
+        return Err(GRADVM_ORENATOR_ERROR::INDEX_DIRECTIVAE(format!(
+            "SOLVTIO unit {}",
+            SOLVTIO.len() - 1
+        )));
+    }
+
+    Ok(())
+}
+
+// reads a level file outside of the asset server, along with the shadow map next to it if any
+pub fn LEGE_FASCICVLVM_GRADVS(
+    VIA: &std::path::Path,
+    INDEX: u32,
+) -> Result<GRADVM, GRADVM_ORENATOR_ERROR> {
+    let TEXTVS = std::fs::read_to_string(VIA)?;
+    let mut GRADVS = LEGE_GRADVM(&TEXTVS, INDEX, Handle::default())?;
+
+    let VIA_MAPPAE = VIA.with_extension("png");
+    if VIA_MAPPAE.exists() {
+        let MAPPA = image::open(&VIA_MAPPAE)?.to_rgba8();
+        VMBRAS_APPLICA(&mut GRADVS, MAPPA.width(), MAPPA.height(), MAPPA.as_raw());
+    }
+
+    Ok(GRADVS)
+}
+
+// reads a directive line (without its leading '@') such as:
+//   @CAPACITAS 2          rovers carry at most 2 minerals at once
+//   @REDDE SATVRNALIA     minerals must be delivered to a dish (or INITIVM for the start tile)
+//   @ORDO 2 0 1           minerals must be collected in this order (bare @ORDO: reading order)
+//   @DOMINVS 0 1          mineral 0 must be collected by rover 1
+//   @PILA CAPACITAS=4 MOTVS=1 MORA=0 VERTE=0 SOL=1 VMBRA=0 INITIVM=2
+//                         battery capacity, move cost, wait cost, turn cost, sun recharge,
+//                         shadow drain and starting charge of every rover (any subset of them)
+//   @PILA_VEHICVLI 1 CAPACITAS=5
+//                         same as @PILA, for unit 1 only (@PILA doesn't apply to helicopters)
+//   @SARCINA 2            charge of the battery pack each helicopter carries
+//   @IMPERIA RELATIVA     commands are forward/reverse/turns instead of compass moves
+//                         (ABSOLVTA, the default, for compass moves)
+//   @PAR MANDATA=9 VICES=6 PILA=4
+//                         fewest commands of any solution, then turns and battery charge spent
+//                         by the fastest one, written by the level_par tool from what the
+//                         solver finds
+//   @SOLVTIO 1 UURW       commands of unit 1 in a reference solution, one letter each (see
+//                         LITTERA_MANDATI), bare for a unit without commands, also written by
+//                         the level_par tool and used for hints
+// minerals are numbered in reading order, starting at 0, units too but rovers come first
+fn LEGE_DIRECTIVAM(DIRECTIVA: &str, GRADVS: &mut GRADVM) -> Result<(), GRADVM_ORENATOR_ERROR> {
+    let ERRATVM = || GRADVM_ORENATOR_ERROR::DIRECTIVA(DIRECTIVA.to_string());
+    let mut VERBA = DIRECTIVA.split_whitespace();
+    let NVMERVS = |VERBVM: Option<&str>| -> Result<usize, GRADVM_ORENATOR_ERROR> {
+        VERBVM
+            .and_then(|VERBVM| VERBVM.parse().ok())
+            .ok_or_else(ERRATVM)
+    };
+    // counts and indices stored as u8 have to fit rather than wrap around
+    let OCTETVS = |VERBVM: Option<&str>| -> Result<u8, GRADVM_ORENATOR_ERROR> {
+        u8::try_from(NVMERVS(VERBVM)?).map_err(|_| ERRATVM())
+    };
+
+    match VERBA.next() {
+        Some("CAPACITAS") => {
+            // rovers that can't carry a single mineral could never finish the level
+            let CAPACITAS = OCTETVS(VERBA.next())?;
+            if CAPACITAS == 0 {
+                return Err(ERRATVM());
+            }
+            GRADVS.OBIECTVM.CAPACITAS = Some(CAPACITAS);
+        }
+        Some("REDDE") => {
+            GRADVS.OBIECTVM.DESTINATIO = match VERBA.next() {
+                Some("SATVRNALIA") => Some(DESTINATIO::SATVRNALIA),
+                Some("INITIVM") => Some(DESTINATIO::INITIVM),
+                _ => return Err(ERRATVM()),
+            };
+        }
+        Some("ORDO") => {
+            let mut ORDO = Vec::new();
+            for VERBVM in VERBA {
+                ORDO.push(NVMERVS(Some(VERBVM))?);
+            }
+            GRADVS.OBIECTVM.ORDO = Some(ORDO);
+        }
+        Some("DOMINVS") => {
+            let CRYSTALLVM = NVMERVS(VERBA.next())?;
+            let VEHICVLVM = OCTETVS(VERBA.next())?;
+            GRADVS.OBIECTVM.DOMINI.insert(CRYSTALLVM, VEHICVLVM);
+        }
+        Some("IMPERIA") => {
+            GRADVS.IMPERIA = match VERBA.next() {
+                Some("ABSOLVTA") => ControlScheme::Absolute,
+                Some("RELATIVA") => ControlScheme::Relative,
+                _ => return Err(ERRATVM()),
+            };
         }
-        let mut GRADVS_MODIFICATVS = GRADVM {
-            TEGLVAE: HashMap::new(),
-            MAPPAE_VREMBRAE: GRADVS.MAPPAE_VREMBRAE,
-            LATIVIDO: GRADVS.LATIVIDO,
-            ALTIVIDO: GRADVS.ALTIVIDO,
-            INDEX: GRADVS.INDEX,
-            NVMERVS_VEHICVLORVM_MOBILIVM: GRADVS.NVMERVS_VEHICVLORVM_MOBILIVM,
-            NVMERVS_CRYSTALLORVM: GRADVS.NVMERVS_CRYSTALLORVM,
-            NEXVS: HashMap::new(),
-        };
-
-        for ITERATOR in GRADVS.TEGLVAE.iter() {
-            let mut COORDINATAE = ITERATOR.0.clone();
-            COORDINATAE.1 += GRADVS.ALTIVIDO;
-            GRADVS_MODIFICATVS
-                .TEGLVAE
-                .insert(COORDINATAE, ITERATOR.1.clone());
+        Some("SARCINA") => {
+            GRADVS.SARCINA = OCTETVS(VERBA.next())?;
         }
+        Some("PILA") => {
+            LEGE_PILAM(VERBA, &mut GRADVS.PILA).ok_or_else(ERRATVM)?;
+        }
+        Some("PILA_VEHICVLI") => {
+            let VEHICVLVM = OCTETVS(VERBA.next())?;
+            let PILA = GRADVS.PILAE_VEHICVLORVM.entry(VEHICVLVM).or_default();
+            LEGE_PILAM(VERBA, PILA).ok_or_else(ERRATVM)?;
+        }
+        Some("PAR") => {
+            GRADVS.PAR = Some(LEGE_PAR(VERBA).ok_or_else(ERRATVM)?);
+        }
+        Some("SOLVTIO") => {
+            let VEHICVLVM = OCTETVS(VERBA.next())? as usize;
+            let MANDATA = VERBA
+                .next()
+                .unwrap_or("")
+                .chars()
+                .map(MANDATVM_LITTERAE)
+                .collect::<Option<Vec<ActionType>>>()
+                .ok_or_else(ERRATVM)?;
 
-        for ITERATOR in GRADVS.NEXVS.iter() {
-            let mut COORDINATAE = ITERATOR.0.clone();
-            let mut DESTINATIO = ITERATOR.1.clone();
-            COORDINATAE.1 += GRADVS.ALTIVIDO;
-            DESTINATIO.1 += GRADVS.ALTIVIDO;
-            GRADVS_MODIFICATVS.NEXVS.insert(COORDINATAE, DESTINATIO);
+            let SOLVTIO = GRADVS.SOLVTIO.get_or_insert_default();
+            if SOLVTIO.len() <= VEHICVLVM {
+                SOLVTIO.resize(VEHICVLVM + 1, Vec::new());
+            }
+            SOLVTIO[VEHICVLVM] = MANDATA;
         }
+        _ => return Err(ERRATVM()),
+    }
+
+    Ok(())
+}
 
-        Ok(GRADVS_MODIFICATVS)
+// reads the KEY=VALUE of a par, every key is required
+fn LEGE_PAR<'a>(VERBA: impl Iterator<Item = &'a str>) -> Option<PAR> {
+    let (mut MANDATA, mut VICES, mut PILA) = (None, None, None);
+    for VERBVM in VERBA {
+        let (CLAVIS, VALOR) = VERBVM.split_once('=')?;
+        let VALOR = Some(VALOR.parse::<u32>().ok()?);
+        match CLAVIS {
+            "MANDATA" => MANDATA = VALOR,
+            "VICES" => VICES = VALOR,
+            "PILA" => PILA = VALOR,
+            _ => return None,
+        }
     }
-    fn extensions(&self) -> &[&str] {
-        &["lvl"]
+
+    Some(PAR {
+        MANDATA: MANDATA?,
+        VICES: VICES?,
+        PILA: PILA?,
+    })
+}
+
+// letter of a command in a @SOLVTIO directive
+pub fn LITTERA_MANDATI(MANDATVM: ActionType) -> char {
+    match MANDATVM {
+        ActionType::MoveUp => 'U',
+        ActionType::MoveDown => 'D',
+        ActionType::MoveLeft => 'L',
+        ActionType::MoveRight => 'R',
+        ActionType::Forward => 'F',
+        ActionType::Reverse => 'B',
+        ActionType::TurnLeft => '<',
+        ActionType::TurnRight => '>',
+        ActionType::Wait => 'W',
+        ActionType::Scout => 'V',
+        ActionType::DropPack => 'P',
     }
 }
 
+// command of a letter in a @SOLVTIO directive
+pub fn MANDATVM_LITTERAE(LITTERA: char) -> Option<ActionType> {
+    match LITTERA {
+        'U' => Some(ActionType::MoveUp),
+        'D' => Some(ActionType::MoveDown),
+        'L' => Some(ActionType::MoveLeft),
+        'R' => Some(ActionType::MoveRight),
+        'F' => Some(ActionType::Forward),
+        'B' => Some(ActionType::Reverse),
+        '<' => Some(ActionType::TurnLeft),
+        '>' => Some(ActionType::TurnRight),
+        'W' => Some(ActionType::Wait),
+        'V' => Some(ActionType::Scout),
+        'P' => Some(ActionType::DropPack),
+        _ => None,
+    }
+}
+
+// reads KEY=VALUE battery rules
+fn LEGE_PILAM<'a>(
+    VERBA: impl Iterator<Item = &'a str>,
+    PILA: &mut BatteryRulesOverride,
+) -> Option<()> {
+    for VERBVM in VERBA {
+        let (CLAVIS, VALOR) = VERBVM.split_once('=')?;
+        let VALOR = Some(VALOR.parse::<u8>().ok()?);
+        match CLAVIS {
+            // an empty battery could never move the unit
+            "CAPACITAS" if VALOR == Some(0) => return None,
+            "CAPACITAS" => PILA.capacity = VALOR,
+            "MOTVS" => PILA.move_cost = VALOR,
+            "MORA" => PILA.wait_cost = VALOR,
+            "VERTE" => PILA.turn_cost = VALOR,
+            "SOL" => PILA.sun_recharge = VALOR,
+            "VMBRA" => PILA.shadow_drain = VALOR,
+            "INITIVM" => PILA.starting_charge = VALOR,
+            _ => return None,
+        }
+    }
+
+    Some(())
+}
+
 fn GRADVS_ONERIS(mut commands: Commands, asset_server: Res<AssetServer>) {
     let mut GRADVS = Vec::new();
 
@@ -281,19 +685,20 @@ fn UMBRAE_COLLOCATOR(
             }
 
             let DATA = DATA.as_ref().unwrap();
+            VMBRAS_APPLICA(GRADVM, DIMENSIO.width, DIMENSIO.height, DATA);
+        }
+    }
+}
 
-            for TEGVLA in GRADVM.TEGLVAE.iter_mut() {
-                let PIXEL_X =
-                    (TEGVLA.0.0 as f32 + 0.5) / GRADVM.LATIVIDO as f32 * DIMENSIO.width as f32;
-                let PIXEL_Y = (1.0 - (TEGVLA.0.1 as f32 - 0.5) / GRADVM.ALTIVIDO as f32)
-                    * DIMENSIO.height as f32;
-                let INDEX = f32::round(PIXEL_Y) as usize * DIMENSIO.width as usize
-                    + f32::round(PIXEL_X) as usize;
-                let COLOR = DATA.get(INDEX * 4 + 3);
-                if let Some(ALPHA) = COLOR {
-                    TEGVLA.1.VMBRA = *ALPHA > (255.0 * LEVEL_SHADOW_ALPHA_MASK) as u8;
-                }
-            }
+// marks the tiles in the shadow from the RGBA pixels of a shadow map
+pub fn VMBRAS_APPLICA(GRADVM: &mut GRADVM, LATITVDO: u32, ALTITVDO: u32, DATA: &[u8]) {
+    for TEGVLA in GRADVM.TEGLVAE.iter_mut() {
+        let PIXEL_X = (TEGVLA.0.0 as f32 + 0.5) / GRADVM.LATIVIDO as f32 * LATITVDO as f32;
+        let PIXEL_Y = (1.0 - (TEGVLA.0.1 as f32 - 0.5) / GRADVM.ALTIVIDO as f32) * ALTITVDO as f32;
+        let INDEX = f32::round(PIXEL_Y) as usize * LATITVDO as usize + f32::round(PIXEL_X) as usize;
+        let COLOR = DATA.get(INDEX * 4 + 3);
+        if let Some(ALPHA) = COLOR {
+            TEGVLA.1.VMBRA = *ALPHA > (255.0 * LEVEL_SHADOW_ALPHA_MASK) as u8;
         }
     }
 }
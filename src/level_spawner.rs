@@ -1,14 +1,19 @@
-use crate::game_control::actions::ActionList;
+use crate::audio::Music;
+use crate::camera_controller::{OrbitCamera, OrbitView};
+use crate::gameplay::{GameState, LevelSpawnRequestEvent, LEVEL_SHADOW_ALPHA_MASK, TILE_SIZE};
 use crate::hentai_anime::*;
-use crate::level::{GRADVM, GRADVM_ONVSTVS, TEGVLA_TYPVS};
+use crate::level::{GRADVM, TEGVLA_TYPVS};
 use crate::mesh_loader::{load_gltf, GLTFLoadConfig, MeshLoader};
+use crate::objective::ObjectiveProgress;
 use crate::particle::dust::DustSpawner;
 use crate::particle::particle::Particle;
 use crate::puzzle_evaluation::PuzzleResponseEvent;
-use crate::rover::{RoverCollectable, RoverEntity, RoverPlugin, RoverStates};
-use crate::title_screen::GameState;
-use crate::ui::control_ui::{on_rover_click, RoverColors};
-use crate::ui::win_screen::NextLevelRequestEvent;
+use crate::rover::{RoverCollectable, RoverEntity};
+use crate::save::SaveData;
+use crate::settings::GraphicsQuality;
+use crate::ui::control_ui::{on_rover_click, RoverColors, UIRoverColors};
+use crate::ui::touch::is_portrait;
+use crate::unit::UnitKind;
 use bevy::app::Startup;
 use bevy::asset::{Handle, RenderAssetUsages};
 use bevy::audio::{AudioPlayer, PlaybackMode, PlaybackSettings, Volume};
@@ -28,16 +33,16 @@ use bevy::render::camera::TemporalJitter;
 use bevy::render::mesh::{Indices, PrimitiveTopology};
 use bevy::render::render_resource::{TextureViewDescriptor, TextureViewDimension};
 use bevy::time::{Timer, TimerMode};
+use bevy::window::PrimaryWindow;
 use bevy::{
     app::{App, Plugin, Update},
     asset::AssetServer,
     ecs::{
         entity::Entity,
-        event::{Event, EventReader, EventWriter},
+        event::{Event, EventReader},
         query::With,
         system::{Commands, Query},
     },
-    log,
 };
 use bevy_rapier3d::plugin::{NoUserData, RapierPhysicsPlugin};
 use bevy_rapier3d::prelude::{DebugRenderContext, RapierDebugRenderPlugin};
@@ -57,17 +62,10 @@ pub struct Cubemap {
 #[derive(Component)]
 pub struct LevelElement;
 
-pub const TILE_SIZE: f32 = 2.0;
-pub const LEVEL_SHADOW_ALPHA_MASK: f32 = 0.5;
 pub const ROCK_PADDING: i32 = 5;
 
 pub struct LevelSpawnerPlugin;
 
-#[derive(Event)]
-pub struct LevelSpawnRequestEvent {
-    level: Handle<GRADVM>,
-}
-
 #[derive(Event)]
 pub struct AfterLevelSpawnEvent;
 
@@ -75,36 +73,35 @@ pub struct AfterLevelSpawnEvent;
 #[derive(Component)]
 pub struct TileEntity;
 
-#[derive(Resource)]
-pub struct ActiveLevel(pub Option<Handle<GRADVM>>);
+/// Mineral (or its light) that stays invisible until a helicopter scouts it.
+#[derive(Component)]
+pub struct HiddenMineral(pub usize);
 
 impl Plugin for LevelSpawnerPlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<LevelSpawnRequestEvent>();
         app.add_event::<AfterLevelSpawnEvent>();
         app.add_systems(
             Update,
             load_level.run_if(not(in_state(GameState::TitleScreen))),
         );
-        app.add_systems(OnExit(GameState::TitleScreen), spawn_initial_level);
         app.add_systems(
             OnEnter(GameState::TitleScreen),
             cleanup_level_on_title_screen,
         );
         app.add_systems(Startup, setup_scene);
-        app.add_systems(Update, handle_puzzle_solved_event);
-        app.add_systems(Update, handle_next_level_request);
-        app.add_systems(Update, (handle_puzzle_failed_event, update_reset_timer));
+        app.add_systems(
+            Update,
+            apply_graphics_quality.run_if(resource_changed::<SaveData>),
+        );
+        app.add_systems(Update, play_puzzle_response_sounds);
+        app.add_systems(Update, reveal_scouted_minerals);
 
         app.add_systems(Update, asset_loaded);
-        app.insert_resource(ActiveLevel(None));
         app.add_plugins((
             RapierPhysicsPlugin::<NoUserData>::default(),
             RapierDebugRenderPlugin::default().disabled(),
         ));
 
-        app.add_plugins(RoverPlugin);
-
         #[cfg(not(target_arch = "wasm32"))]
         app.add_plugins(TemporalAntiAliasPlugin);
 
@@ -116,6 +113,7 @@ impl Plugin for LevelSpawnerPlugin {
 
 fn setup_scene(mut commands: Commands, asset_server: ResMut<AssetServer>) {
     commands.spawn((
+        Music,
         AudioPlayer::new(asset_server.load("Space Program.ogg")),
         PlaybackSettings {
             mode: PlaybackMode::Loop,
@@ -186,13 +184,51 @@ fn setup_scene(mut commands: Commands, asset_server: ResMut<AssetServer>) {
     camera_bundle.insert((TemporalAntiAliasing::default(), TemporalJitter::default()));
 }
 
-fn spawn_initial_level(
-    mut events: EventWriter<LevelSpawnRequestEvent>,
-    levels: Res<GRADVM_ONVSTVS>,
+/// Puts the effects of the graphics preset of the settings on the camera.
+fn apply_graphics_quality(
+    mut commands: Commands,
+    save_data: Res<SaveData>,
+    mut applied: Local<Option<GraphicsQuality>>,
+    cameras: Query<Entity, With<Camera3d>>,
+    mut shadow_map: ResMut<DirectionalLightShadowMap>,
 ) {
-    events.write(LevelSpawnRequestEvent {
-        level: levels.GRADVS[0].clone(),
-    });
+    let quality = save_data.settings.graphics;
+    if *applied == Some(quality) {
+        return;
+    }
+    let Ok(camera) = cameras.single() else {
+        return;
+    };
+    *applied = Some(quality);
+
+    shadow_map.size = quality.shadow_map_size();
+
+    let mut camera = commands.entity(camera);
+    let ambient_occlusion = match quality {
+        GraphicsQuality::Low | GraphicsQuality::Medium => None,
+        GraphicsQuality::High => Some(ScreenSpaceAmbientOcclusionQualityLevel::Medium),
+        GraphicsQuality::Ultra => Some(ScreenSpaceAmbientOcclusionQualityLevel::Ultra),
+    };
+    match ambient_occlusion {
+        Some(quality_level) => camera.insert(ScreenSpaceAmbientOcclusion {
+            quality_level,
+            ..default()
+        }),
+        None => camera.remove::<ScreenSpaceAmbientOcclusion>(),
+    };
+
+    if quality.bloom() {
+        camera.insert(Bloom::default());
+    } else {
+        camera.remove::<Bloom>();
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    if quality.temporal_anti_aliasing() {
+        camera.insert((TemporalAntiAliasing::default(), TemporalJitter::default()));
+    } else {
+        camera.remove::<(TemporalAntiAliasing, TemporalJitter)>();
+    }
 }
 
 fn load_level(
@@ -202,15 +238,15 @@ fn load_level(
     mut materials: ResMut<Assets<StandardMaterial>>,
     asset_server: Res<AssetServer>,
     mut mesh_loader: ResMut<MeshLoader>,
-    mut active_level: ResMut<ActiveLevel>,
-    mut action_list: ResMut<ActionList>,
-    mut rover_colors: ResMut<RoverColors>,
+    rover_colors: Res<RoverColors>,
+    ui_rover_colors: Res<UIRoverColors>,
     levels: Res<Assets<GRADVM>>,
     level_elements: Query<Entity, With<LevelElement>>,
-    mut camera_transform: Query<(&Camera, &mut Transform, &GlobalTransform), With<Camera3d>>,
+    cameras: Query<&Camera, With<Camera3d>>,
+    mut orbit_camera: ResMut<OrbitCamera>,
+    windows: Query<&Window, With<PrimaryWindow>>,
     particles: Query<Entity, (With<Particle>, Without<LevelElement>)>,
     mut graphs: ResMut<Assets<AnimationGraph>>,
-    mut next_state: ResMut<NextState<GameState>>,
 ) {
     if events.is_empty() {
         return;
@@ -235,11 +271,17 @@ fn load_level(
     let level_width = level.LATIVIDO as f32 * TILE_SIZE;
     let level_height = level.ALTIVIDO as f32 * TILE_SIZE;
 
-    for (cam, mut trans, g_trans) in camera_transform.iter_mut() {
-        trans.translation = Vec3::new(0.0, 8.0, 5.0);
+    // upright, the control panel takes the bottom of the screen instead of its left side
+    let portrait = windows.single().is_ok_and(is_portrait);
+    let (max_ndc_x, max_ndc_y) = if portrait { (0.9, 0.5) } else { (0.6, 1.0) };
+
+    for cam in cameras.iter() {
+        // the view framing the level is worked out on a scratch transform, the orbit camera
+        // eases the actual camera there
+        let mut trans = Transform::from_xyz(0.0, 8.0, 5.0);
         trans.look_at(Vec3::new(0.0, 0.0, 0.0), Vec3::Y);
 
-        let mut g_transform = *g_trans;
+        let mut g_transform = GlobalTransform::from(trans);
 
         loop {
             let mut any_out = false;
@@ -273,10 +315,10 @@ fn load_level(
                 )
                 .unwrap_or(out);
 
-            any_out |= abs(ndc1.x) > 0.6 || abs(ndc1.y) > 1.0;
-            any_out |= abs(ndc2.x) > 0.6 || abs(ndc2.y) > 1.0;
-            any_out |= abs(ndc3.x) > 0.6 || abs(ndc3.y) > 1.0;
-            any_out |= abs(ndc4.x) > 0.6 || abs(ndc4.y) > 1.0;
+            any_out |= abs(ndc1.x) > max_ndc_x || abs(ndc1.y) > max_ndc_y;
+            any_out |= abs(ndc2.x) > max_ndc_x || abs(ndc2.y) > max_ndc_y;
+            any_out |= abs(ndc3.x) > max_ndc_x || abs(ndc3.y) > max_ndc_y;
+            any_out |= abs(ndc4.x) > max_ndc_x || abs(ndc4.y) > max_ndc_y;
 
             if !any_out {
                 break;
@@ -285,9 +327,22 @@ fn load_level(
             trans.translation.y += 2.0;
             trans.look_at(Vec3::new(0.0, 0.0, 0.0), Vec3::Y);
             g_transform = GlobalTransform::default();
-            g_transform = g_transform.mul_transform(*trans);
+            g_transform = g_transform.mul_transform(trans);
         }
-        trans.translation.x -= trans.translation.y / 4.0;
+
+        // looking at the board from the right of the sidebar, or from above the bottom panel
+        let height = trans.translation.y;
+        let focus = if portrait {
+            Vec3::new(0.0, 0.0, height / 4.0)
+        } else {
+            Vec3::new(-height / 4.0, 0.0, 0.0)
+        };
+        orbit_camera.frame(OrbitView {
+            focus,
+            yaw: 0.0,
+            pitch: height.atan2(trans.translation.z),
+            distance: Vec2::new(height, trans.translation.z).length(),
+        });
     }
 
     let mars_texture = asset_server.load("mars.png");
@@ -303,11 +358,8 @@ fn load_level(
         })),
         Transform::from_xyz(0.0, 0.0, 0.0),
     ));
-    let mut num_rovers = 0;
     // Spawn cylinders at each tile position
     for ((x, z), tile) in level.TEGLVAE.iter() {
-        let logical_x = *x as i32;
-        let logical_z = *z as i32;
         let effective_x = (*x as f32 * TILE_SIZE - level_width / 2.0) + TILE_SIZE / 2.0;
         // mirror along the z to align correctly with how it looks in the level
         let effective_z = (-*z as f32 * TILE_SIZE + level_height / 2.0) + TILE_SIZE / 2.0;
@@ -333,55 +385,51 @@ fn load_level(
 
         // Store rover spawn position for the start tile
 
-        if matches!(tile.TYPVS, TEGVLA_TYPVS::INITIVM) {
-            num_rovers += 1;
-            let rover_index = num_rovers - 1;
-            let rover_colors_cloned = rover_colors.0.clone();
+        if matches!(tile.TYPVS, TEGVLA_TYPVS::INITIVM | TEGVLA_TYPVS::INGENII) {
+            // units are numbered in reading order so that level directives can refer to them
+            let rover_index = level
+                .VEHICVLA
+                .iter()
+                .position(|start| *start == tile_pos)
+                .unwrap_or(0) as u8;
+            let rover = RoverEntity::at_start(level, rover_index, Color::srgb(0.5, 0.2, 0.8));
+            let kind = rover.kind;
+            let (model, transform) = match kind {
+                UnitKind::Rover => (
+                    "rover.glb",
+                    Transform::from_xyz(effective_x, 0.09, effective_z)
+                        .with_scale(Vec3::splat(0.15 * TILE_SIZE)),
+                ),
+                UnitKind::Helicopter => (
+                    "ingenuity.glb",
+                    Transform::from_xyz(effective_x, 1.0 * TILE_SIZE, effective_z)
+                        .with_scale(Vec3::splat(0.2 * TILE_SIZE)),
+                ),
+            };
+            let rover_color = rover_colors.color(rover_index as usize);
             load_gltf(
-                String::from("rover.glb"),
+                String::from(model),
                 GLTFLoadConfig {
                     entity_initializer: Some(Box::new(move |commands: &mut EntityCommands| {
                         commands
                             .insert(
                                 // should spawn at the tile position
-                                Transform::from_xyz(effective_x, 0.09, effective_z)
-                                    .with_scale(Vec3::splat(0.15 * TILE_SIZE))
-                                    .with_rotation(Quat::from_rotation_y(-PI / 2.0)),
+                                transform.with_rotation(Quat::from_rotation_y(-PI / 2.0)),
                             )
-                            .insert(RoverEntity {
-                                is_acting: false,
-                                is_turn_done: false,
-                                base_color: Color::srgb(0.5, 0.2, 0.8),
-                                gltf_handle: Default::default(),
-                                logical_position: I8Vec2::new(
-                                    logical_x.try_into().unwrap(),
-                                    logical_z.try_into().unwrap(),
-                                ),
-                                battery_level: 3,
-                                identifier: rover_index,
-                                heading: -PI / 2.0,
-                                rover_state: RoverStates::Standby,
-                                collided: false,
-                                spawned_fail_particle: false,
-                                spawned_wait_particle: false,
-                                is_done: false,
-                            })
+                            .insert(rover.clone())
                             .insert(LevelElement)
-                            .insert(DustSpawner {
-                                timer: Timer::from_seconds(0.4, TimerMode::Repeating),
-                            })
                             .insert(Pickable::default())
                             .observe(play_all_animations_when_ready)
                             .observe(on_rover_click);
+
+                        // helicopters don't touch the ground
+                        if kind == UnitKind::Rover {
+                            commands.insert(DustSpawner {
+                                timer: Timer::from_seconds(0.4, TimerMode::Repeating),
+                            });
+                        }
                     })),
-                    scene_color_override: Some(
-                        rover_colors_cloned
-                            .get(rover_index as usize)
-                            .cloned()
-                            .or_else(|| rover_colors_cloned.get(0).cloned())
-                            // pink
-                            .unwrap_or(Color::srgb(1.0, 0.0, 1.0)),
-                    ),
+                    scene_color_override: Some(rover_color),
                     ..Default::default()
                 },
                 &asset_server,
@@ -390,6 +438,19 @@ fn load_level(
         }
 
         if matches!(tile.TYPVS, TEGVLA_TYPVS::FINIS) {
+            let mineral_index = level
+                .CRYSTALLA
+                .iter()
+                .position(|mineral| *mineral == tile_pos)
+                .unwrap_or(0);
+            // minerals reserved for a specific rover take on its colour
+            let owner_color = level
+                .OBIECTVM
+                .DOMINI
+                .get(&mineral_index)
+                .map(|owner| ui_rover_colors.color(*owner as usize));
+            let is_hidden = level.CRYSTALLA_OCCVLTA.contains(&mineral_index);
+
             load_gltf(
                 String::from("mineral.glb"),
                 GLTFLoadConfig {
@@ -404,26 +465,39 @@ fn load_level(
                                     )),
                             )
                             .insert(LevelElement)
-                            .insert(RoverCollectable);
+                            .insert(RoverCollectable {
+                                index: mineral_index,
+                                logical_position: I8Vec2::new(tile_pos.0, tile_pos.1),
+                            });
+
+                        if is_hidden {
+                            commands
+                                .insert(Visibility::Hidden)
+                                .insert(HiddenMineral(mineral_index));
+                        }
                     })),
+                    scene_color_override: owner_color,
                     ..Default::default()
                 },
                 &asset_server,
                 &mut mesh_loader,
             );
 
-            commands.spawn((
+            let mut light = commands.spawn((
                 Transform::from_xyz(effective_x, 0.0, effective_z)
                     .with_scale(Vec3::splat(0.05 * TILE_SIZE))
                     .with_rotation(Quat::from_rotation_y(random::<f32>() * PI * 2.0)),
                 LevelElement,
                 PointLight {
                     intensity: 500_000.0,
-                    color: BLUE.into(),
+                    color: owner_color.unwrap_or(BLUE.into()),
                     shadows_enabled: false,
                     ..default()
                 },
             ));
+            if is_hidden {
+                light.insert((Visibility::Hidden, HiddenMineral(mineral_index)));
+            }
         }
 
         if matches!(tile.TYPVS, TEGVLA_TYPVS::SATVRNALIA) {
@@ -447,28 +521,6 @@ fn load_level(
             );
         }
 
-        if matches!(tile.TYPVS, TEGVLA_TYPVS::INGENII) {
-            load_gltf(
-                String::from("ingenuity.glb"),
-                GLTFLoadConfig {
-                    entity_initializer: Some(Box::new(move |commands: &mut EntityCommands| {
-                        commands
-                            .insert(
-                                // should spawn at the tile position
-                                Transform::from_xyz(effective_x, 1.0 * TILE_SIZE, effective_z)
-                                    .with_scale(Vec3::splat(0.2 * TILE_SIZE))
-                                    .with_rotation(Quat::from_rotation_y(-PI / 2.0)),
-                            )
-                            .insert(LevelElement)
-                            .observe(play_all_animations_when_ready);
-                    })),
-                    ..Default::default()
-                },
-                &asset_server,
-                &mut mesh_loader,
-            );
-        }
-
         if matches!(tile.TYPVS, TEGVLA_TYPVS::CRATERA) {
             load_gltf(
                 String::from("crater.glb"),
@@ -490,20 +542,22 @@ fn load_level(
         }
     }
 
-    for (start, end) in level.NEXVS.iter() {
-        if start > end {
-            continue;
-        }
+    for (start, ends) in level.NEXVS.iter() {
+        for end in ends {
+            if start > end {
+                continue;
+            }
 
-        let start = Vec2::new(
-            (start.0 as f32 * TILE_SIZE - level_width / 2.0) + TILE_SIZE / 2.0,
-            (-start.1 as f32 * TILE_SIZE + level_height / 2.0) + TILE_SIZE / 2.0,
-        );
-        let end = Vec2::new(
-            (end.0 as f32 * TILE_SIZE - level_width / 2.0) + TILE_SIZE / 2.0,
-            (-end.1 as f32 * TILE_SIZE + level_height / 2.0) + TILE_SIZE / 2.0,
-        );
-        spawn_wire(&mut commands, &mut meshes, &mut materials, start, end);
+            let start = Vec2::new(
+                (start.0 as f32 * TILE_SIZE - level_width / 2.0) + TILE_SIZE / 2.0,
+                (-start.1 as f32 * TILE_SIZE + level_height / 2.0) + TILE_SIZE / 2.0,
+            );
+            let end = Vec2::new(
+                (end.0 as f32 * TILE_SIZE - level_width / 2.0) + TILE_SIZE / 2.0,
+                (-end.1 as f32 * TILE_SIZE + level_height / 2.0) + TILE_SIZE / 2.0,
+            );
+            spawn_wire(&mut commands, &mut meshes, &mut materials, start, end);
+        }
     }
 
     let rock_padding_x = max(ROCK_PADDING, (level.LATIVIDO / 4) as i32);
@@ -556,12 +610,6 @@ fn load_level(
         level.MAPPAE_VREMBRAE.clone(),
     );
 
-    active_level.0 = Some(event.level.clone());
-
-    let action_event = action_list.clone();
-    commands.send_event(action_event);
-
-    next_state.set(GameState::Programming);
     commands.send_event(AfterLevelSpawnEvent);
 }
 
@@ -796,103 +844,21 @@ fn asset_loaded(
     }
 }
 
-fn handle_puzzle_solved_event(
+fn play_puzzle_response_sounds(
     mut commands: Commands,
     mut events: EventReader<PuzzleResponseEvent>,
-    mut action_list: ResMut<ActionList>,
     asset_server: Res<AssetServer>,
 ) {
     for event in events.read() {
-        if *event == PuzzleResponseEvent::Solved {
-            commands.spawn((
-                AudioPlayer::new(asset_server.load("sfx/win.ogg")),
-                PlaybackSettings::DESPAWN,
-            ));
-
-            for i in 0..action_list.actions.len() {
-                action_list.actions[i].clear();
-                action_list.actions.push(vec![]);
-            }
-        }
-    }
-}
-
-fn handle_next_level_request(
-    mut commands: Commands,
-    mut events: EventReader<NextLevelRequestEvent>,
-    levels: Res<Assets<GRADVM>>,
-    level_handles: Res<GRADVM_ONVSTVS>,
-    mut active_level: ResMut<ActiveLevel>,
-) {
-    for _ in events.read() {
-        let Some(active_level_handle) = &active_level.0 else {
-            log::error!("No active level.");
-            return;
+        let sound = match event {
+            PuzzleResponseEvent::Solved => "sfx/win.ogg",
+            PuzzleResponseEvent::Failed => "sfx/fail.ogg",
+            PuzzleResponseEvent::InProgress => continue,
         };
-
-        let Some(current_level) = levels.get(active_level_handle) else {
-            log::error!("No active level.");
-            return;
-        };
-
-        let Some(next_level_handle) = level_handles
-            .GRADVS
-            .get(current_level.INDEX as usize + 1)
-            .or(level_handles.GRADVS.get(0))
-        else {
-            log::error!("No next level.");
-            return;
-        };
-
-        active_level.0 = Some(next_level_handle.clone());
-
-        commands.spawn(ResetTimer {
-            timer: Timer::from_seconds(0.01, TimerMode::Once),
-        });
-    }
-}
-
-#[derive(Component)]
-pub struct ResetTimer {
-    timer: Timer,
-}
-
-fn handle_puzzle_failed_event(
-    mut commands: Commands,
-    mut events: EventReader<PuzzleResponseEvent>,
-    asset_server: Res<AssetServer>,
-) {
-    for event in events.read() {
-        if *event == PuzzleResponseEvent::Failed {
-            commands.spawn((
-                AudioPlayer::new(asset_server.load("sfx/fail.ogg")),
-                PlaybackSettings::DESPAWN,
-            ));
-            commands.spawn(ResetTimer {
-                timer: Timer::from_seconds(1.0, TimerMode::Once),
-            });
-            break;
-        }
-    }
-    events.clear();
-}
-
-fn update_reset_timer(
-    mut commands: Commands,
-    mut query: Query<(Entity, &mut ResetTimer)>,
-    mut level_spawn_request_writer: EventWriter<LevelSpawnRequestEvent>,
-    active_level: Res<ActiveLevel>,
-    time: Res<Time>,
-) {
-    for (entity, mut timer) in query.iter_mut() {
-        timer.timer.tick(time.delta());
-
-        if timer.timer.just_finished() {
-            commands.entity(entity).despawn();
-            level_spawn_request_writer.write(LevelSpawnRequestEvent {
-                level: active_level.0.clone().unwrap(),
-            });
-        }
+        commands.spawn((
+            AudioPlayer::new(asset_server.load(sound)),
+            PlaybackSettings::DESPAWN,
+        ));
     }
 }
 
@@ -908,3 +874,20 @@ fn cleanup_level_on_title_screen(
         commands.entity(particle).despawn();
     }
 }
+
+fn reveal_scouted_minerals(
+    mut commands: Commands,
+    objective_progress: Res<ObjectiveProgress>,
+    mut hidden_minerals: Query<(Entity, &HiddenMineral, &mut Visibility)>,
+) {
+    if !objective_progress.is_changed() {
+        return;
+    }
+
+    for (entity, hidden_mineral, mut visibility) in hidden_minerals.iter_mut() {
+        if objective_progress.is_revealed(hidden_mineral.0) {
+            *visibility = Visibility::Inherited;
+            commands.entity(entity).remove::<HiddenMineral>();
+        }
+    }
+}
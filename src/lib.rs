@@ -0,0 +1,48 @@
+#[cfg(not(feature = "headless"))]
+compile_error!("the game logic needs the headless feature, enabled by default through presentation");
+
+#[cfg(feature = "presentation")]
+pub mod audio;
+pub mod battery;
+#[cfg(feature = "presentation")]
+pub mod camera_controller;
+pub mod daily_challenge;
+#[cfg(feature = "debug")]
+pub mod debug_camera_controller;
+pub mod environment;
+pub mod game_control;
+pub mod game_mode;
+pub mod gameplay;
+pub mod generator;
+pub mod help;
+#[cfg(feature = "presentation")]
+pub mod hentai_anime;
+pub mod hint;
+pub mod level;
+#[cfg(feature = "presentation")]
+pub mod level_spawner;
+pub mod locale;
+#[cfg(feature = "presentation")]
+pub mod mesh_loader;
+pub mod objective;
+pub mod palette;
+#[cfg(feature = "presentation")]
+pub mod particle;
+#[cfg(feature = "presentation")]
+pub mod presentation;
+pub mod puzzle_evaluation;
+pub mod rover;
+pub mod save;
+#[cfg(feature = "presentation")]
+pub mod scene_hook;
+pub mod score;
+pub mod settings;
+pub mod simulation;
+pub mod solver;
+#[cfg(feature = "presentation")]
+pub mod title_screen;
+#[cfg(feature = "presentation")]
+pub mod ui;
+pub mod unit;
+pub mod verification;
+pub mod wire_network;
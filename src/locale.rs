@@ -0,0 +1,265 @@
+use crate::save::SaveData;
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::LazyLock;
+use thiserror::Error;
+
+/// Language of the text of the game, each has a string table in assets/locale.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Language {
+    #[default]
+    English,
+    French,
+    German,
+    Spanish,
+}
+
+impl Language {
+    pub const ALL: [Language; 4] = [
+        Language::English,
+        Language::French,
+        Language::German,
+        Language::Spanish,
+    ];
+
+    /// Name of the language in that language, so that players find their own
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::French => "Français",
+            Language::German => "Deutsch",
+            Language::Spanish => "Español",
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::French => "fr",
+            Language::German => "de",
+            Language::Spanish => "es",
+        }
+    }
+
+    /// Path of the string table, relative to the assets
+    pub fn path(&self) -> String {
+        format!("locale/{}.strings", self.code())
+    }
+}
+
+/// Text of the game by key, read from a `.strings` file, see assets/locale/en.strings.
+#[derive(Asset, TypePath, Debug, Clone, Default)]
+pub struct StringTable(HashMap<String, String>);
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum StringTableError {
+    #[error("line {line}: expected `key = text`")]
+    MissingSeparator { line: usize },
+    #[error("line {line}: `{key}` is defined more than once")]
+    DuplicateKey { line: usize, key: String },
+}
+
+impl StringTable {
+    /// Reads `key = text` lines, skipping blank ones and comments starting with `#`. `\n` in a
+    /// text is a line break.
+    pub fn parse(source: &str) -> Result<Self, StringTableError> {
+        let mut strings = HashMap::new();
+        for (index, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let Some((key, text)) = line.split_once('=') else {
+                return Err(StringTableError::MissingSeparator { line: index + 1 });
+            };
+            let key = key.trim();
+            if key.is_empty() {
+                return Err(StringTableError::MissingSeparator { line: index + 1 });
+            }
+
+            let text = text.trim().replace("\\n", "\n");
+            if strings.insert(key.to_string(), text).is_some() {
+                return Err(StringTableError::DuplicateKey {
+                    line: index + 1,
+                    key: key.to_string(),
+                });
+            }
+        }
+        Ok(StringTable(strings))
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).map(String::as_str)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(String::as_str)
+    }
+}
+
+/// Built into the game, it is what every other language falls back to.
+static ENGLISH: LazyLock<StringTable> = LazyLock::new(|| {
+    StringTable::parse(include_str!("../assets/locale/en.strings"))
+        .expect("the English string table is valid")
+});
+
+/// Text in the language of the settings, in English where its table has none.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct Strings {
+    language: Language,
+    table: StringTable,
+}
+
+impl Strings {
+    pub fn new(language: Language, table: StringTable) -> Self {
+        Strings { language, table }
+    }
+
+    pub fn english() -> &'static StringTable {
+        &ENGLISH
+    }
+
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// Text of `key`, the key itself when not even English has it so that it stands out.
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        self.table
+            .get(key)
+            .or_else(|| ENGLISH.get(key))
+            .unwrap_or(key)
+    }
+
+    /// Text of `key`, for the text whose English comes with an asset rather than the table.
+    pub fn get_or<'a>(&'a self, key: &str, english: &'a str) -> &'a str {
+        self.table.get(key).unwrap_or(english)
+    }
+
+    /// Text of `key` with each `{name}` replaced by its value.
+    pub fn format(&self, key: &str, args: &[(&str, &dyn Display)]) -> String {
+        let mut text = self.get(key).to_string();
+        for (name, value) in args {
+            text = text.replace(&format!("{{{name}}}"), &value.to_string());
+        }
+        text
+    }
+
+    /// `key.one` or `key.other` depending on `count`, with `{count}` replaced.
+    pub fn plural(&self, key: &str, count: usize) -> String {
+        let form = if count == 1 { "one" } else { "other" };
+        self.format(&format!("{key}.{form}"), &[("count", &count)])
+    }
+}
+
+#[derive(Default)]
+pub struct StringTableLoader;
+
+#[derive(Debug, Error)]
+pub enum StringTableLoaderError {
+    #[error("Could not load string table: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("String table is not UTF-8: {0}")]
+    Utf8(#[from] std::string::FromUtf8Error),
+    #[error("Could not parse string table: {0}")]
+    Parse(#[from] StringTableError),
+}
+
+impl AssetLoader for StringTableLoader {
+    type Asset = StringTable;
+    type Settings = ();
+    type Error = StringTableLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = vec![];
+        reader.read_to_end(&mut bytes).await?;
+        Ok(StringTable::parse(&String::from_utf8(bytes)?)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["strings"]
+    }
+}
+
+/// Loads the string table of the language picked in the settings into `Strings`.
+pub struct LocalePlugin;
+
+/// Table of the language in use, none for English which is built in.
+#[derive(Resource, Default)]
+struct LanguageTable {
+    language: Language,
+    handle: Option<Handle<StringTable>>,
+}
+
+impl Plugin for LocalePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<StringTable>()
+            .init_asset_loader::<StringTableLoader>();
+        app.init_resource::<Strings>();
+        app.init_resource::<LanguageTable>();
+        app.add_systems(
+            Update,
+            (
+                switch_language.run_if(resource_changed::<SaveData>),
+                update_strings,
+            )
+                .chain(),
+        );
+    }
+}
+
+fn switch_language(
+    save_data: Res<SaveData>,
+    mut language_table: ResMut<LanguageTable>,
+    tables: Res<Assets<StringTable>>,
+    mut strings: ResMut<Strings>,
+    asset_server: Res<AssetServer>,
+) {
+    let language = save_data.settings.language;
+    if language_table.language == language {
+        return;
+    }
+
+    language_table.language = language;
+    language_table.handle = None;
+    if language == Language::English {
+        *strings = Strings::default();
+        return;
+    }
+
+    let handle = asset_server.load(language.path());
+    // a table loaded before doesn't send another event
+    if let Some(table) = tables.get(&handle) {
+        *strings = Strings::new(language, table.clone());
+    }
+    language_table.handle = Some(handle);
+}
+
+fn update_strings(
+    mut events: EventReader<AssetEvent<StringTable>>,
+    language_table: Res<LanguageTable>,
+    tables: Res<Assets<StringTable>>,
+    mut strings: ResMut<Strings>,
+) {
+    for event in events.read() {
+        let Some(handle) = &language_table.handle else {
+            continue;
+        };
+        if !event.is_loaded_with_dependencies(handle) && !event.is_modified(handle) {
+            continue;
+        }
+        if let Some(table) = tables.get(handle) {
+            *strings = Strings::new(language_table.language, table.clone());
+        }
+    }
+}
@@ -1,38 +1,9 @@
 extern crate core;
 
-mod debug_camera_controller;
-mod game_control;
-mod help;
-mod hentai_anime;
-mod level;
-mod level_spawner;
-mod mesh_loader;
-mod particle;
-mod puzzle_evaluation;
-mod rover;
-mod scene_hook;
-mod title_screen;
-mod ui;
-
-use crate::debug_camera_controller::DebugCameraControllerPlugin;
-use crate::game_control::actions::ActionController;
-use crate::help::help::HelpPlugin;
-use crate::level::GRADVS_ONERATOR_PLUGIN;
-use crate::level_spawner::LevelSpawnerPlugin;
-use crate::mesh_loader::MeshLoaderPlugin;
-use crate::particle::dust::DustPlugin;
-use crate::particle::fail_particle::FailParticlePlugin;
-use crate::particle::particle::ParticlePlugin;
-use crate::particle::wait_particle::WaitParticlePlugin;
-use crate::puzzle_evaluation::PuzzleEvaluationPlugin;
-use crate::title_screen::{GameState, TitleScreenPlugin};
-use crate::ui::battery_ui::BatteryUIPlugin;
-use crate::ui::final_screen::FinalScreenPlugin;
-use crate::ui::interactive_button::InteractiveButtonPlugin;
-use crate::ui::level_indicator_ui::LevelIndicatorPlugin;
-use crate::ui::win_screen::WinScreenPlugin;
+use mission_ares::gameplay::GameplayPlugins;
+use mission_ares::presentation::PresentationPlugins;
 use bevy::DefaultPlugins;
-use bevy::app::{App, AppExit, PluginGroup};
+use bevy::app::{App, PluginGroup};
 use bevy::asset::AssetMetaCheck;
 use bevy::ecs::error::{BevyError, ErrorContext, GLOBAL_ERROR_HANDLER};
 use bevy::ecs::world::error::EntityMutableFetchError;
@@ -40,7 +11,6 @@ use bevy::image::{ImageAddressMode, ImageFilterMode, ImageSamplerDescriptor};
 use bevy::prelude::*;
 use bevy::render::render_resource::{AddressMode, FilterMode};
 use bevy::window::{CursorGrabMode, CursorOptions};
-use ui::control_ui::ControlUIPlugin;
 
 fn main() {
     let mut app = App::new();
@@ -86,40 +56,11 @@ fn main() {
                 ..default()
             }),
     );
-    app.add_plugins(MeshPickingPlugin);
-    app.add_plugins(InteractiveButtonPlugin);
-    app.add_plugins(MeshLoaderPlugin);
-    app.add_plugins(ActionController);
-    app.add_plugins(ControlUIPlugin);
-    app.add_plugins(HelpPlugin);
-    app.add_plugins(TitleScreenPlugin);
-    app.add_plugins(DebugCameraControllerPlugin);
-    app.add_plugins(GRADVS_ONERATOR_PLUGIN);
-    app.add_plugins(LevelSpawnerPlugin);
-    app.add_plugins(PuzzleEvaluationPlugin);
-    app.add_plugins(BatteryUIPlugin);
-    app.add_plugins(LevelIndicatorPlugin);
-    app.add_plugins(WinScreenPlugin);
-    app.add_plugins(FinalScreenPlugin);
-    app.add_plugins(DustPlugin);
-    app.add_plugins(ParticlePlugin);
-    app.add_plugins(FailParticlePlugin);
-    app.add_plugins(WaitParticlePlugin);
-    app.insert_state(GameState::TitleScreen);
-    app.add_systems(Update, quit_on_escape);
+    app.add_plugins((GameplayPlugins, PresentationPlugins));
 
     app.run();
 }
 
-fn quit_on_escape(
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut app_exit_events: EventWriter<AppExit>,
-) {
-    if keyboard_input.just_pressed(KeyCode::Escape) {
-        app_exit_events.write(AppExit::Success);
-    }
-}
-
 fn global_error_handler(error: BevyError, ctx: ErrorContext) {
     // ignore the bullshit issue where modifying an entity causes a crash in dev
     // if the entity is also de-spawned by another system in the same frame
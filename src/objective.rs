@@ -0,0 +1,92 @@
+use crate::level::{DESTINATIO, GRADVM, TEGVLA_TYPVS};
+use bevy::math::I8Vec2;
+use bevy::prelude::*;
+
+/// Tracks which minerals have been picked up and delivered on the active level.
+#[derive(Resource, Default, Clone, Debug)]
+pub struct ObjectiveProgress {
+    /// Mineral indices in the order they were picked up
+    pub collected: Vec<usize>,
+    /// Mineral indices that reached their destination
+    pub delivered: Vec<usize>,
+    /// Hidden mineral indices a helicopter has scouted
+    pub revealed: Vec<usize>,
+}
+
+impl ObjectiveProgress {
+    pub fn is_collected(&self, mineral: usize) -> bool {
+        self.collected.contains(&mineral)
+    }
+
+    pub fn is_revealed(&self, mineral: usize) -> bool {
+        self.revealed.contains(&mineral)
+    }
+}
+
+/// Whether `rover` is allowed to pick up `mineral` right now, given what it already carries.
+pub fn can_collect(
+    level: &GRADVM,
+    progress: &ObjectiveProgress,
+    rover: u8,
+    cargo: &[usize],
+    mineral: usize,
+) -> bool {
+    let objective = &level.OBIECTVM;
+
+    if progress.is_collected(mineral) {
+        return false;
+    }
+
+    // hidden minerals have to be scouted before anyone knows where to dig
+    if level.CRYSTALLA_OCCVLTA.contains(&mineral) && !progress.is_revealed(mineral) {
+        return false;
+    }
+
+    if objective
+        .CAPACITAS
+        .is_some_and(|capacity| cargo.len() >= capacity as usize)
+    {
+        return false;
+    }
+
+    if objective
+        .DOMINI
+        .get(&mineral)
+        .is_some_and(|&owner| owner != rover)
+    {
+        return false;
+    }
+
+    if let Some(order) = &objective.ORDO {
+        // the next mineral in the order that hasn't been collected yet is the only one allowed
+        let next = order.iter().find(|idx| !progress.is_collected(**idx));
+        if next.is_some_and(|next| *next != mineral) {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Whether a rover standing on `position` can drop off its cargo there.
+pub fn is_delivery_tile(level: &GRADVM, position: I8Vec2, start_position: I8Vec2) -> bool {
+    match level.OBIECTVM.DESTINATIO {
+        None => false,
+        Some(DESTINATIO::INITIVM) => position == start_position,
+        Some(DESTINATIO::SATVRNALIA) => level
+            .TEGLVAE
+            .get(&(position.x, position.y))
+            .is_some_and(|tile| matches!(tile.TYPVS, TEGVLA_TYPVS::SATVRNALIA)),
+    }
+}
+
+/// Whether every mineral of the level has been collected and, if required, delivered.
+pub fn is_complete(level: &GRADVM, progress: &ObjectiveProgress) -> bool {
+    (0..level.CRYSTALLA.len()).all(|mineral| {
+        if level.OBIECTVM.DESTINATIO.is_some() {
+            progress.delivered.contains(&mineral)
+        } else {
+            progress.is_collected(mineral)
+        }
+    })
+}
@@ -0,0 +1,132 @@
+use bevy::color::{Alpha, Color};
+use serde::{Deserialize, Serialize};
+
+/// Colours telling the units apart. Units also show their number on the board, on their button
+/// and in their program, so that colour is never the only cue.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Palette {
+    #[default]
+    Standard,
+    /// For players who hardly see green
+    Deuteranopia,
+    /// For players who hardly see red
+    Protanopia,
+    /// For players who hardly see blue
+    Tritanopia,
+    HighContrast,
+}
+
+/// Opacity of the colour painted over the unit models, the first unit is left unpainted
+const MODEL_ALPHA: f32 = 0.35;
+
+impl Palette {
+    pub const ALL: [Palette; 5] = [
+        Palette::Standard,
+        Palette::Deuteranopia,
+        Palette::Protanopia,
+        Palette::Tritanopia,
+        Palette::HighContrast,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Palette::Standard => "Standard",
+            Palette::Deuteranopia => "Deuteranopia",
+            Palette::Protanopia => "Protanopia",
+            Palette::Tritanopia => "Tritanopia",
+            Palette::HighContrast => "High contrast",
+        }
+    }
+
+    /// Key of the name in the string tables
+    pub fn key(&self) -> &'static str {
+        match self {
+            Palette::Standard => "palette.standard",
+            Palette::Deuteranopia => "palette.deuteranopia",
+            Palette::Protanopia => "palette.protanopia",
+            Palette::Tritanopia => "palette.tritanopia",
+            Palette::HighContrast => "palette.high_contrast",
+        }
+    }
+
+    /// The palette after this one, cycling back to the first.
+    pub fn next(&self) -> Palette {
+        let index = Palette::ALL.iter().position(|palette| palette == self);
+        Palette::ALL[index.map_or(0, |index| (index + 1) % Palette::ALL.len())]
+    }
+
+    /// Colours of the units in the UI and in the path previews, by unit number.
+    pub fn ui_colors(&self) -> Vec<Color> {
+        let srgb = |colors: [(u8, u8, u8); 6]| {
+            colors
+                .into_iter()
+                .map(|(r, g, b)| Color::srgb_u8(r, g, b))
+                .collect()
+        };
+        match self {
+            Palette::Standard => srgb([
+                (230, 230, 230),
+                (51, 166, 51),  // green
+                (51, 102, 191), // blue
+                (204, 191, 51), // yellow
+                (166, 64, 166), // purple
+                (204, 51, 51),  // red
+            ]),
+            // blue and orange stay apart without telling red from green
+            Palette::Deuteranopia => srgb([
+                (230, 230, 230),
+                (0, 114, 178),   // blue
+                (230, 159, 0),   // orange
+                (86, 180, 233),  // sky blue
+                (240, 228, 66),  // yellow
+                (204, 121, 167), // pink
+            ]),
+            Palette::Protanopia => srgb([
+                (230, 230, 230),
+                (0, 114, 178),  // blue
+                (240, 228, 66), // yellow
+                (86, 180, 233), // sky blue
+                (230, 159, 0),  // orange
+                (136, 34, 85),  // wine
+            ]),
+            // red and teal stay apart without telling blue from green
+            Palette::Tritanopia => srgb([
+                (230, 230, 230),
+                (228, 26, 28),   // red
+                (0, 180, 180),   // teal
+                (247, 129, 191), // pink
+                (166, 86, 40),   // brown
+                (152, 78, 163),  // purple
+            ]),
+            Palette::HighContrast => srgb([
+                (255, 255, 255),
+                (255, 215, 0), // yellow
+                (0, 255, 255), // cyan
+                (255, 0, 255), // magenta
+                (0, 255, 0),   // lime
+                (255, 128, 0), // orange
+            ]),
+        }
+    }
+
+    /// Colours painted over the unit models, by unit number.
+    pub fn model_colors(&self) -> Vec<Color> {
+        self.ui_colors()
+            .into_iter()
+            .enumerate()
+            .map(|(index, color)| {
+                if index == 0 {
+                    Color::WHITE
+                } else {
+                    color.with_alpha(MODEL_ALPHA)
+                }
+            })
+            .collect()
+    }
+}
+
+/// What a unit is called on the board and in the UI, its number starting from 1 like the keys
+/// selecting it.
+pub fn unit_label(index: usize) -> String {
+    (index + 1).to_string()
+}
@@ -1,5 +1,6 @@
 use crate::particle::particle::Particle;
 use crate::rover::{RoverEntity, RoverStates};
+use crate::settings::full_motion;
 use bevy::pbr::NotShadowCaster;
 use bevy::prelude::*;
 use rand::random;
@@ -14,7 +15,7 @@ pub struct DustPlugin;
 
 impl Plugin for DustPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, spawn_dust);
+        app.add_systems(Update, spawn_dust.run_if(full_motion));
     }
 }
 
@@ -1,6 +1,7 @@
 use crate::particle::particle::Particle;
 use crate::rover::{ActionExecution, RoverEntity};
-use crate::title_screen::GameState;
+use crate::gameplay::GameState;
+use crate::settings::full_motion;
 use bevy::app::{App, Plugin, Update};
 use bevy::asset::{AssetServer, Assets};
 use bevy::color::Color;
@@ -14,7 +15,9 @@ impl Plugin for WaitParticlePlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
-            wait_particle_spawner.run_if(in_state(GameState::Execution)),
+            wait_particle_spawner
+                .run_if(in_state(GameState::Execution))
+                .run_if(full_motion),
         );
     }
 }
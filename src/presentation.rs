@@ -0,0 +1,78 @@
+use crate::audio::VolumePlugin;
+use crate::camera_controller::CameraControllerPlugin;
+#[cfg(feature = "debug")]
+use crate::debug_camera_controller::DebugCameraControllerPlugin;
+use crate::game_control::path_preview::PathPreviewPlugin;
+use crate::help::help::HelpPlugin;
+use crate::help::tutorial::TutorialPlugin;
+use crate::level_spawner::LevelSpawnerPlugin;
+use crate::locale::LocalePlugin;
+use crate::mesh_loader::MeshLoaderPlugin;
+use crate::particle::dust::DustPlugin;
+use crate::particle::fail_particle::FailParticlePlugin;
+use crate::particle::particle::ParticlePlugin;
+use crate::particle::wait_particle::WaitParticlePlugin;
+use crate::rover::RoverPresentationPlugin;
+use crate::title_screen::TitleScreenPlugin;
+use crate::ui::battery_ui::BatteryUIPlugin;
+use crate::ui::control_ui::ControlUIPlugin;
+use crate::ui::final_screen::FinalScreenPlugin;
+use crate::ui::focus::FocusPlugin;
+use crate::ui::hint_ui::HintUIPlugin;
+use crate::ui::interactive_button::InteractiveButtonPlugin;
+use crate::ui::level_indicator_ui::LevelIndicatorPlugin;
+use crate::ui::loading_ui::LoadingUIPlugin;
+use crate::ui::localized_text::LocalizedTextPlugin;
+use crate::ui::pause_menu::PauseMenuPlugin;
+use crate::ui::rover_label::RoverLabelPlugin;
+use crate::ui::settings_menu::SettingsMenuPlugin;
+use crate::ui::tooltip::TooltipPlugin;
+use crate::ui::touch::TouchPlugin;
+use crate::ui::win_screen::WinScreenPlugin;
+use bevy::app::PluginGroupBuilder;
+use bevy::picking::mesh_picking::MeshPickingPlugin;
+use bevy::prelude::*;
+
+/// The scene, the UI, the particles and the sounds, on top of the `GameplayPlugins`.
+pub struct PresentationPlugins;
+
+impl PluginGroup for PresentationPlugins {
+    fn build(self) -> PluginGroupBuilder {
+        let group = PluginGroupBuilder::start::<Self>()
+            .add(MeshPickingPlugin)
+            .add(LocalePlugin)
+            .add(LocalizedTextPlugin)
+            .add(InteractiveButtonPlugin)
+            .add(FocusPlugin)
+            .add(TooltipPlugin)
+            .add(TouchPlugin)
+            .add(MeshLoaderPlugin)
+            .add(PathPreviewPlugin)
+            .add(ControlUIPlugin)
+            .add(HintUIPlugin)
+            .add(HelpPlugin)
+            .add(SettingsMenuPlugin)
+            .add(PauseMenuPlugin)
+            .add(TutorialPlugin)
+            .add(TitleScreenPlugin)
+            .add(CameraControllerPlugin)
+            .add(LevelSpawnerPlugin)
+            .add(RoverPresentationPlugin)
+            .add(BatteryUIPlugin)
+            .add(LevelIndicatorPlugin)
+            .add(LoadingUIPlugin)
+            .add(RoverLabelPlugin)
+            .add(WinScreenPlugin)
+            .add(FinalScreenPlugin)
+            .add(DustPlugin)
+            .add(ParticlePlugin)
+            .add(FailParticlePlugin)
+            .add(WaitParticlePlugin)
+            .add(VolumePlugin);
+
+        #[cfg(feature = "debug")]
+        let group = group.add(DebugCameraControllerPlugin);
+
+        group
+    }
+}
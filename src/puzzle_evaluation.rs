@@ -1,22 +1,28 @@
-use crate::game_control::actions::ActionType::Wait;
-use crate::rover::{ActionExecution, RoverCollectable, RoverEntity};
-use crate::{level::GRADVM, level_spawner::ActiveLevel};
+use crate::objective::ObjectiveProgress;
+use crate::rover::{ActionExecution, RoverCollectable, RoverEntity, advance_turn};
+use crate::score::ExecutionStats;
+use crate::simulation::{FailureReason, TurnOutcome, UnitState, finish_turn, turn_outcome};
+use crate::{gameplay::ActiveLevel, level::GRADVM};
 use bevy::prelude::*;
-use std::cmp::min;
-use std::collections::HashMap;
 
 pub struct PuzzleEvaluationPlugin;
 
 impl Plugin for PuzzleEvaluationPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, on_puzzle_evaluation_request);
+        // turns are evaluated on the fixed timestep they are played on
+        app.add_systems(
+            FixedUpdate,
+            on_puzzle_evaluation_request.after(advance_turn),
+        );
         app.add_systems(Update, debug_puzzle_evaluation);
         app.add_event::<PuzzleEvaluationRequestEvent>();
         app.add_event::<PuzzleResponseEvent>();
+        app.init_resource::<ObjectiveProgress>();
+        app.init_resource::<ExecutionStats>();
     }
 }
 
-#[derive(Event, PartialEq)]
+#[derive(Event, Clone, Debug, PartialEq)]
 pub enum PuzzleResponseEvent {
     Solved,
     Failed,
@@ -26,21 +32,20 @@ pub enum PuzzleResponseEvent {
 #[derive(Event)]
 pub struct PuzzleEvaluationRequestEvent;
 
-fn on_puzzle_evaluation_request(
+#[allow(clippy::too_many_arguments)]
+pub fn on_puzzle_evaluation_request(
+    mut commands: Commands,
     mut evaluation_requests: EventReader<PuzzleEvaluationRequestEvent>,
     mut puzzle_response_event_writer: EventWriter<PuzzleResponseEvent>,
     mut rovers: Query<&mut RoverEntity>,
-    minerals: Query<&RoverCollectable>,
+    minerals: Query<(Entity, &RoverCollectable)>,
+    mut objective_progress: ResMut<ObjectiveProgress>,
+    mut execution_stats: ResMut<ExecutionStats>,
     action_execution: Res<ActionExecution>,
     active_level: Res<ActiveLevel>,
     levels: Res<Assets<GRADVM>>,
 ) {
     for _ in evaluation_requests.read() {
-        if minerals.is_empty() {
-            puzzle_response_event_writer.write(PuzzleResponseEvent::Solved);
-            break;
-        }
-
         let Some(active_level_handle) = &active_level.0 else {
             log::error!(
                 "No active level. How the FUCK could you request that I evaluate the puzzle?"
@@ -54,162 +59,61 @@ fn on_puzzle_evaluation_request(
             );
             return;
         };
-        let mut i = 0;
-
-        let mut rover_positions: HashMap<(i8, i8), RoverEntity> = HashMap::new();
-
-        for rover in rovers.iter() {
-            rover_positions.insert(
-                (rover.logical_position.x, rover.logical_position.y),
-                rover.clone(),
-            );
-        }
-
-        for mut rover in rovers.iter_mut() {
-            if rover.is_acting {
-                continue; // Do not affect battery level for rovers still acting
-            }
-
-            let tile_coords = (rover.logical_position.x, rover.logical_position.y);
-            if let Some(&other) = active_level.NEXVS.get(&tile_coords) {
-                for (other_pos, other_rover) in rover_positions.iter() {
-                    let Some(tile_first) = active_level
-                        .TEGLVAE
-                        .get(&(rover.logical_position.x, rover.logical_position.y))
-                    else {
-                        log::error!(
-                            "No tile found for rover. This IS BAAAD man ☠️☠️☠️ fuck these guys bro"
-                        );
-                        return;
-                    };
-
-                    let Some(tile_second) = active_level.TEGLVAE.get(&(
-                        other_rover.logical_position.x,
-                        other_rover.logical_position.y,
-                    )) else {
-                        log::error!(
-                            "No tile found for rover. This IS BAAAD man ☠️☠️☠️ fuck these guys bro"
-                        );
-                        return;
-                    };
-
-                    if other == *other_pos {
-                        if tile_first.VMBRA && tile_second.VMBRA {
-                            if rover.battery_level < other_rover.battery_level
-                                && other_rover.battery_level > 0
-                            //&& !rover.is_done
-                            {
-                                println!(
-                                    "[UMBRA POWER TRANSFER] Rover {} receiving power",
-                                    rover.identifier
-                                );
-                                rover.battery_level += 1;
-                                rover.battery_level = min(rover.battery_level, 3);
-                            }
-
-                            if rover.battery_level > other_rover.battery_level
-                                && rover.battery_level > 0
-                            {
-                                println!(
-                                    "[UMBRA POWER TRANSFER] Rover {} giving power",
-                                    rover.identifier
-                                );
-                                rover.battery_level -= 1;
-                            }
-                        } else if tile_first.VMBRA || tile_second.VMBRA {
-                            println!("ONE IN UMBRA");
-                            if tile_first.VMBRA && other_rover.battery_level > 0
-                            /* && !rover.is_done*/
-                            {
-                                println!("PROVIDING POWER");
-                                rover.battery_level += 1;
-                                rover.battery_level = min(rover.battery_level, 3);
-                            }
-
-                            if tile_second.VMBRA
-                                && rover.battery_level > 0
-                                && other_rover.battery_level < 3
-                            {
-                                println!("GIVING POWER");
-                                rover.battery_level -= 1;
-                            }
-                        } else {
-                            println!("BOTH IN THE SUN");
-                        }
-
-                        break;
-                    }
-                }
-            }
-        }
-
-        let rover_executions = action_execution.action_states.clone();
-        for mut rover in rovers.iter_mut() {
-            let Some(tile) = active_level
-                .TEGLVAE
-                .get(&(rover.logical_position.x, rover.logical_position.y))
+        execution_stats.turns += 1;
+
+        // the rules go through the units in the order they are numbered
+        let mut rovers: Vec<Mut<RoverEntity>> = rovers.iter_mut().collect();
+        rovers.sort_by_key(|rover| rover.identifier);
+
+        let mut units: Vec<UnitState> = rovers.iter().map(|rover| rover.unit_state()).collect();
+        let failures: Vec<Option<FailureReason>> =
+            rovers.iter().map(|rover| rover.failure).collect();
+        let turn_end = finish_turn(
+            active_level,
+            &mut units,
+            &action_execution.turn_actions,
+            &failures,
+            &mut objective_progress,
+        );
+        execution_stats.battery_used += turn_end.battery_used;
+
+        for (rover, unit) in rovers.iter_mut().zip(&units) {
+            rover.apply_state(unit);
+            let Some(state) = action_execution
+                .action_states
+                .get(rover.identifier as usize)
             else {
-                log::error!(
-                    "No tile found for rover. This IS BAAAD man ☠️☠️☠️ fuck these guys bro"
-                );
+                log::error!("No execution state for unit {}", rover.identifier);
                 return;
             };
-
-            let state = rover_executions.get(i).unwrap();
-
-            println!(
-                "Action list len {}, active action index {} rover {} position {}",
-                state.action_list.len(),
-                state.active_action_idx,
-                rover.identifier,
-                rover.logical_position
-            );
-            if state.active_action_idx > 0
-                && let Some(state) = state.action_list.get(state.active_action_idx - 1)
-            {
-                print!("{:?}", state.moves.0);
-                if tile.VMBRA {
-                    println!(" to tile in shadow");
-                } else {
-                    println!(" to tile in sun");
-                }
-                let state_type = if rover.is_done { Wait } else { state.moves.0 };
-
-                if state_type != Wait && rover.battery_level > 0 && !rover.is_acting {
-                    println!("Losing 1 battery");
-                    rover.battery_level -= 1;
-                }
-
-                if !tile.VMBRA && rover.battery_level < 3 && !rover.is_acting {
-                    println!("Gaining 1 battery");
-                    rover.battery_level += 1;
-                }
-            }
             rover.is_done = state.action_list.len() == state.active_action_idx;
-            i += 1;
         }
 
-        if let Some(_rover) = rovers.iter().find(|rover| rover.collided) {
-            println!("PUZZLE FAILED! Why? VEHICVLVM MOBILE COLLIDIT!");
-            puzzle_response_event_writer.write(PuzzleResponseEvent::Failed);
-            break;
+        for (mineral_entity, mineral) in minerals.iter() {
+            if turn_end.collected.contains(&mineral.index) {
+                commands.entity(mineral_entity).despawn();
+            }
         }
 
-        if rovers
-            .iter()
-            .enumerate()
-            .find(|(idx, _rover)| {
-                rover_executions[*idx].active_action_idx
-                    != action_execution.action_states[*idx].action_list.len()
-            })
-            .is_none()
-        {
-            println!("PUZZLE FAILED! Why? NVLLAE ACTIONES AMPLIVS");
-            puzzle_response_event_writer.write(PuzzleResponseEvent::Failed);
-            break;
+        match turn_outcome(active_level, &objective_progress, &failures) {
+            TurnOutcome::Solved => {
+                puzzle_response_event_writer.write(PuzzleResponseEvent::Solved);
+                break;
+            }
+            TurnOutcome::Failed(reason) => {
+                log::info!("PUZZLE FAILED! Why? {}", reason.description());
+                puzzle_response_event_writer.write(PuzzleResponseEvent::Failed);
+                break;
+            }
+            TurnOutcome::InProgress if rovers.iter().all(|rover| rover.is_done) => {
+                log::info!("PUZZLE FAILED! Why? NVLLAE ACTIONES AMPLIVS");
+                puzzle_response_event_writer.write(PuzzleResponseEvent::Failed);
+                break;
+            }
+            TurnOutcome::InProgress => {
+                puzzle_response_event_writer.write(PuzzleResponseEvent::InProgress);
+            }
         }
-
-        puzzle_response_event_writer.write(PuzzleResponseEvent::InProgress);
     }
 }
 
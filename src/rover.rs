@@ -1,10 +1,11 @@
+use crate::battery::BatteryRules;
 use crate::game_control::actions::{Action, ActionType};
-use crate::hentai_anime::Animation;
-use crate::level::{is_pos_in_level, GRADVM};
-use crate::level_spawner::{ActiveLevel, TILE_SIZE};
+use crate::level::GRADVM;
+use crate::gameplay::{ActiveLevel, GameState, TILE_SIZE};
 use crate::puzzle_evaluation::{PuzzleEvaluationRequestEvent, PuzzleResponseEvent};
-use crate::title_screen::GameState;
-use bevy::math::ops::abs;
+use crate::unit::UnitKind;
+use crate::puzzle_evaluation::on_puzzle_evaluation_request;
+use crate::simulation::{FailureReason, UnitState, move_units};
 use bevy::math::I8Vec2;
 use bevy::prelude::*;
 use std::f32::consts::PI;
@@ -12,8 +13,8 @@ use std::time::Duration;
 
 const SPEED: f32 = 7.5;
 const WAIT_ACTION_TIME: f32 = 0.5;
-const TURN_SPEED: f32 = 5.0;
-const WAIT_OTHER_DIPSHITS_TO_TURN_TIME: f32 = 0.5;
+/// Time a unit takes to face a new direction, every move of the turn waits for it
+const TURN_TIME: f32 = 0.3;
 
 const WAIT_BETWEEN_TURNS: f32 = 0.25;
 
@@ -25,23 +26,96 @@ pub enum RoverStates {
 
 #[derive(Component, Clone)]
 pub struct RoverEntity {
+    pub kind: UnitKind,
     pub is_acting: bool,
     pub is_turn_done: bool,
     pub base_color: Color,
-    pub gltf_handle: Handle<Gltf>,
     pub logical_position: I8Vec2,
     pub battery_level: u8,
+    pub battery_rules: BatteryRules,
     pub identifier: u8,
     pub heading: f32,
     pub rover_state: RoverStates,
     pub collided: bool,
+    /// Why the command the unit collided on couldn't be performed
+    pub failure: Option<FailureReason>,
     pub spawned_fail_particle: bool,
     pub spawned_wait_particle: bool,
     pub is_done: bool,
+    pub start_position: I8Vec2,
+    /// Indices of the minerals currently carried
+    pub cargo: Vec<usize>,
+    /// Charge of the battery pack a helicopter can hand to a rover, 0 once handed over
+    pub battery_pack: u8,
+}
+
+impl RoverEntity {
+    /// A unit of `level` as it starts the level, `identifier` being its number in reading order.
+    pub fn at_start(level: &GRADVM, identifier: u8, base_color: Color) -> Self {
+        let kind = level.GENVS(identifier);
+        let battery_rules = level.REGVLAE_PILAE(identifier);
+        let start = level
+            .VEHICVLA
+            .get(identifier as usize)
+            .copied()
+            .unwrap_or_default();
+        let start_position = I8Vec2::new(start.0, start.1);
+
+        RoverEntity {
+            kind,
+            is_acting: false,
+            is_turn_done: false,
+            base_color,
+            logical_position: start_position,
+            battery_level: battery_rules.starting_charge,
+            battery_rules,
+            identifier,
+            heading: -PI / 2.0,
+            rover_state: RoverStates::Standby,
+            collided: false,
+            failure: None,
+            spawned_fail_particle: false,
+            spawned_wait_particle: false,
+            is_done: false,
+            start_position,
+            cargo: vec![],
+            battery_pack: match kind {
+                UnitKind::Rover => 0,
+                UnitKind::Helicopter => level.SARCINA,
+            },
+        }
+    }
+
+    /// The unit as far as the rules of [`crate::simulation`] are concerned.
+    pub fn unit_state(&self) -> UnitState {
+        UnitState {
+            kind: self.kind,
+            position: self.logical_position,
+            heading: self.heading,
+            battery_level: self.battery_level,
+            battery_rules: self.battery_rules,
+            start_position: self.start_position,
+            cargo: self.cargo.clone(),
+            battery_pack: self.battery_pack,
+        }
+    }
+
+    /// Takes on what the rules made of the unit during a turn.
+    pub fn apply_state(&mut self, unit: &UnitState) {
+        self.logical_position = unit.position;
+        self.heading = unit.heading;
+        self.battery_level = unit.battery_level;
+        self.cargo = unit.cargo.clone();
+        self.battery_pack = unit.battery_pack;
+    }
 }
 
 #[derive(Component)]
-pub struct RoverCollectable;
+pub struct RoverCollectable {
+    /// Index of the mineral in the level's reading order
+    pub index: usize,
+    pub logical_position: I8Vec2,
+}
 
 #[derive(Event)]
 pub struct ActionListExecute {
@@ -52,54 +126,83 @@ pub struct ActionListExecute {
 pub struct RoverActionState {
     pub action_list: Vec<Action>,
     pub active_action_idx: usize,
-    pub wait_time_start: f32,
     pub is_waiting: bool,
-    pub is_turning: bool,
-    pub wait_time: f32,
+    /// Pose of the unit when the turn started, the visuals go from there to the logical pose
+    pub turn_start_position: I8Vec2,
+    pub turn_start_heading: f32,
+    /// Seconds into the turn at which the unit is done with its command, `None` without one
+    pub action_time: Option<f32>,
+}
+
+/// Where the current turn stands. Turns only move forward on the fixed timestep, so a program
+/// always plays out the same way whatever the frame rate.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TurnPhase {
+    /// Units are performing their commands
+    #[default]
+    Acting,
+    /// Every unit is done, short pause before the turn is evaluated
+    BetweenTurns,
+    /// Waiting for the evaluation of the turn
+    Evaluating,
 }
 
 #[derive(Resource, Clone, Debug)]
 pub struct ActionExecution {
     pub is_built: bool,
-    pub is_evaluating: bool,
+    pub phase: TurnPhase,
+    /// Time spent in the current phase, in fixed timesteps
+    pub phase_time: Duration,
+    /// Seconds the turn starts with for the units changing direction, 0 if none does
+    pub turning_time: f32,
     pub action_states: Vec<RoverActionState>,
-}
-
-#[derive(Component)]
-pub struct BetweenTurnsTimer {
-    timer: Timer,
+    /// Command each unit performs this turn, by identifier, `None` once out of commands
+    pub turn_actions: Vec<Option<ActionType>>,
 }
 
 pub struct RoverPlugin;
 
+/// How the units look and sound while the turns play out.
+#[cfg(feature = "presentation")]
+pub struct RoverPresentationPlugin;
+
 impl Plugin for RoverPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
-            Update,
+            FixedUpdate,
             (
-                start_execution.run_if(not(in_state(GameState::TitleScreen))),
-                action_execution.run_if(not(in_state(GameState::TitleScreen))),
-                continue_execution.run_if(not(in_state(GameState::TitleScreen))),
-                update_rover_collectables.run_if(not(in_state(GameState::TitleScreen))),
-                update_rover_sounds.run_if(not(in_state(GameState::TitleScreen))),
-                detect_move_done.run_if(in_state(GameState::Execution)),
-                update_betweenturns_timer.run_if(in_state(GameState::Execution)),
-            ),
+                (start_execution, advance_turn).chain(),
+                continue_execution.after(on_puzzle_evaluation_request),
+            )
+                .run_if(not(in_state(GameState::TitleScreen))),
         );
         app.insert_resource(ActionExecution {
             is_built: false,
-            is_evaluating: false,
+            phase: TurnPhase::default(),
+            phase_time: Duration::ZERO,
+            turning_time: 0.0,
             action_states: vec![],
+            turn_actions: vec![],
         });
         app.add_event::<ActionListExecute>();
     }
 }
 
+#[cfg(feature = "presentation")]
+impl Plugin for RoverPresentationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (play_unit_animations, animate_units, update_rover_sounds)
+                .run_if(not(in_state(GameState::TitleScreen))),
+        );
+    }
+}
+
 fn setup_action_movements(
     active_level: &Res<ActiveLevel>,
     levels: &Res<Assets<GRADVM>>,
     action_execution: &mut ResMut<ActionExecution>,
-    time: &Res<Time>,
     rover_query: &mut Query<&mut RoverEntity>,
 ) {
     for rover in rover_query.iter() {
@@ -108,135 +211,141 @@ fn setup_action_movements(
         }
     }
 
-    let mut prev_pos_vec = Vec::new();
-    let mut position_vec = Vec::new();
-
-    for mut rover in rover_query.iter_mut() {
-        let robot_num = rover.identifier as usize;
-
-        let mut is_action_valid = true;
-        let prev_pos = rover.logical_position;
-
-        let Some(level_handle) = &active_level.0 else {
-            return;
-        };
-        let level = levels.get(level_handle).unwrap();
+    let Some(level_handle) = &active_level.0 else {
+        return;
+    };
+    let level = levels.get(level_handle).unwrap();
 
-        let actions = &action_execution.action_states[robot_num].action_list;
-        if actions.is_empty() {
-            continue;
-        }
+    // the rules go through the units in the order they are numbered
+    let mut rovers: Vec<Mut<RoverEntity>> = rover_query.iter_mut().collect();
+    rovers.sort_by_key(|rover| rover.identifier);
+
+    let actions: Vec<Option<ActionType>> = rovers
+        .iter()
+        .map(|rover| {
+            let state = &action_execution.action_states[rover.identifier as usize];
+            state
+                .action_list
+                .get(state.active_action_idx)
+                .map(|action| action.moves.0)
+        })
+        .collect();
+    let mut units: Vec<UnitState> = rovers.iter().map(|rover| rover.unit_state()).collect();
+    let failures = move_units(level, &mut units, &actions);
+
+    let mut is_anyone_turning = false;
+    for (mut rover, (unit, (action, failure))) in rovers
+        .into_iter()
+        .zip(units.iter().zip(actions.iter().zip(failures)))
+    {
+        let state = &mut action_execution.action_states[rover.identifier as usize];
+
+        state.turn_start_position = rover.logical_position;
+        state.turn_start_heading = rover.heading;
+        state.action_time = None;
+        rover.rover_state = RoverStates::Standby;
 
-        let Some(action) = actions.get(action_execution.action_states[robot_num].active_action_idx)
-        else {
+        // units without a command this turn have nothing to wait for
+        let Some(action_type) = action else {
+            rover.is_turn_done = true;
             continue;
         };
 
-        let mut new_heading = rover.heading;
-        let mut new_pos = rover.logical_position;
-
-        // Predict new position
-        let action_type = action.moves.0;
-        match action_type {
-            ActionType::MoveUp => {
-                new_pos += I8Vec2::new(0, 1);
-                new_heading = -PI / 2.0;
-            }
-            ActionType::MoveDown => {
-                new_pos -= I8Vec2::new(0, 1);
-                new_heading = PI / 2.0;
-            }
-            ActionType::MoveLeft => {
-                new_pos -= I8Vec2::new(1, 0);
-                new_heading = 0.0;
-            }
-            ActionType::MoveRight => {
-                new_pos += I8Vec2::new(1, 0);
-                new_heading = PI;
-            }
-            ActionType::Wait => {
-                action_execution.action_states[robot_num].wait_time_start =
-                    time.elapsed_secs_wrapped();
-                action_execution.action_states[robot_num].wait_time = WAIT_ACTION_TIME;
-                action_execution.action_states[robot_num].is_waiting = true;
-            }
+        rover.is_turn_done = false;
+        if rover.heading != unit.heading {
+            is_anyone_turning = true;
         }
+        rover.apply_state(unit);
 
-        if !is_pos_in_level(level, &new_pos)
-            || rover.battery_level == 0 && action_type != ActionType::Wait
-        {
-            is_action_valid = false;
+        if let Some(failure) = failure {
+            rover.collided = true;
+            rover.failure = Some(failure);
+        } else {
+            log::debug!(
+                "Setting position for rover {}, {}",
+                rover.identifier,
+                rover.logical_position
+            );
+            rover.is_acting = true;
+            state.is_waiting = action_type.is_stationary();
+            if rover.logical_position != state.turn_start_position {
+                rover.rover_state = RoverStates::Moving;
+            }
         }
+    }
+    action_execution.turn_actions = actions;
 
-        if position_vec.contains(&new_pos) {
-            is_action_valid = false;
+    // moves wait for every unit to face its new direction
+    let turning_time = if is_anyone_turning { TURN_TIME } else { 0.0 };
+    for rover in rover_query.iter() {
+        let state = &mut action_execution.action_states[rover.identifier as usize];
+        if rover.is_turn_done {
+            continue;
         }
 
-        let tuple = (new_pos, prev_pos);
-        if prev_pos_vec.contains(&tuple) {
-            is_action_valid = false;
-        }
+        state.action_time = Some(if state.is_waiting {
+            WAIT_ACTION_TIME
+        } else if rover.logical_position != state.turn_start_position {
+            turning_time + TILE_SIZE / SPEED
+        } else {
+            turning_time
+        });
+    }
 
-        rover.rover_state = RoverStates::Standby;
+    action_execution.turning_time = turning_time;
+    action_execution.phase = TurnPhase::Acting;
+    action_execution.phase_time = Duration::ZERO;
+}
 
-        if !is_action_valid {
-            action_execution.action_states[robot_num].wait_time_start = time.elapsed_secs_wrapped();
-            action_execution.action_states[robot_num].is_waiting = true;
-            rover.logical_position = prev_pos;
-            rover.collided = true;
+/// Position and heading of a unit after performing `action`, ignoring anything that could get
+/// in its way. Turning takes a whole action and keeps the unit on its tile.
+pub fn predict_pose(position: I8Vec2, heading: f32, action: ActionType) -> (I8Vec2, f32) {
+    let direction = heading_direction(heading);
+    match action {
+        ActionType::MoveUp => (position + I8Vec2::new(0, 1), -PI / 2.0),
+        ActionType::MoveDown => (position - I8Vec2::new(0, 1), PI / 2.0),
+        ActionType::MoveLeft => (position - I8Vec2::new(1, 0), 0.0),
+        ActionType::MoveRight => (position + I8Vec2::new(1, 0), PI),
+        ActionType::Forward => (position + direction, heading),
+        ActionType::Reverse => (position - direction, heading),
+        ActionType::TurnLeft => (
+            position,
+            direction_heading(I8Vec2::new(-direction.y, direction.x)),
+        ),
+        ActionType::TurnRight => (
+            position,
+            direction_heading(I8Vec2::new(direction.y, -direction.x)),
+        ),
+        ActionType::Wait | ActionType::Scout | ActionType::DropPack => (position, heading),
+    }
+}
 
-            if rover.heading != new_heading {
-                action_execution.action_states[robot_num].is_turning = true;
-                rover.heading = new_heading;
-                rover.is_acting = true;
-            }
-        } else {
-            println!(
-                "Setting position for rover {}, {}",
-                rover.identifier, new_pos
-            );
-            rover.logical_position = new_pos;
-            rover.rover_state = RoverStates::Moving;
-            rover.is_acting = true;
-            rover.is_turn_done = false;
+/// Tile offset a unit with this heading faces, on the level grid (y going up).
+pub fn heading_direction(heading: f32) -> I8Vec2 {
+    I8Vec2::new(-heading.cos().round() as i8, -heading.sin().round() as i8)
+}
 
-            if rover.heading != new_heading {
-                action_execution.action_states[robot_num].is_turning = true;
-                rover.heading = new_heading;
-            }
-        }
-        position_vec.push(rover.logical_position);
-        prev_pos_vec.push((prev_pos, rover.logical_position));
+fn direction_heading(direction: I8Vec2) -> f32 {
+    match (direction.x, direction.y) {
+        (0, 1) => -PI / 2.0,
+        (0, -1) => PI / 2.0,
+        (1, 0) => PI,
+        _ => 0.0,
     }
 }
 
 fn start_execution(
     mut events: EventReader<ActionListExecute>,
     mut action_execution: ResMut<ActionExecution>,
-    mut rover_query: Query<(&mut RoverEntity)>,
-    time: Res<Time>,
+    mut rover_query: Query<&mut RoverEntity>,
     active_level: Res<ActiveLevel>,
     levels: Res<Assets<GRADVM>>,
-    mut player_query: Query<&mut AnimationPlayer>,
-    mut animation: Query<&Animation, With<RoverEntity>>,
 ) {
     for event in events.read() {
         if action_execution.is_built {
             return; // Avoid double execution
         }
 
-        // Start animations
-        for animation in animation.iter_mut() {
-            if let Some(player_entity) = animation.player_entity {
-                if let Ok(mut player) = player_query.get_mut(player_entity) {
-                    for hentai in &animation.animation_list {
-                        player.play(hentai.clone()).repeat();
-                        //println!("Start rover anime");
-                    }
-                }
-            }
-        }
-
         action_execution.is_built = true;
 
         action_execution.action_states.clear();
@@ -244,10 +353,10 @@ fn start_execution(
             action_execution.action_states.push(RoverActionState {
                 action_list: action_list.clone(),
                 active_action_idx: 0,
-                wait_time_start: 0.0,
                 is_waiting: false,
-                is_turning: false,
-                wait_time: 0.0,
+                turn_start_position: I8Vec2::ZERO,
+                turn_start_heading: 0.0,
+                action_time: None,
             })
         }
 
@@ -257,176 +366,152 @@ fn start_execution(
             &active_level,
             &levels,
             &mut action_execution,
-            &time,
             &mut rover_query,
         );
     }
 }
 
-fn detect_move_done(
-    mut commands: Commands,
-    query: Query<&RoverEntity>,
+/// Plays the turn out on the fixed timestep: units finish their command once its time has come,
+/// then the turn is evaluated after a short pause.
+pub fn advance_turn(
     mut action_execution: ResMut<ActionExecution>,
+    mut rover_query: Query<&mut RoverEntity>,
+    mut evaluation_requests: EventWriter<PuzzleEvaluationRequestEvent>,
+    time: Res<Time<Fixed>>,
 ) {
-    if action_execution.is_evaluating || !action_execution.is_built {
+    if !action_execution.is_built {
         return;
     }
 
-    if query.is_empty() {
-        return;
-    }
+    action_execution.phase_time += time.delta();
+    let phase_time = action_execution.phase_time.as_secs_f32();
+
+    match action_execution.phase {
+        TurnPhase::Acting => {
+            for mut rover in rover_query.iter_mut() {
+                let state = &mut action_execution.action_states[rover.identifier as usize];
+                let Some(action_time) = state.action_time else {
+                    continue;
+                };
+                if rover.is_turn_done || phase_time < action_time {
+                    continue;
+                }
 
-    let mut i = 0;
-    for rover in query.iter() {
-        i += 1;
-        if !rover.is_turn_done && !rover.is_done {
-            return;
+                if state.active_action_idx < state.action_list.len() {
+                    state.active_action_idx += 1;
+                }
+                state.is_waiting = false;
+                rover.is_acting = false;
+                rover.is_turn_done = true;
+                rover.rover_state = RoverStates::Standby;
+                log::debug!("End of action for rover {}", rover.identifier);
+                log::debug!("New Action Idx {}", state.active_action_idx);
+            }
+
+            if !rover_query.is_empty()
+                && rover_query
+                    .iter()
+                    .all(|rover| rover.is_turn_done || rover.is_done)
+            {
+                action_execution.phase = TurnPhase::BetweenTurns;
+                action_execution.phase_time = Duration::ZERO;
+            }
         }
+        TurnPhase::BetweenTurns => {
+            if phase_time >= WAIT_BETWEEN_TURNS {
+                action_execution.phase = TurnPhase::Evaluating;
+                action_execution.phase_time = Duration::ZERO;
+                evaluation_requests.write(PuzzleEvaluationRequestEvent);
+            }
+        }
+        TurnPhase::Evaluating => {}
     }
-
-    action_execution.is_evaluating = true;
-
-    commands.spawn(BetweenTurnsTimer {
-        timer: Timer::new(Duration::from_secs_f32(WAIT_BETWEEN_TURNS), TimerMode::Once),
-    });
 }
 
-fn update_betweenturns_timer(
-    mut commands: Commands,
-    mut query: Query<(Entity, &mut BetweenTurnsTimer)>,
-    time: Res<Time>,
+#[cfg(feature = "presentation")]
+fn play_unit_animations(
+    mut events: EventReader<ActionListExecute>,
+    mut player_query: Query<&mut AnimationPlayer>,
+    mut animation: Query<&crate::hentai_anime::Animation, With<RoverEntity>>,
 ) {
-    for (entity, mut timer) in query.iter_mut() {
-        timer.timer.tick(time.delta());
-        if timer.timer.just_finished() {
-            commands.entity(entity).despawn();
-            commands.send_event(PuzzleEvaluationRequestEvent);
+    if events.read().last().is_none() {
+        return;
+    }
+
+    // Start animations
+    for animation in animation.iter_mut() {
+        if let Some(player_entity) = animation.player_entity {
+            if let Ok(mut player) = player_query.get_mut(player_entity) {
+                for hentai in &animation.animation_list {
+                    player.play(hentai.clone()).repeat();
+                    //println!("Start rover anime");
+                }
+            }
         }
     }
 }
 
-fn action_execution(
-    mut rover_query: Query<(Entity, &mut RoverEntity, &mut Transform)>,
+/// Places units between the pose they started the turn in and their logical pose, turning
+/// first and moving after, smoothed over the time left until the next fixed timestep.
+#[cfg(feature = "presentation")]
+fn animate_units(
+    mut rover_query: Query<(&RoverEntity, &mut Transform)>,
     active_level: Res<ActiveLevel>,
     levels: Res<Assets<GRADVM>>,
-    mut action_execution: ResMut<ActionExecution>,
-    time: Res<Time>,
+    action_execution: Res<ActionExecution>,
+    time: Res<Time<Fixed>>,
 ) {
     if !action_execution.is_built {
         return;
     }
-    let Some(level_handle) = &active_level.0 else {
+    let Some(level) = active_level
+        .0
+        .as_ref()
+        .and_then(|level_handle| levels.get(level_handle))
+    else {
         return;
     };
-    let level = levels.get(level_handle).unwrap();
-
-    let effective_level_width = level.LATIVIDO as f32 * TILE_SIZE;
-    let effective_level_height = level.ALTIVIDO as f32 * TILE_SIZE;
-
-    let mut any_rover_turning = false;
-
-    for (_, mut rover, _) in rover_query.iter_mut() {
-        let robot_num = rover.identifier as usize;
-
-        if action_execution.action_states[robot_num].is_turning {
-            any_rover_turning = true;
-        }
-    }
-
-    // Iterate through each robot and move them progressively towards the next tile based on action
-    for (_, mut rover, mut trans) in rover_query.iter_mut() {
-        if rover.is_turn_done {
-            continue;
-        }
-        let robot_num = rover.identifier as usize;
-
-        //dbg!(&action_execution.action_states[robot_num]);
-
-        // If in wait, skip rest of loop logic
-        if action_execution.action_states[robot_num].is_waiting {
-            let current_time = time.elapsed_secs_wrapped();
 
-            let wait_duration =
-                current_time - action_execution.action_states[robot_num].wait_time_start;
-
-            if wait_duration > action_execution.action_states[robot_num].wait_time {
-                if action_execution.action_states[robot_num].wait_time == WAIT_ACTION_TIME {
-                    // Only perform the following if wait action was reason for wait
-                    if action_execution.action_states[robot_num].active_action_idx
-                        < action_execution.action_states[robot_num].action_list.len()
-                    {
-                        action_execution.action_states[robot_num].active_action_idx += 1;
-                    }
-                    rover.is_acting = false;
-                    rover.is_turn_done = true;
-                }
-
-                action_execution.action_states[robot_num].is_waiting = false;
-            }
-
-            continue;
-        }
-
-        if action_execution.action_states[robot_num].is_turning {
-            let diff = trans
-                .rotation
-                .angle_between(Quat::from_rotation_y(rover.heading));
-
-            if abs(diff) > 0.1 {
-                let step = TURN_SPEED * time.delta_secs();
-
-                trans.rotation = trans
-                    .rotation
-                    .slerp(Quat::from_rotation_y(rover.heading), step);
-            } else {
-                trans.rotation = Quat::from_rotation_y(rover.heading);
-                action_execution.action_states[robot_num].is_turning = false;
-            }
-
-            continue;
-        }
+    // past the acting phase, everyone is where the turn took them
+    let elapsed = match action_execution.phase {
+        TurnPhase::Acting => (action_execution.phase_time + time.overstep()).as_secs_f32(),
+        TurnPhase::BetweenTurns | TurnPhase::Evaluating => f32::INFINITY,
+    };
+    let turning_time = action_execution.turning_time;
+    let turn_progress = if turning_time > 0.0 {
+        (elapsed / turning_time).clamp(0.0, 1.0)
+    } else {
+        1.0
+    };
+    let move_progress = ((elapsed - turning_time) / (TILE_SIZE / SPEED)).clamp(0.0, 1.0);
 
-        if any_rover_turning {
+    for (rover, mut trans) in rover_query.iter_mut() {
+        let Some(state) = action_execution
+            .action_states
+            .get(rover.identifier as usize)
+        else {
             continue;
-        }
-
-        let logical_pos = rover.logical_position;
-
-        let translation = &mut trans.translation;
+        };
 
-        let end_x =
-            (logical_pos.x as f32 * TILE_SIZE - effective_level_width / 2.0) + TILE_SIZE / 2.0;
-        // mirror along the z to align correctly with how it looks in the level
-        let end_z =
-            (-logical_pos.y as f32 * TILE_SIZE + effective_level_height / 2.0) + TILE_SIZE / 2.0;
-        let target = Vec3::new(end_x, translation.y, end_z);
+        trans.rotation = Quat::from_rotation_y(state.turn_start_heading)
+            .slerp(Quat::from_rotation_y(rover.heading), turn_progress);
 
-        let diff = target - *translation;
+        let start = tile_translation(level, state.turn_start_position, trans.translation.y);
+        let end = tile_translation(level, rover.logical_position, trans.translation.y);
+        trans.translation = start.lerp(end, move_progress);
+    }
+}
 
-        // Movement logic
-        let distance = diff.length();
-        let step = SPEED * time.delta_secs();
+/// Centre of a tile in the world, at height `y`.
+#[cfg(feature = "presentation")]
+fn tile_translation(level: &GRADVM, position: I8Vec2, y: f32) -> Vec3 {
+    let effective_level_width = level.LATIVIDO as f32 * TILE_SIZE;
+    let effective_level_height = level.ALTIVIDO as f32 * TILE_SIZE;
 
-        if distance > 0.01 {
-            let dir = diff.normalize();
-            let new_pos = *translation + dir * step.min(distance);
-            trans.translation = new_pos;
-        } else {
-            trans.translation = target;
-            if action_execution.action_states[robot_num].active_action_idx
-                < action_execution.action_states[robot_num].action_list.len()
-            {
-                action_execution.action_states[robot_num].active_action_idx += 1;
-            }
-            rover.is_acting = false;
-            rover.is_turn_done = true;
-            println!("End of moving for rover {}", rover.identifier);
-            println!(
-                "New Action Idx {}",
-                action_execution.action_states[robot_num].active_action_idx
-            );
-        }
-    }
+    let x = (position.x as f32 * TILE_SIZE - effective_level_width / 2.0) + TILE_SIZE / 2.0;
+    // mirror along the z to align correctly with how it looks in the level
+    let z = (-position.y as f32 * TILE_SIZE + effective_level_height / 2.0) + TILE_SIZE / 2.0;
+    Vec3::new(x, y, z)
 }
 
 fn continue_execution(
@@ -435,20 +520,19 @@ fn continue_execution(
     mut rover_query: Query<&mut RoverEntity>,
     active_level: Res<ActiveLevel>,
     levels: Res<Assets<GRADVM>>,
-    time: Res<Time>,
 ) {
     for event in events.read() {
         match event {
             PuzzleResponseEvent::Solved => {
                 events.clear();
                 action_execution.is_built = false;
-                action_execution.is_evaluating = false;
+                action_execution.phase = TurnPhase::default();
                 break;
             }
             PuzzleResponseEvent::Failed => {
                 events.clear();
                 action_execution.is_built = false;
-                action_execution.is_evaluating = false;
+                action_execution.phase = TurnPhase::default();
                 break;
             }
             PuzzleResponseEvent::InProgress => {
@@ -464,36 +548,14 @@ fn continue_execution(
                     &active_level,
                     &levels,
                     &mut action_execution,
-                    &time,
                     &mut rover_query,
                 );
-
-                action_execution.is_evaluating = false;
-            }
-        }
-    }
-}
-
-fn update_rover_collectables(
-    mut commands: Commands,
-    collectable_queries: Query<(Entity, &Transform, &RoverCollectable)>,
-    rovers: Query<(&Transform, &RoverEntity), Without<RoverCollectable>>,
-) {
-    for (rover_transform, rover) in rovers.iter() {
-        for (collectable_entity, collectable_transform, rover_collectable) in
-            collectable_queries.iter()
-        {
-            if rover_transform
-                .translation
-                .distance(collectable_transform.translation)
-                < 1.0
-            {
-                commands.entity(collectable_entity).despawn();
             }
         }
     }
 }
 
+#[cfg(feature = "presentation")]
 fn update_rover_sounds(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
@@ -0,0 +1,135 @@
+use crate::score::LevelScore;
+use crate::settings::Settings;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+/// Key of the save in the browser's local storage.
+#[cfg(target_arch = "wasm32")]
+const SAVE_KEY: &str = "mission_ares_save";
+
+pub struct SavePlugin;
+
+/// File the save is read from and written to, in the data directory of the player unless it's
+/// inserted before the `SavePlugin` is added. The web build keeps the save in the local storage.
+#[derive(Resource, Clone, Debug)]
+pub struct SavePath(pub PathBuf);
+
+impl Default for SavePath {
+    fn default() -> Self {
+        SavePath(default_save_path())
+    }
+}
+
+/// Everything the game remembers between sessions. Fields missing from an older save are left
+/// at their default.
+#[derive(Resource, Serialize, Deserialize, Default, Clone, Debug)]
+#[serde(default)]
+pub struct SaveData {
+    /// Daily challenge results, by date (YYYY-MM-DD)
+    pub daily: BTreeMap<String, DailyRecord>,
+    /// Best score of each campaign level, by level number
+    pub best_scores: BTreeMap<String, LevelScore>,
+    /// Help topics already shown, by identifier
+    pub seen_help: BTreeSet<String>,
+    pub settings: Settings,
+}
+
+#[derive(Serialize, Deserialize, Default, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(default)]
+pub struct DailyRecord {
+    /// Programs executed on the challenge that day, solved or not
+    pub attempts: u32,
+    /// Fewest commands of a program that solved the challenge
+    pub best_commands: Option<u32>,
+    /// Attempts it took to solve the challenge the first time
+    pub solved_after: Option<u32>,
+}
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        let save_path = app
+            .world_mut()
+            .get_resource_or_insert_with(SavePath::default)
+            .clone();
+        app.insert_resource(load_save_data(&save_path.0));
+        app.add_systems(Last, write_save_data);
+    }
+}
+
+fn load_save_data(path: &Path) -> SaveData {
+    let Some(contents) = read_save(path) else {
+        return SaveData::default();
+    };
+
+    ron::from_str(&contents).unwrap_or_else(|error| {
+        log::warn!("Ignoring unreadable save: {}", error);
+        SaveData::default()
+    })
+}
+
+fn write_save_data(save_data: Res<SaveData>, save_path: Res<SavePath>) {
+    if !save_data.is_changed() || save_data.is_added() {
+        return;
+    }
+
+    match ron::ser::to_string_pretty(&*save_data, ron::ser::PrettyConfig::default()) {
+        Ok(contents) => write_save(&save_path.0, &contents),
+        Err(error) => log::error!("Could not serialize the save: {}", error),
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn default_save_path() -> PathBuf {
+    let data_directory = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("APPDATA").map(PathBuf::from))
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+        .unwrap_or_default();
+
+    data_directory.join("mission_ares").join("save.ron")
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read_save(path: &Path) -> Option<String> {
+    std::fs::read_to_string(path).ok()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write_save(path: &Path, contents: &str) {
+    if let Some(directory) = path.parent() {
+        let _ = std::fs::create_dir_all(directory);
+    }
+
+    if let Err(error) = std::fs::write(path, contents) {
+        log::error!("Could not write the save to {}: {}", path.display(), error);
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn default_save_path() -> PathBuf {
+    PathBuf::new()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+#[cfg(target_arch = "wasm32")]
+fn read_save(_path: &Path) -> Option<String> {
+    local_storage()?.get_item(SAVE_KEY).ok()?
+}
+
+#[cfg(target_arch = "wasm32")]
+fn write_save(_path: &Path, contents: &str) {
+    let Some(storage) = local_storage() else {
+        log::error!("No local storage to write the save to");
+        return;
+    };
+
+    if storage.set_item(SAVE_KEY, contents).is_err() {
+        log::error!("Could not write the save to the local storage");
+    }
+}
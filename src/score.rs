@@ -0,0 +1,178 @@
+use crate::game_mode::GameMode;
+use crate::hint::HintState;
+use crate::level::{GRADVM, PAR};
+use crate::gameplay::ActiveLevel;
+use crate::puzzle_evaluation::PuzzleResponseEvent;
+use crate::rover::ActionListExecute;
+use crate::save::SaveData;
+use crate::simulation::{TurnOutcome, simulate};
+use crate::solver::Solution;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+
+pub const MAX_STARS: u8 = 3;
+
+pub struct ScorePlugin;
+
+/// How efficiently a level was solved.
+#[derive(Serialize, Deserialize, Default, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(default)]
+pub struct LevelScore {
+    pub commands: u32,
+    pub turns: u32,
+    pub battery_used: u32,
+    /// Hints asked for before solving the level
+    pub hints: u32,
+}
+
+/// What the program being executed has cost so far, counted by the puzzle evaluation.
+#[derive(Resource, Default, Clone, Copy, Debug)]
+pub struct ExecutionStats {
+    pub commands: u32,
+    pub turns: u32,
+    pub battery_used: u32,
+}
+
+/// Score of the last solved level, along with its stars when the level has a par.
+#[derive(Resource, Default, Clone, Copy, Debug)]
+pub struct LastScore {
+    pub score: Option<LevelScore>,
+    pub par: Option<PAR>,
+    pub stars: u8,
+    /// Whether the score beat the best one saved for the level
+    pub is_new_best: bool,
+}
+
+impl Plugin for ScorePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LastScore>();
+        app.add_systems(Update, (reset_execution_stats, record_score).chain());
+    }
+}
+
+impl LevelScore {
+    /// One star for solving the level, one for matching the command par and one for matching
+    /// both the turn and battery par. Solving with hints never earns every star.
+    pub fn stars(&self, par: &PAR) -> u8 {
+        let mut stars = 1;
+        if self.commands <= par.MANDATA {
+            stars += 1;
+        }
+        if self.turns <= par.VICES && self.battery_used <= par.PILA {
+            stars += 1;
+        }
+        if self.hints > 0 {
+            stars = stars.min(MAX_STARS - 1);
+        }
+        stars
+    }
+
+    /// Whether this score is worth keeping over `other`: more stars first, then fewer hints,
+    /// commands, turns and battery, in that order.
+    pub fn is_better_than(&self, other: &LevelScore, par: Option<&PAR>) -> bool {
+        let rank = |score: &LevelScore| {
+            (
+                Reverse(par.map_or(0, |par| score.stars(par))),
+                score.hints,
+                score.commands,
+                score.turns,
+                score.battery_used,
+            )
+        };
+        rank(self) < rank(other)
+    }
+}
+
+/// Par of a level from what the solver finds: the turns and battery of its fastest solution and
+/// the commands of the one with the fewest. `None` if either doesn't solve the level.
+pub fn solution_par(level: &GRADVM, fastest: &Solution, fewest_commands: &Solution) -> Option<PAR> {
+    let (outcome, state) = simulate(level, &fastest.actions);
+    if outcome != TurnOutcome::Solved || simulate(level, &fewest_commands.actions).0 != outcome {
+        return None;
+    }
+
+    Some(PAR {
+        MANDATA: fewest_commands
+            .actions
+            .iter()
+            .map(|actions| actions.len() as u32)
+            .sum(),
+        VICES: state.turn as u32,
+        PILA: state.battery_used,
+    })
+}
+
+/// Key of a level in the save, only the campaign levels are always the same.
+fn save_key(game_mode: GameMode, level: &GRADVM) -> Option<String> {
+    match game_mode {
+        GameMode::Campaign => Some((level.INDEX + 1).to_string()),
+        GameMode::Endless | GameMode::Daily => None,
+    }
+}
+
+fn reset_execution_stats(
+    mut events: EventReader<ActionListExecute>,
+    mut execution_stats: ResMut<ExecutionStats>,
+) {
+    for event in events.read() {
+        *execution_stats = ExecutionStats {
+            commands: event
+                .action_list
+                .iter()
+                .map(|actions| actions.len() as u32)
+                .sum(),
+            ..default()
+        };
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn record_score(
+    mut events: EventReader<PuzzleResponseEvent>,
+    execution_stats: Res<ExecutionStats>,
+    mut last_score: ResMut<LastScore>,
+    mut save_data: ResMut<SaveData>,
+    game_mode: Res<GameMode>,
+    active_level: Res<ActiveLevel>,
+    levels: Res<Assets<GRADVM>>,
+    hint_state: Option<Res<HintState>>,
+) {
+    for event in events.read() {
+        if *event != PuzzleResponseEvent::Solved {
+            continue;
+        }
+
+        let Some(level) = active_level
+            .0
+            .as_ref()
+            .and_then(|handle| levels.get(handle))
+        else {
+            continue;
+        };
+
+        let score = LevelScore {
+            commands: execution_stats.commands,
+            turns: execution_stats.turns,
+            battery_used: execution_stats.battery_used,
+            hints: hint_state.as_ref().map_or(0, |hint_state| hint_state.used),
+        };
+        let par = level.PAR;
+
+        let mut is_new_best = false;
+        if let Some(key) = save_key(*game_mode, level) {
+            let best = save_data.best_scores.get(&key);
+            if best.is_none_or(|best| score.is_better_than(best, par.as_ref())) {
+                save_data.best_scores.insert(key, score);
+                is_new_best = true;
+            }
+        }
+
+        *last_score = LastScore {
+            score: Some(score),
+            par,
+            stars: par.map_or(1, |par| score.stars(&par)),
+            is_new_best,
+        };
+    }
+}
@@ -0,0 +1,98 @@
+use crate::game_control::keymap::Keymap;
+use crate::locale::Language;
+use crate::palette::Palette;
+use crate::save::SaveData;
+use bevy::prelude::Res;
+use serde::{Deserialize, Serialize};
+
+/// Preferences of the player, saved along with their progress. Fields missing from an older save
+/// are left at their default.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct Settings {
+    pub keymap: Keymap,
+    pub language: Language,
+    /// Colours of the units
+    pub palette: Palette,
+    /// Volumes from 0 to 1, the music and the sound effects are scaled by the master volume
+    pub master_volume: f32,
+    pub music_volume: f32,
+    pub sfx_volume: f32,
+    pub graphics: GraphicsQuality,
+    /// Size of the UI relative to its default size
+    pub ui_scale: f32,
+    /// Leaves out the particles and the camera easing between views
+    pub reduced_motion: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            keymap: Keymap::default(),
+            language: Language::default(),
+            palette: Palette::default(),
+            master_volume: 1.0,
+            music_volume: 1.0,
+            sfx_volume: 1.0,
+            graphics: GraphicsQuality::default(),
+            ui_scale: 1.0,
+            reduced_motion: false,
+        }
+    }
+}
+
+pub const MIN_UI_SCALE: f32 = 0.75;
+pub const MAX_UI_SCALE: f32 = 1.5;
+
+/// Preset of the costly rendering effects: ambient occlusion, bloom, temporal anti-aliasing and
+/// the resolution of the shadows.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GraphicsQuality {
+    Low,
+    Medium,
+    High,
+    #[default]
+    Ultra,
+}
+
+impl GraphicsQuality {
+    pub const ALL: [GraphicsQuality; 4] = [
+        GraphicsQuality::Low,
+        GraphicsQuality::Medium,
+        GraphicsQuality::High,
+        GraphicsQuality::Ultra,
+    ];
+
+    /// Key of the name in the string tables
+    pub fn key(&self) -> &'static str {
+        match self {
+            GraphicsQuality::Low => "graphics.low",
+            GraphicsQuality::Medium => "graphics.medium",
+            GraphicsQuality::High => "graphics.high",
+            GraphicsQuality::Ultra => "graphics.ultra",
+        }
+    }
+
+    /// Size of the shadow map, in texels on each side
+    pub fn shadow_map_size(&self) -> usize {
+        match self {
+            GraphicsQuality::Low => 1024,
+            GraphicsQuality::Medium => 2048,
+            GraphicsQuality::High | GraphicsQuality::Ultra => 4096,
+        }
+    }
+
+    pub fn bloom(&self) -> bool {
+        *self != GraphicsQuality::Low
+    }
+
+    /// Only available natively
+    pub fn temporal_anti_aliasing(&self) -> bool {
+        matches!(self, GraphicsQuality::High | GraphicsQuality::Ultra)
+    }
+}
+
+/// Run condition of the systems moving things only for show, which reduced motion leaves out.
+pub fn full_motion(save_data: Res<SaveData>) -> bool {
+    !save_data.settings.reduced_motion
+}
@@ -0,0 +1,407 @@
+use crate::battery::{BatteryRules, TurnActivity};
+use crate::game_control::actions::ActionType;
+use crate::level::GRADVM;
+use crate::objective::{ObjectiveProgress, can_collect, is_complete, is_delivery_tile};
+use crate::rover::predict_pose;
+use crate::unit::{UnitKind, scouted_minerals};
+use crate::wire_network::{WireParticipant, exchange_power, wire_networks};
+use bevy::math::I8Vec2;
+use std::f32::consts::PI;
+
+/// Heading every unit starts a level with, facing up.
+pub const START_HEADING: f32 = -PI / 2.0;
+
+/// A unit as far as the rules of a level are concerned, without anything about how it looks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnitState {
+    pub kind: UnitKind,
+    pub position: I8Vec2,
+    pub heading: f32,
+    pub battery_level: u8,
+    pub battery_rules: BatteryRules,
+    pub start_position: I8Vec2,
+    /// Indices of the minerals currently carried
+    pub cargo: Vec<usize>,
+    pub battery_pack: u8,
+}
+
+impl UnitState {
+    /// Why this unit can't perform `action`, regardless of what the other units do.
+    pub fn failure(&self, level: &GRADVM, action: ActionType) -> Option<FailureReason> {
+        let (new_position, _) = predict_pose(self.position, self.heading, action);
+
+        let has_enough_battery = if action.is_turn() {
+            self.battery_rules.can_turn(self.battery_level)
+        } else {
+            action.is_stationary() || self.battery_rules.can_move(self.battery_level)
+        };
+
+        if !has_enough_battery {
+            Some(FailureReason::OutOfBattery)
+        } else if !self.kind.can_enter(level, &new_position) {
+            Some(FailureReason::BlockedMove)
+        } else {
+            None
+        }
+    }
+}
+
+/// Why a program stopped before solving the level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureReason {
+    /// Two units of the same kind tried to end up on the same tile, or to swap tiles
+    Collision,
+    /// A unit tried to leave the level or drive off the ground
+    BlockedMove,
+    /// A unit didn't have enough charge left for its command
+    OutOfBattery,
+    /// Every unit ran out of commands before the objective was met
+    OutOfCommands,
+}
+
+impl FailureReason {
+    pub fn description(&self) -> &'static str {
+        match self {
+            FailureReason::Collision => "two units collided",
+            FailureReason::BlockedMove => "a unit was blocked",
+            FailureReason::OutOfBattery => "a unit ran out of battery",
+            FailureReason::OutOfCommands => "every unit ran out of commands",
+        }
+    }
+
+    /// Key of the description in the string tables
+    pub fn key(&self) -> &'static str {
+        match self {
+            FailureReason::Collision => "failure.collision",
+            FailureReason::BlockedMove => "failure.blocked_move",
+            FailureReason::OutOfBattery => "failure.out_of_battery",
+            FailureReason::OutOfCommands => "failure.out_of_commands",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TurnOutcome {
+    Solved,
+    Failed(FailureReason),
+    InProgress,
+}
+
+/// The rules of the game without any entity, timer or animation: the same turn the rovers play
+/// out on screen, resolved at once. Used wherever a program has to be checked without running
+/// the game, by the solver for instance.
+#[derive(Debug, Clone)]
+pub struct SimulationState {
+    pub units: Vec<UnitState>,
+    pub progress: ObjectiveProgress,
+    /// Number of turns played so far
+    pub turn: usize,
+    /// Charge spent by every unit on their commands, sunlight and wires left aside
+    pub battery_used: u32,
+}
+
+impl SimulationState {
+    pub fn new(level: &GRADVM) -> Self {
+        let units = level
+            .VEHICVLA
+            .iter()
+            .enumerate()
+            .map(|(identifier, (x, y))| {
+                let kind = level.GENVS(identifier as u8);
+                let battery_rules = level.REGVLAE_PILAE(identifier as u8);
+                UnitState {
+                    kind,
+                    position: I8Vec2::new(*x, *y),
+                    heading: START_HEADING,
+                    battery_level: battery_rules.starting_charge,
+                    battery_rules,
+                    start_position: I8Vec2::new(*x, *y),
+                    cargo: vec![],
+                    battery_pack: if kind == UnitKind::Helicopter {
+                        level.SARCINA
+                    } else {
+                        0
+                    },
+                }
+            })
+            .collect();
+
+        Self {
+            units,
+            progress: ObjectiveProgress::default(),
+            turn: 0,
+            battery_used: 0,
+        }
+    }
+
+    /// Plays one turn, `actions` holding the command of each unit or `None` for units that are
+    /// out of commands.
+    pub fn step(&mut self, level: &GRADVM, actions: &[Option<ActionType>]) -> TurnOutcome {
+        self.turn += 1;
+
+        let failures = move_units(level, &mut self.units, actions);
+        let turn_end = finish_turn(
+            level,
+            &mut self.units,
+            actions,
+            &failures,
+            &mut self.progress,
+        );
+        self.battery_used += turn_end.battery_used;
+
+        turn_outcome(level, &self.progress, &failures)
+    }
+}
+
+/// What the end of a turn did besides changing the units and the objective progress.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TurnEnd {
+    /// Charge spent by the units on their commands, sunlight and wires left aside
+    pub battery_used: u32,
+    /// Minerals picked up during the turn
+    pub collected: Vec<usize>,
+}
+
+/// First half of a turn: every unit with a command moves, `units` and `actions` being indexed
+/// by unit identifier. A unit that can't perform its command stays on its tile (facing where it
+/// was headed) and gets the reason back. Units out of commands are parked and, like in the
+/// game, don't get in anyone's way.
+pub fn move_units(
+    level: &GRADVM,
+    units: &mut [UnitState],
+    actions: &[Option<ActionType>],
+) -> Vec<Option<FailureReason>> {
+    let mut failures = vec![None; units.len()];
+    let mut positions: Vec<(UnitKind, I8Vec2)> = vec![];
+    let mut moves: Vec<(UnitKind, (I8Vec2, I8Vec2))> = vec![];
+
+    for (identifier, unit) in units.iter_mut().enumerate() {
+        let Some(action) = actions.get(identifier).cloned().flatten() else {
+            continue;
+        };
+
+        let previous_position = unit.position;
+        let (new_position, new_heading) = predict_pose(unit.position, unit.heading, action);
+
+        // only units of the same kind can run into each other
+        let reason = if let Some(reason) = unit.failure(level, action) {
+            Some(reason)
+        } else if positions
+            .iter()
+            .any(|(kind, pos)| unit.kind.collides_with(*kind) && *pos == new_position)
+            || moves.iter().any(|(kind, swap)| {
+                unit.kind.collides_with(*kind) && *swap == (new_position, previous_position)
+            })
+        {
+            Some(FailureReason::Collision)
+        } else {
+            None
+        };
+
+        unit.heading = new_heading;
+        match reason {
+            Some(reason) => failures[identifier] = Some(reason),
+            None => unit.position = new_position,
+        }
+
+        positions.push((unit.kind, unit.position));
+        moves.push((unit.kind, (previous_position, unit.position)));
+    }
+
+    failures
+}
+
+/// Second half of a turn, once the units are done moving: power flows through the wires,
+/// batteries pay for the commands and soak up sunlight, helicopters scout and hand over their
+/// packs, then rovers deliver and pick up minerals. `failures` is what [`move_units`] returned,
+/// units that failed don't get to do anything on their tile.
+pub fn finish_turn(
+    level: &GRADVM,
+    units: &mut [UnitState],
+    actions: &[Option<ActionType>],
+    failures: &[Option<FailureReason>],
+    progress: &mut ObjectiveProgress,
+) -> TurnEnd {
+    let mut turn_end = TurnEnd::default();
+    let failed = |identifier: usize| failures.get(identifier).copied().flatten().is_some();
+
+    share_wire_power(level, units);
+
+    // stationary commands take effect once every battery is settled
+    let mut performed_commands = vec![];
+    for (identifier, unit) in units.iter_mut().enumerate() {
+        let action = actions.get(identifier).cloned().flatten();
+        let activity = match action {
+            None => TurnActivity::Idle,
+            Some(action) if action.is_turn() => TurnActivity::Turned,
+            Some(action) if action.is_stationary() => TurnActivity::Waited,
+            Some(_) => TurnActivity::Moved,
+        };
+
+        if let Some(action) = action
+            && activity == TurnActivity::Waited
+            && !failed(identifier)
+        {
+            performed_commands.push((identifier, action));
+        }
+
+        turn_end.battery_used += unit
+            .battery_rules
+            .charge_spent(unit.battery_level, activity) as u32;
+        let is_charging = unit.kind.is_charging(level, &unit.position, activity);
+        unit.battery_level =
+            unit.battery_rules
+                .apply_turn(unit.battery_level, activity, !is_charging);
+    }
+
+    for (identifier, action) in performed_commands {
+        perform(level, units, identifier, action, progress);
+    }
+
+    for (identifier, unit) in units.iter_mut().enumerate() {
+        if failed(identifier) || !unit.kind.can_carry_minerals() {
+            continue;
+        }
+
+        if !unit.cargo.is_empty() && is_delivery_tile(level, unit.position, unit.start_position) {
+            progress.delivered.append(&mut unit.cargo);
+        }
+
+        for (mineral, (x, y)) in level.CRYSTALLA.iter().enumerate() {
+            if unit.position != I8Vec2::new(*x, *y)
+                || !can_collect(level, progress, identifier as u8, &unit.cargo, mineral)
+            {
+                continue;
+            }
+
+            progress.collected.push(mineral);
+            turn_end.collected.push(mineral);
+            // minerals only need to be carried around if they have somewhere to go
+            if level.OBIECTVM.DESTINATIO.is_some() {
+                unit.cargo.push(mineral);
+            }
+        }
+    }
+
+    turn_end
+}
+
+/// How the level stands once a turn is finished. Running out of commands is left to the
+/// caller, which knows how long the programs are.
+pub fn turn_outcome(
+    level: &GRADVM,
+    progress: &ObjectiveProgress,
+    failures: &[Option<FailureReason>],
+) -> TurnOutcome {
+    if is_complete(level, progress) {
+        return TurnOutcome::Solved;
+    }
+
+    match failures.iter().flatten().next() {
+        Some(reason) => TurnOutcome::Failed(*reason),
+        None => TurnOutcome::InProgress,
+    }
+}
+
+fn share_wire_power(level: &GRADVM, units: &mut [UnitState]) {
+    if level.NEXVS.is_empty() {
+        return;
+    }
+
+    // helicopters never plug into the wires
+    let mut participants: Vec<WireParticipant> = units
+        .iter()
+        .enumerate()
+        .filter(|(_, unit)| unit.kind == UnitKind::Rover)
+        .map(|(identifier, unit)| WireParticipant {
+            identifier: identifier as u8,
+            position: (unit.position.x, unit.position.y),
+            in_shadow: level
+                .TEGLVAE
+                .get(&(unit.position.x, unit.position.y))
+                .is_some_and(|tile| tile.VMBRA),
+            battery_level: unit.battery_level,
+            capacity: unit.battery_rules.capacity,
+        })
+        .collect();
+
+    exchange_power(&wire_networks(&level.NEXVS), &mut participants);
+
+    for participant in participants {
+        units[participant.identifier as usize].battery_level = participant.battery_level;
+    }
+}
+
+fn perform(
+    level: &GRADVM,
+    units: &mut [UnitState],
+    identifier: usize,
+    action: ActionType,
+    progress: &mut ObjectiveProgress,
+) {
+    let position = units[identifier].position;
+
+    match action {
+        ActionType::Scout => {
+            for mineral in scouted_minerals(level, position) {
+                if !progress.is_revealed(mineral) {
+                    progress.revealed.push(mineral);
+                }
+            }
+        }
+        ActionType::DropPack => {
+            let battery_pack = units[identifier].battery_pack;
+            if battery_pack == 0 {
+                return;
+            }
+
+            let Some(rover) = units
+                .iter_mut()
+                .find(|unit| unit.kind == UnitKind::Rover && unit.position == position)
+            else {
+                return;
+            };
+            rover.battery_level = rover
+                .battery_level
+                .saturating_add(battery_pack)
+                .min(rover.battery_rules.capacity);
+            units[identifier].battery_pack = 0;
+        }
+        _ => {}
+    }
+}
+
+/// Runs a whole program, one command list per unit, the way the game would once the player
+/// presses execute.
+pub fn simulate(level: &GRADVM, programs: &[Vec<ActionType>]) -> (TurnOutcome, SimulationState) {
+    let mut state = SimulationState::new(level);
+    let length = programs
+        .iter()
+        .map(|actions| actions.len())
+        .max()
+        .unwrap_or(0);
+
+    if length == 0 {
+        return (TurnOutcome::Failed(FailureReason::OutOfCommands), state);
+    }
+
+    loop {
+        let turn = state.turn;
+        let actions: Vec<Option<ActionType>> = (0..state.units.len())
+            .map(|unit| {
+                programs
+                    .get(unit)
+                    .and_then(|actions| actions.get(turn))
+                    .cloned()
+            })
+            .collect();
+
+        match state.step(level, &actions) {
+            TurnOutcome::InProgress if state.turn >= length => {
+                return (TurnOutcome::Failed(FailureReason::OutOfCommands), state);
+            }
+            TurnOutcome::InProgress => {}
+            outcome => return (outcome, state),
+        }
+    }
+}
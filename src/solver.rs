@@ -0,0 +1,360 @@
+use crate::game_control::actions::ActionType;
+use crate::level::GRADVM;
+use crate::simulation::{SimulationState, TurnOutcome, simulate};
+use bevy::platform::collections::{HashMap, HashSet};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+use std::f32::consts::PI;
+
+/// How far the solver is allowed to look before giving up.
+#[derive(Debug, Clone, Copy)]
+pub struct SolverLimits {
+    pub max_turns: usize,
+    pub max_states: usize,
+}
+
+impl Default for SolverLimits {
+    fn default() -> Self {
+        Self {
+            max_turns: 24,
+            max_states: 200_000,
+        }
+    }
+}
+
+/// The shortest program found for a level.
+#[derive(Debug, Clone)]
+pub struct Solution {
+    /// Command list of each unit, without the waits left at the end
+    pub actions: Vec<Vec<ActionType>>,
+    /// Turns the program takes to solve the level
+    pub turns: usize,
+}
+
+/// Why the solver came back without a solution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolverFailure {
+    /// Every program was tried, none of them solves the level
+    NoSolution,
+    /// The limits were reached before finding a solution, there may still be one
+    LimitsReached,
+}
+
+/// Finds a program solving `level` in as few turns as possible by trying every combination of
+/// commands, turn after turn. Returns `None` if there is none within the limits.
+pub fn solve(level: &GRADVM, limits: SolverLimits) -> Option<Solution> {
+    solve_with_prefix(level, &[], limits).ok()
+}
+
+/// Same as [`solve`], for programs starting with the commands of `prefix` (one list per unit,
+/// missing units have none), telling apart the levels proven unsolvable from the ones the
+/// limits cut short.
+pub fn solve_with_prefix(
+    level: &GRADVM,
+    prefix: &[Vec<ActionType>],
+    limits: SolverLimits,
+) -> Result<Solution, SolverFailure> {
+    let prefix_turns = prefix
+        .iter()
+        .map(|actions| actions.len())
+        .max()
+        .unwrap_or(0);
+    let start = SimulationState::new(level);
+    // waiting comes first so that, among the shortest programs, units with nothing to do idle
+    // instead of wandering about
+    let unit_actions: Vec<Vec<ActionType>> = start
+        .units
+        .iter()
+        .map(|unit| {
+            let mut actions = unit.kind.actions(level.IMPERIA);
+            actions.sort_by_key(|action| *action != ActionType::Wait);
+            actions
+        })
+        .collect();
+
+    // every state reached, with the state it came from and the commands that led to it
+    let mut history: Vec<(usize, Vec<ActionType>)> = vec![(0, vec![])];
+    let mut visited = HashSet::new();
+    visited.insert(visited_key(&start, prefix_turns));
+
+    let mut queue = VecDeque::from([(0, start)]);
+    let mut failure = SolverFailure::NoSolution;
+    while let Some((index, state)) = queue.pop_front() {
+        if state.turn >= limits.max_turns {
+            failure = SolverFailure::LimitsReached;
+            continue;
+        }
+
+        // commands that would fail whatever the other units do aren't worth combining
+        let choices: Vec<Vec<ActionType>> = state
+            .units
+            .iter()
+            .zip(unit_actions.iter())
+            .enumerate()
+            .map(|(unit_index, (unit, actions))| {
+                if let Some(action) = prefix
+                    .get(unit_index)
+                    .and_then(|actions| actions.get(state.turn))
+                {
+                    return vec![*action];
+                }
+
+                actions
+                    .iter()
+                    .cloned()
+                    .filter(|action| unit.failure(level, *action).is_none())
+                    .collect()
+            })
+            .collect();
+
+        for actions in combinations(&choices) {
+            let mut next = state.clone();
+            let commands: Vec<Option<ActionType>> = actions.iter().cloned().map(Some).collect();
+
+            match next.step(level, &commands) {
+                TurnOutcome::Failed(_) => continue,
+                TurnOutcome::Solved => {
+                    history.push((index, actions));
+                    return Ok(solution(level, &history, history.len() - 1));
+                }
+                TurnOutcome::InProgress => {}
+            }
+
+            if !visited.insert(visited_key(&next, prefix_turns)) {
+                continue;
+            }
+            if visited.len() > limits.max_states {
+                return Err(SolverFailure::LimitsReached);
+            }
+
+            history.push((index, actions));
+            queue.push_back((history.len() - 1, next));
+        }
+    }
+
+    Err(failure)
+}
+
+/// Finds a program solving `level` with as few commands as possible, however many turns it
+/// takes, starting from the `fastest` one [`solve`] found. A unit out of commands doesn't pay
+/// for the turns left, so this is searched turn by turn like [`solve`] but in order of commands
+/// spent, each unit being able to stop for good at any turn. Returns `fastest` back if no
+/// program with fewer commands is found within the limits.
+pub fn solve_fewest_commands(level: &GRADVM, fastest: &Solution, limits: SolverLimits) -> Solution {
+    let most_commands: usize = fastest.actions.iter().map(|actions| actions.len()).sum();
+    let start = SimulationState::new(level);
+    let unit_actions: Vec<Vec<ActionType>> = start
+        .units
+        .iter()
+        .map(|unit| unit.kind.actions(level.IMPERIA))
+        .collect();
+
+    // every state reached, with the state it came from and the commands that led to it, `None`
+    // for the units done with their program
+    let mut history: Vec<(usize, Vec<Option<ActionType>>)> = vec![(0, vec![])];
+    let mut cheapest = HashMap::new();
+    let done = vec![false; start.units.len()];
+    cheapest.insert(done_key(&start, &done), 0);
+
+    // commands spent so far, then turns, so that the first solution out is the cheapest
+    let mut queue = BinaryHeap::from([Reverse((0, 0, 0))]);
+    let mut states = vec![Some((start, done, false))];
+    while let Some(Reverse((cost, _, index))) = queue.pop() {
+        let Some((state, done, solved)) = states[index].take() else {
+            continue;
+        };
+        if solved {
+            return fewest_commands_solution(level, &history, index);
+        }
+        if state.turn >= limits.max_turns {
+            continue;
+        }
+        // reached again for fewer commands since it was queued
+        if cheapest
+            .get(&done_key(&state, &done))
+            .is_some_and(|cheapest| *cheapest < cost)
+        {
+            continue;
+        }
+
+        let choices: Vec<Vec<Option<ActionType>>> = state
+            .units
+            .iter()
+            .zip(unit_actions.iter())
+            .zip(done.iter())
+            .map(|((unit, actions), done)| {
+                if *done {
+                    return vec![None];
+                }
+
+                actions
+                    .iter()
+                    .cloned()
+                    .filter(|action| unit.failure(level, *action).is_none())
+                    .map(Some)
+                    .chain([None])
+                    .collect()
+            })
+            .collect();
+
+        for commands in combinations(&choices) {
+            // the program is over once every unit is
+            if commands.iter().all(Option::is_none) {
+                continue;
+            }
+
+            let mut next = state.clone();
+            let outcome = next.step(level, &commands);
+            if let TurnOutcome::Failed(_) = outcome {
+                continue;
+            }
+
+            let next_done: Vec<bool> = commands.iter().map(Option::is_none).collect();
+            let next_cost = cost + commands.iter().flatten().count();
+            if next_cost >= most_commands {
+                continue;
+            }
+            let key = done_key(&next, &next_done);
+            if cheapest
+                .get(&key)
+                .is_some_and(|cheapest| *cheapest <= next_cost)
+            {
+                continue;
+            }
+            cheapest.insert(key, next_cost);
+            if cheapest.len() > limits.max_states {
+                return fastest.clone();
+            }
+
+            history.push((index, commands));
+            states.push(Some((
+                next.clone(),
+                next_done,
+                outcome == TurnOutcome::Solved,
+            )));
+            queue.push(Reverse((next_cost, next.turn, history.len() - 1)));
+        }
+    }
+
+    fastest.clone()
+}
+
+/// Every way of picking one command per unit.
+fn combinations<T: Copy>(choices: &[Vec<T>]) -> Vec<Vec<T>> {
+    choices
+        .iter()
+        .fold(vec![vec![]], |combinations, unit_choices| {
+            combinations
+                .iter()
+                .flat_map(|combination| {
+                    unit_choices.iter().map(move |action| {
+                        let mut combination = combination.clone();
+                        combination.push(*action);
+                        combination
+                    })
+                })
+                .collect()
+        })
+}
+
+fn solution(level: &GRADVM, history: &[(usize, Vec<ActionType>)], mut index: usize) -> Solution {
+    let mut turns = vec![];
+    while index != 0 {
+        let (parent, actions) = &history[index];
+        turns.push(actions.clone());
+        index = *parent;
+    }
+    turns.reverse();
+
+    let mut actions: Vec<Vec<ActionType>> = (0..level.VEHICVLA.len())
+        .map(|unit| turns.iter().map(|turn| turn[unit]).collect())
+        .collect();
+
+    // a unit out of commands doesn't pay for waiting, trimming only keeps the program if it
+    // still plays out the same way
+    let mut trimmed = actions.clone();
+    for unit_actions in trimmed.iter_mut() {
+        while unit_actions.last() == Some(&ActionType::Wait) {
+            unit_actions.pop();
+        }
+    }
+    let (outcome, state) = simulate(level, &trimmed);
+    if outcome == TurnOutcome::Solved && state.turn == turns.len() {
+        actions = trimmed;
+    }
+
+    Solution {
+        actions,
+        turns: turns.len(),
+    }
+}
+
+fn fewest_commands_solution(
+    level: &GRADVM,
+    history: &[(usize, Vec<Option<ActionType>>)],
+    mut index: usize,
+) -> Solution {
+    let mut turns = vec![];
+    while index != 0 {
+        let (parent, commands) = &history[index];
+        turns.push(commands.clone());
+        index = *parent;
+    }
+    turns.reverse();
+
+    Solution {
+        actions: (0..level.VEHICVLA.len())
+            .map(|unit| turns.iter().filter_map(|turn| turn[unit]).collect())
+            .collect(),
+        turns: turns.len(),
+    }
+}
+
+/// Key of a state for [`solve_fewest_commands`], which also tells apart the units done with
+/// their program.
+fn done_key(state: &SimulationState, done: &[bool]) -> Vec<u8> {
+    let mut key = state_key(state);
+    key.extend(done.iter().map(|done| *done as u8));
+    key
+}
+
+/// Key of a state among the visited ones. While a prefix is playing out, the same state doesn't
+/// lead to the same commands from one turn to the next.
+fn visited_key(state: &SimulationState, prefix_turns: usize) -> Vec<u8> {
+    let mut key = state_key(state);
+    if state.turn < prefix_turns {
+        key.push(state.turn as u8);
+    }
+    key
+}
+
+/// Everything that matters to what can happen next, two states with the same key are the same
+/// puzzle.
+fn state_key(state: &SimulationState) -> Vec<u8> {
+    let mut key = vec![];
+    for unit in state.units.iter() {
+        let mut cargo = unit.cargo.clone();
+        cargo.sort();
+
+        key.push(unit.position.x as u8);
+        key.push(unit.position.y as u8);
+        key.push((unit.heading / (PI / 2.0)).round() as i8 as u8);
+        key.push(unit.battery_level);
+        key.push(unit.battery_pack);
+        key.push(cargo.len() as u8);
+        key.extend(cargo.iter().map(|mineral| *mineral as u8));
+    }
+
+    for minerals in [
+        &state.progress.collected,
+        &state.progress.delivered,
+        &state.progress.revealed,
+    ] {
+        let mut minerals = minerals.clone();
+        minerals.sort();
+        key.push(minerals.len() as u8);
+        key.extend(minerals.iter().map(|mineral| *mineral as u8));
+    }
+
+    key
+}
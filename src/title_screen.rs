@@ -1,5 +1,11 @@
+use crate::daily_challenge::DailyChallenge;
+use crate::gameplay::GameState;
+use crate::game_mode::{EndlessRun, GameMode, LevelGeneration};
 use crate::ui::interactive_button::InteractiveButton;
+use crate::ui::localized_text::LocalizedText;
+use crate::ui::settings_menu::OpenSettings;
 use bevy::prelude::*;
+use rand::random;
 
 pub struct TitleScreenPlugin;
 
@@ -7,15 +13,17 @@ pub struct TitleScreenPlugin;
 pub struct StartGameButton;
 
 #[derive(Component)]
-pub struct TitleScreenUI;
+pub struct EndlessGameButton;
 
-#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, States)]
-pub enum GameState {
-    #[default]
-    TitleScreen,
-    Programming,
-    Execution,
-}
+#[derive(Component)]
+pub struct DailyChallengeButton;
+
+/// Opens the settings dialog.
+#[derive(Component)]
+pub struct SettingsButton;
+
+#[derive(Component)]
+pub struct TitleScreenUI;
 
 impl Plugin for TitleScreenPlugin {
     fn build(&self, app: &mut App) {
@@ -24,7 +32,13 @@ impl Plugin for TitleScreenPlugin {
         app.add_systems(OnExit(GameState::TitleScreen), clean);
         app.add_systems(
             Update,
-            start_game_click_handler.run_if(in_state(GameState::TitleScreen)),
+            (
+                start_game_click_handler,
+                endless_game_click_handler,
+                daily_challenge_click_handler,
+                settings_click_handler,
+            )
+                .run_if(in_state(GameState::TitleScreen)),
         );
     }
 }
@@ -48,35 +62,47 @@ fn on_enter(mut commands: Commands, asset_server: Res<AssetServer>) {
             },
         ))
         .with_children(|parent| {
+            spawn_mode_button(parent, &asset_server, StartGameButton, "title.start", false);
+            spawn_mode_button(
+                parent,
+                &asset_server,
+                EndlessGameButton,
+                "title.endless",
+                true,
+            );
+            spawn_mode_button(
+                parent,
+                &asset_server,
+                DailyChallengeButton,
+                "title.daily",
+                true,
+            );
+
             parent
                 .spawn((
                     Button,
-                    StartGameButton,
+                    SettingsButton,
                     Node {
-                        width: Val::Px(250.0),
-                        height: Val::Px(65.0),
-                        top: Val::Percent(35.0),
-                        // horizontally center child text
-                        justify_content: JustifyContent::Center,
-                        // vertically center child text
-                        align_items: AlignItems::Center,
+                        position_type: PositionType::Absolute,
+                        bottom: Val::Px(15.0),
+                        left: Val::Px(15.0),
+                        padding: UiRect::axes(Val::Px(16.0), Val::Px(8.0)),
                         ..default()
                     },
                     BackgroundColor::from(Color::Srgba(Srgba::hex("3a312e").unwrap())),
-                    BorderRadius::all(Val::Px(15.0)),
-                    BorderColor::from(Color::Srgba(Srgba::hex("3a312e").unwrap())),
+                    BorderRadius::all(Val::Px(10.0)),
                     InteractiveButton::simple(
                         Color::Srgba(Srgba::hex("3a312e").unwrap()),
                         Color::WHITE,
-                        true,
+                        false,
                     ),
                 ))
                 .with_children(|parent| {
                     parent.spawn((
-                        Text::new("Start Game"),
+                        LocalizedText::new("title.settings"),
                         TextFont {
                             font: asset_server.load("fonts/SpaceGrotesk-Light.ttf"),
-                            font_size: 40.0,
+                            font_size: 24.0,
                             ..default()
                         },
                         TextColor(Color::srgba(0.9, 0.9, 0.9, 1.0)),
@@ -84,7 +110,7 @@ fn on_enter(mut commands: Commands, asset_server: Res<AssetServer>) {
                 });
 
             parent.spawn((
-                Text::new("Made in Rust!"),
+                LocalizedText::new("title.made_in_rust"),
                 TextFont {
                     font: asset_server.load("fonts/SpaceGrotesk-Light.ttf"),
                     font_size: 48.0,
@@ -115,6 +141,55 @@ fn on_enter(mut commands: Commands, asset_server: Res<AssetServer>) {
     ));
 }
 
+/// One of the buttons picking a game mode, in a row with a gap before all but the first.
+fn spawn_mode_button(
+    parent: &mut ChildSpawnerCommands,
+    asset_server: &AssetServer,
+    button: impl Component,
+    label_key: &str,
+    has_gap: bool,
+) {
+    parent
+        .spawn((
+            Button,
+            button,
+            Node {
+                width: Val::Px(250.0),
+                height: Val::Px(65.0),
+                top: Val::Percent(35.0),
+                margin: if has_gap {
+                    UiRect::left(Val::Px(30.0))
+                } else {
+                    UiRect::default()
+                },
+                // horizontally center child text
+                justify_content: JustifyContent::Center,
+                // vertically center child text
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor::from(Color::Srgba(Srgba::hex("3a312e").unwrap())),
+            BorderRadius::all(Val::Px(15.0)),
+            BorderColor::from(Color::Srgba(Srgba::hex("3a312e").unwrap())),
+            InteractiveButton::simple(
+                Color::Srgba(Srgba::hex("3a312e").unwrap()),
+                Color::WHITE,
+                true,
+            ),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                LocalizedText::new(label_key),
+                TextFont {
+                    font: asset_server.load("fonts/SpaceGrotesk-Light.ttf"),
+                    font_size: 40.0,
+                    ..default()
+                },
+                TextColor(Color::srgba(0.9, 0.9, 0.9, 1.0)),
+            ));
+        });
+}
+
 fn start_game_click_handler(
     interaction_query: Query<
         &Interaction,
@@ -125,23 +200,100 @@ fn start_game_click_handler(
         ),
     >,
     mut next_state: ResMut<NextState<GameState>>,
+    mut game_mode: ResMut<GameMode>,
     gamepads: Query<&Gamepad>,
+    generations: Query<(), With<LevelGeneration>>,
 ) {
+    // another mode is already getting its level ready
+    if !generations.is_empty() {
+        return;
+    }
+
     for interaction in interaction_query.iter() {
         if *interaction == Interaction::Pressed {
+            *game_mode = GameMode::Campaign;
             next_state.set(GameState::Programming);
         }
     }
 
     for gamepad in &gamepads {
         if gamepad.pressed(GamepadButton::Start) {
+            *game_mode = GameMode::Campaign;
             next_state.set(GameState::Programming);
         }
     }
 }
 
+fn endless_game_click_handler(
+    mut commands: Commands,
+    interaction_query: Query<
+        &Interaction,
+        (
+            Changed<Interaction>,
+            With<EndlessGameButton>,
+            With<InteractiveButton>,
+        ),
+    >,
+    mut game_mode: ResMut<GameMode>,
+    generations: Query<(), With<LevelGeneration>>,
+) {
+    if !generations.is_empty() {
+        return;
+    }
+
+    for interaction in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            *game_mode = GameMode::Endless;
+            // the game starts once the first level is generated
+            let run = EndlessRun::new(random());
+            commands.spawn(run.generate_level(true));
+            commands.insert_resource(run);
+            return;
+        }
+    }
+}
+
+fn daily_challenge_click_handler(
+    mut commands: Commands,
+    interaction_query: Query<
+        &Interaction,
+        (
+            Changed<Interaction>,
+            With<DailyChallengeButton>,
+            With<InteractiveButton>,
+        ),
+    >,
+    mut game_mode: ResMut<GameMode>,
+    generations: Query<(), With<LevelGeneration>>,
+) {
+    if !generations.is_empty() {
+        return;
+    }
+
+    for interaction in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            *game_mode = GameMode::Daily;
+            let challenge = DailyChallenge::today();
+            commands.spawn(challenge.generate_level());
+            commands.insert_resource(challenge);
+            return;
+        }
+    }
+}
+
 fn clean(mut commands: Commands, query: Query<Entity, With<TitleScreenUI>>) {
     for ui_element in query.iter() {
         commands.entity(ui_element).despawn();
     }
 }
+
+fn settings_click_handler(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<SettingsButton>)>,
+    mut open_settings: ResMut<OpenSettings>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            open_settings.0 = true;
+        }
+    }
+}
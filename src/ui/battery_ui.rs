@@ -1,3 +1,4 @@
+use crate::battery::BatteryRules;
 use crate::rover::RoverEntity;
 use bevy::app::{App, Plugin, Startup, Update};
 use bevy::asset::{AssetServer, Handle};
@@ -59,7 +60,7 @@ fn rebuild(
                 ..default()
             },
             ImageNode {
-                image: images.images[3].clone(),
+                image: images.images[images.images.len() - 1].clone(),
                 ..default()
             },
             BatteryUIElement { rover_id: rover },
@@ -81,10 +82,13 @@ fn update(
         for (id, rover, rover_transform) in &rovers {
             if id == ui_elem.rover_id {
                 found = true;
-                img.image = images.images[rover.battery_level as usize].clone();
                 img.image = images
                     .images
-                    .get(rover.battery_level as usize)
+                    .get(battery_image_index(
+                        &rover.battery_rules,
+                        rover.battery_level,
+                        images.images.len(),
+                    ))
                     .unwrap_or(&images.images[0])
                     .clone();
 
@@ -103,3 +107,18 @@ fn update(
         }
     }
 }
+
+/// Picks the battery image matching the charge, whatever the capacity of the battery is.
+/// The first image is reserved for an empty battery and the last one for a full battery.
+fn battery_image_index(rules: &BatteryRules, battery_level: u8, image_count: usize) -> usize {
+    if battery_level == 0 || image_count < 2 {
+        return 0;
+    }
+
+    if battery_level >= rules.capacity || image_count < 3 {
+        return image_count - 1;
+    }
+
+    let levels = (image_count - 1) as f32;
+    ((rules.fill_ratio(battery_level) * levels).round() as usize).clamp(1, image_count - 2)
+}
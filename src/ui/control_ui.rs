@@ -1,18 +1,30 @@
-use crate::game_control::actions::{Action, ActionList, ActionType};
-use crate::help::help::{HelpButton, HelpDialog, show_help_for_empty_actions};
+use crate::game_control::actions::{Action, ActionList, ActionType, ControlScheme};
+use crate::game_control::keymap::{KeyAction, Keymap, gamepad_just_pressed_actions};
+use crate::help::topic::{HelpTrigger, HelpTriggerEvent};
 use crate::level::GRADVM;
-use crate::level_spawner::{ActiveLevel, LevelElement};
-use crate::mesh_loader::DebugLogEntityRequest;
+use crate::gameplay::{ActiveLevel, GameState};
+use crate::locale::Strings;
+use crate::level_spawner::LevelElement;
+use crate::mesh_loader::{DebugLogEntityRequest, SceneColorOverride};
+use crate::palette::{Palette, unit_label};
 use crate::rover::{ActionListExecute, RoverEntity};
-use crate::title_screen::GameState;
+use crate::save::SaveData;
 use crate::ui::Px_dynamic;
+use crate::ui::hint_ui::build_hint_button;
 use crate::ui::interactive_button::InteractiveButton;
+use crate::ui::localized_text::LocalizedText;
+use crate::ui::pause_menu::not_paused;
+use crate::ui::tooltip::Tooltip;
+use crate::ui::touch::{is_portrait, is_tap};
+use crate::unit::UnitKind;
 use bevy::ecs::relationship::RelatedSpawnerCommands;
 use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
 use bevy::pbr::SpotLight;
 use bevy::picking::events::{Click, Pointer};
 use bevy::picking::hover::HoverMap;
+use bevy::picking::pointer::PointerId;
 use bevy::prelude::*;
+use bevy::window::{PrimaryWindow, WindowResized};
 
 pub struct ControlUIPlugin;
 
@@ -28,13 +40,27 @@ pub struct ExecuteButton;
 #[derive(Resource)]
 pub struct RoverColors(pub Vec<Color>);
 
+impl RoverColors {
+    /// Colour of unit `index`, the palette starts over on levels with more units than colours.
+    pub fn color(&self, index: usize) -> Color {
+        self.0[index % self.0.len()]
+    }
+}
+
 #[derive(Resource)]
 pub struct UIRoverColors(pub Vec<Color>);
 
+impl UIRoverColors {
+    /// Colour of unit `index`, the palette starts over on levels with more units than colours.
+    pub fn color(&self, index: usize) -> Color {
+        self.0[index % self.0.len()]
+    }
+}
+
 #[derive(Component)]
 pub struct CommandButton(pub ActionType);
 
-#[derive(Component)]
+#[derive(Component, Clone, Copy)]
 pub struct ActionDeleteButton {
     rover_index: usize,
     action_index: usize,
@@ -59,30 +85,31 @@ impl Plugin for ControlUIPlugin {
                 robot_button_handler.run_if(in_state(GameState::Programming)),
                 delete_action_handler.run_if(in_state(GameState::Programming)),
                 clear_all_handler.run_if(in_state(GameState::Programming)),
+                keyboard_command_handler
+                    .run_if(in_state(GameState::Programming))
+                    .run_if(not_paused),
             ),
         );
-        app.add_systems(Update, execute_handler);
+        app.add_systems(Update, execute_handler.run_if(not_paused));
+        app.add_systems(OnEnter(GameState::TitleScreen), cleanup_control_ui);
         app.add_systems(Update, update_scroll_position);
+        app.add_systems(
+            Update,
+            rebuild_on_orientation_change.run_if(not(in_state(GameState::TitleScreen))),
+        );
         app.add_systems(Update, spawn_selection_light);
         app.add_systems(Update, update_selection_light);
 
-        app.insert_resource(UIRoverColors(vec![
-            Color::srgba(0.9, 0.9, 0.9, 1.0),
-            Color::srgba(0.2, 0.65, 0.2, 1.0),   // green
-            Color::srgba(0.2, 0.4, 0.75, 1.0),   // blue
-            Color::srgba(0.8, 0.75, 0.2, 1.0),   // yellow
-            Color::srgba(0.65, 0.25, 0.65, 1.0), // purple
-            Color::srgba(0.8, 0.2, 0.2, 1.0),    // red
-        ]));
-
-        app.insert_resource(RoverColors(vec![
-            Color::srgba(1.0, 1.0, 1.0, 1.0),
-            Color::srgba(51.0 / 255.0, 166.0 / 255.0, 51.0 / 255.0, 0.35), // green
-            Color::srgba(51.0 / 255.0, 102.0 / 255.0, 191.0 / 255.0, 0.35), // blue
-            Color::srgba(204.0 / 255.0, 191.0 / 255.0, 51.0 / 255.0, 0.35), // yellow
-            Color::srgba(166.0 / 255.0, 64.0 / 255.0, 166.0 / 255.0, 0.35), // purple
-            Color::srgba(204.0 / 255.0, 51.0 / 255.0, 51.0 / 255.0, 0.35), // red
-        ]));
+        // replaced by the palette in the settings on the first frame
+        app.insert_resource(UIRoverColors(Palette::default().ui_colors()));
+        app.insert_resource(RoverColors(Palette::default().model_colors()));
+        app.add_systems(Update, apply_palette.run_if(resource_changed::<SaveData>));
+        app.add_systems(
+            Update,
+            rebuild_on_language_change
+                .run_if(resource_changed::<Strings>)
+                .run_if(not(in_state(GameState::TitleScreen))),
+        );
     }
 }
 
@@ -101,10 +128,14 @@ fn rebuild_control_ui(
     asset_server: Res<AssetServer>,
     active_level: Res<ActiveLevel>,
     levels: Res<Assets<GRADVM>>,
+    save_data: Res<SaveData>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    strings: Res<Strings>,
 ) {
     if action_lists.is_empty() {
         return;
     }
+    let portrait = windows.single().is_ok_and(is_portrait);
 
     let gradum = levels.get(
         &match &active_level.0 {
@@ -118,6 +149,8 @@ fn rebuild_control_ui(
         let number_of_rovers: usize = gradum.unwrap().NVMERVS_VEHICVLORVM_MOBILIVM as usize;
 
         let selected_robot_index = event.current_selection;
+        let selected_kind = gradum.unwrap().GENVS(selected_robot_index as u8);
+        let control_scheme = gradum.unwrap().IMPERIA;
         for ui_element in current_ui_elem_query.iter() {
             if let Ok(_) = commands.get_entity(ui_element) {
                 commands.entity(ui_element).despawn();
@@ -127,23 +160,29 @@ fn rebuild_control_ui(
         let image_robot = asset_server.load("command_icons/robot.png");
 
         commands
-            .spawn((ControlUI, ui_sidebar_container_node()))
+            .spawn((ControlUI, ui_sidebar_container_node(portrait)))
             .with_children(|container_parent| {
                 container_parent
                     .spawn((
-                        ui_sidebar_node(),
+                        ui_sidebar_node(portrait),
                         BackgroundColor(CONTROL_UI_BACKGROUND_COLOR),
                         BorderColor(CONTROL_UI_BORDER_COLOR),
-                        BorderRadius {
-                            top_right: Px_dynamic(8.0),
-                            bottom_right: Px_dynamic(8.0),
-                            ..default()
-                        },
+                        ui_sidebar_border_radius(portrait),
                     ))
                     .with_children(|parent| {
-                        build_control_panel(parent, &asset_server);
-
-                        let rover_colors = &all_rover_colors.0[0..number_of_rovers];
+                        let keymap = &save_data.settings.keymap;
+                        build_control_panel(
+                            parent,
+                            &asset_server,
+                            selected_kind,
+                            control_scheme,
+                            keymap,
+                            &strings,
+                        );
+
+                        let rover_colors: Vec<Color> = (0..number_of_rovers)
+                            .map(|index| all_rover_colors.color(index))
+                            .collect();
 
                         parent
                             .spawn((
@@ -181,6 +220,10 @@ fn rebuild_control_ui(
                                         .spawn((
                                             Button,
                                             ClearAllButton,
+                                            Tooltip(strings.format(
+                                                "tooltip.clear_all",
+                                                &[("keys", &keymap.describe(KeyAction::ClearAll))],
+                                            )),
                                             Node {
                                                 position_type: PositionType::Absolute,
                                                 bottom: Px_dynamic(6.0),
@@ -242,6 +285,9 @@ fn rebuild_control_ui(
                                                         .spawn((
                                                             Button,
                                                             RobotButton(robot_idx as i32),
+                                                            select_unit_tooltip(
+                                                                robot_idx, keymap, &strings,
+                                                            ),
                                                             Node {
                                                                 width: Px_dynamic(50.0),
                                                                 height: Px_dynamic(50.0),
@@ -283,6 +329,13 @@ fn rebuild_control_ui(
                                                                     ..default()
                                                                 },
                                                             ));
+                                                            // the number tells the units apart
+                                                            // without relying on their colour
+                                                            parent.spawn(unit_label_text(
+                                                                robot_idx,
+                                                                18.0,
+                                                                &asset_server,
+                                                            ));
                                                         });
 
                                                     // Small circle indicator
@@ -373,7 +426,8 @@ fn rebuild_control_ui(
                                         }
                                     });
                             });
-                        build_execute_button(parent, &asset_server);
+                        build_hint_button(parent, &asset_server);
+                        build_execute_button(parent, &asset_server, keymap, &strings);
                     });
             });
     }
@@ -382,10 +436,13 @@ fn rebuild_control_ui(
 fn build_control_panel(
     parent: &mut RelatedSpawnerCommands<ChildOf>,
     asset_server: &Res<AssetServer>,
+    unit_kind: UnitKind,
+    control_scheme: ControlScheme,
+    keymap: &Keymap,
+    strings: &Strings,
 ) {
     let image_move_up = asset_server.load("command_icons/arrow_up_outlined.png");
     let image_move_right = asset_server.load("command_icons/arrow_right_outlined.png");
-    let image_wait = asset_server.load("command_icons/clock_outlined.png");
     let slicer = TextureSlicer {
         border: Default::default(),
         center_scale_mode: SliceScaleMode::Stretch,
@@ -406,7 +463,10 @@ fn build_control_panel(
         },))
         .with_children(|parent| {
             parent.spawn((
-                Text::new("Plan Rover Commands"),
+                LocalizedText::new(match unit_kind {
+                    UnitKind::Rover => "control.plan_rover",
+                    UnitKind::Helicopter => "control.plan_helicopter",
+                }),
                 TextFont {
                     font: asset_server.load("fonts/SpaceGrotesk-Medium.ttf"),
                     font_size: 18.0,
@@ -426,6 +486,9 @@ fn build_control_panel(
             justify_content: JustifyContent::Center,
             align_items: AlignItems::Center,
             column_gap: Px_dynamic(20.0),
+            // helicopters have more commands than fit on a single row
+            flex_wrap: FlexWrap::Wrap,
+            row_gap: Px_dynamic(8.0),
             margin: UiRect {
                 bottom: Px_dynamic(16.0),
                 ..default()
@@ -450,30 +513,30 @@ fn build_control_panel(
                         align_items: AlignItems::Center,
                         ..default()
                     };
-                    let img_up = ImageNode {
-                        image: image_move_up.clone(),
-                        image_mode: NodeImageMode::Sliced(slicer.clone()),
-                        flip_y: false,
-                        ..default()
-                    };
-                    let img_down = ImageNode {
-                        image: image_move_up.clone(),
-                        image_mode: NodeImageMode::Sliced(slicer.clone()),
-                        flip_y: true,
-                        ..default()
-                    };
-                    let img_left = ImageNode {
-                        image: image_move_right.clone(),
-                        image_mode: NodeImageMode::Sliced(slicer.clone()),
-                        flip_x: true,
-                        ..default()
-                    };
-                    let img_right = ImageNode {
-                        image: image_move_right.clone(),
-                        image_mode: NodeImageMode::Sliced(slicer.clone()),
-                        flip_x: false,
-                        ..default()
-                    };
+                    // relative commands take the place of the compass arrows, with their own icons
+                    let [action_up, action_down, action_left, action_right] =
+                        control_scheme.movement_actions();
+                    let arrow_image =
+                        |action: ActionType, image: &Handle<Image>, flip_x: bool, flip_y: bool| {
+                            match control_scheme {
+                                ControlScheme::Absolute => ImageNode {
+                                    image: image.clone(),
+                                    image_mode: NodeImageMode::Sliced(slicer.clone()),
+                                    flip_x,
+                                    flip_y,
+                                    ..default()
+                                },
+                                ControlScheme::Relative => ImageNode {
+                                    image: asset_server.load(action.img_path()),
+                                    image_mode: NodeImageMode::Sliced(slicer.clone()),
+                                    ..default()
+                                },
+                            }
+                        };
+                    let img_up = arrow_image(action_up, &image_move_up, false, false);
+                    let img_down = arrow_image(action_down, &image_move_up, false, true);
+                    let img_left = arrow_image(action_left, &image_move_right, true, false);
+                    let img_right = arrow_image(action_right, &image_move_right, false, false);
 
                     // Left column: left arrow
                     parent
@@ -486,7 +549,8 @@ fn build_control_panel(
                         .with_children(|parent| {
                             parent.spawn((
                                 Button,
-                                CommandButton(ActionType::MoveLeft),
+                                CommandButton(action_left),
+                                command_tooltip(action_left, keymap, strings),
                                 node_for_img.clone(),
                                 img_left.clone(),
                                 Transform::default(),
@@ -504,14 +568,16 @@ fn build_control_panel(
                         .with_children(|parent| {
                             parent.spawn((
                                 Button,
-                                CommandButton(ActionType::MoveUp),
+                                CommandButton(action_up),
+                                command_tooltip(action_up, keymap, strings),
                                 node_for_img.clone(),
                                 img_up.clone(),
                                 Transform::default(),
                             ));
                             parent.spawn((
                                 Button,
-                                CommandButton(ActionType::MoveDown),
+                                CommandButton(action_down),
+                                command_tooltip(action_down, keymap, strings),
                                 node_for_img.clone(),
                                 img_down.clone(),
                                 Transform::default(),
@@ -529,7 +595,8 @@ fn build_control_panel(
                         .with_children(|parent| {
                             parent.spawn((
                                 Button,
-                                CommandButton(ActionType::MoveRight),
+                                CommandButton(action_right),
+                                command_tooltip(action_right, keymap, strings),
                                 node_for_img.clone(),
                                 img_right.clone(),
                                 Transform::default(),
@@ -537,52 +604,114 @@ fn build_control_panel(
                         });
                 });
 
-            // Wait button with text
-            parent
-                .spawn((Node {
-                    display: Display::Flex,
-                    flex_direction: FlexDirection::Column,
-                    align_items: AlignItems::Center,
-                    row_gap: Px_dynamic(4.0),
-                    ..default()
-                },))
-                .with_children(|parent| {
-                    let img_wait = ImageNode {
-                        image: image_wait.clone(),
-                        image_mode: NodeImageMode::Sliced(slicer.clone()),
-                        ..default()
-                    };
-                    let node_for_img = Node {
-                        width: Px_dynamic(40.0),
-                        height: Px_dynamic(40.0),
-                        justify_content: JustifyContent::Center,
-                        align_items: AlignItems::Center,
-                        margin: UiRect::all(Px_dynamic(5.0)),
-                        ..default()
-                    };
+            // Buttons with text for everything that isn't a move, depending on the unit
+            for action in [ActionType::Wait, ActionType::Scout, ActionType::DropPack] {
+                if !unit_kind.can_perform(control_scheme, action) {
+                    continue;
+                }
 
-                    parent.spawn((
-                        Button,
-                        CommandButton(ActionType::Wait),
-                        node_for_img.clone(),
-                        img_wait.clone(),
-                        Transform::default(),
-                    ));
-
-                    parent.spawn((
-                        Text::new("Wait"),
-                        TextFont {
-                            font: asset_server.load("fonts/SpaceGrotesk-Light.ttf"),
-                            font_size: 14.0,
-                            ..default()
-                        },
-                        TextColor(Color::srgb(0.83, 0.83, 0.83)),
-                    ));
-                });
+                build_labelled_command_button(
+                    parent,
+                    asset_server,
+                    &slicer,
+                    action,
+                    keymap,
+                    strings,
+                );
+            }
         });
 }
 
-fn ui_sidebar_container_node() -> Node {
+fn command_tooltip(action: ActionType, keymap: &Keymap, strings: &Strings) -> Tooltip {
+    Tooltip(strings.format(
+        "tooltip.command",
+        &[
+            ("command", &strings.get(action.key())),
+            ("keys", &keymap.describe(KeyAction::for_command(action))),
+        ],
+    ))
+}
+
+fn select_unit_tooltip(index: usize, keymap: &Keymap, strings: &Strings) -> Tooltip {
+    Tooltip(strings.format(
+        "tooltip.select_unit",
+        &[
+            ("number", &(index + 1)),
+            (
+                "keys",
+                &keymap.describe(KeyAction::SelectUnit(index as u8 + 1)),
+            ),
+        ],
+    ))
+}
+
+fn build_labelled_command_button(
+    parent: &mut RelatedSpawnerCommands<ChildOf>,
+    asset_server: &Res<AssetServer>,
+    slicer: &TextureSlicer,
+    action: ActionType,
+    keymap: &Keymap,
+    strings: &Strings,
+) {
+    parent
+        .spawn((Node {
+            display: Display::Flex,
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: Px_dynamic(4.0),
+            ..default()
+        },))
+        .with_children(|parent| {
+            let img = ImageNode {
+                image: asset_server.load(action.img_path()),
+                image_mode: NodeImageMode::Sliced(slicer.clone()),
+                ..default()
+            };
+            let node_for_img = Node {
+                width: Px_dynamic(40.0),
+                height: Px_dynamic(40.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                margin: UiRect::all(Px_dynamic(5.0)),
+                ..default()
+            };
+
+            parent.spawn((
+                Button,
+                CommandButton(action),
+                command_tooltip(action, keymap, strings),
+                node_for_img,
+                img,
+                Transform::default(),
+            ));
+
+            parent.spawn((
+                LocalizedText::new(action.key()),
+                TextFont {
+                    font: asset_server.load("fonts/SpaceGrotesk-Light.ttf"),
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.83, 0.83, 0.83)),
+            ));
+        });
+}
+
+/// Height of the control panel at the bottom of the screen in portrait, in percent of the window
+pub const PORTRAIT_PANEL_HEIGHT: f32 = 45.0;
+
+fn ui_sidebar_container_node(portrait: bool) -> Node {
+    if portrait {
+        return Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(0.0),
+            height: Val::Percent(PORTRAIT_PANEL_HEIGHT),
+            width: Val::Percent(100.0),
+            display: Display::Flex,
+            ..default()
+        };
+    }
+
     Node {
         height: Val::Percent(100.0),
         width: Px_dynamic(300.0),
@@ -593,23 +722,39 @@ fn ui_sidebar_container_node() -> Node {
     }
 }
 
-fn ui_sidebar_node() -> Node {
+fn ui_sidebar_node(portrait: bool) -> Node {
     Node {
-        height: Val::Percent(80.0),
+        height: Val::Percent(if portrait { 100.0 } else { 80.0 }),
         width: Val::Percent(100.0),
         display: Display::Flex,
         flex_direction: FlexDirection::Column,
         padding: UiRect::all(Px_dynamic(14.0)),
-        border: UiRect {
-            right: Px_dynamic(6.0),
-            top: Px_dynamic(6.0),
-            bottom: Px_dynamic(6.0),
-            ..default()
+        border: if portrait {
+            UiRect::top(Px_dynamic(6.0))
+        } else {
+            UiRect {
+                right: Px_dynamic(6.0),
+                top: Px_dynamic(6.0),
+                bottom: Px_dynamic(6.0),
+                ..default()
+            }
         },
         ..default()
     }
 }
 
+fn ui_sidebar_border_radius(portrait: bool) -> BorderRadius {
+    if portrait {
+        BorderRadius::top(Px_dynamic(8.0))
+    } else {
+        BorderRadius {
+            top_right: Px_dynamic(8.0),
+            bottom_right: Px_dynamic(8.0),
+            ..default()
+        }
+    }
+}
+
 fn build_deleteable_action_button(
     parent: &mut RelatedSpawnerCommands<ChildOf>,
     rover_index: usize,
@@ -689,10 +834,35 @@ fn build_deleteable_action_button(
                     should_block_lower: false,
                     ..default()
                 },
-            ));
+            ))
+            .with_child(unit_label_text(rover_index, 12.0, asset_server));
         });
 }
 
+/// Number of a unit over the corner of its button or of its commands.
+fn unit_label_text(rover_index: usize, font_size: f32, asset_server: &AssetServer) -> impl Bundle {
+    (
+        Text::new(unit_label(rover_index)),
+        TextFont {
+            font: asset_server.load("fonts/SpaceGrotesk-Medium.ttf"),
+            font_size,
+            ..default()
+        },
+        TextColor(Color::WHITE),
+        TextShadow {
+            offset: Vec2::splat(1.0),
+            color: Color::linear_rgba(0., 0., 0., 0.9),
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(-2.0),
+            bottom: Val::Px(-4.0),
+            ..default()
+        },
+        Pickable::IGNORE,
+    )
+}
+
 fn multi_robot_command_list() -> Node {
     Node {
         width: Px_dynamic(56.0),
@@ -713,11 +883,17 @@ fn multi_robot_command_list() -> Node {
 fn build_execute_button(
     parent: &mut RelatedSpawnerCommands<ChildOf>,
     asset_server: &Res<AssetServer>,
+    keymap: &Keymap,
+    strings: &Strings,
 ) {
     parent
         .spawn((
             ExecuteButton,
             Button,
+            Tooltip(strings.format(
+                "tooltip.execute",
+                &[("keys", &keymap.describe(KeyAction::Execute))],
+            )),
             Node {
                 width: Val::Percent(100.0),
                 height: Px_dynamic(60.0),
@@ -739,7 +915,7 @@ fn build_execute_button(
         ))
         .with_children(|parent| {
             parent.spawn((
-                Text::new("Execute"),
+                LocalizedText::new("control.execute"),
                 TextFont {
                     font: asset_server.load("fonts/SpaceGrotesk-Light.ttf"),
                     font_size: 26.0,
@@ -754,19 +930,24 @@ fn build_execute_button(
         });
 }
 
+fn cleanup_control_ui(mut commands: Commands, query: Query<Entity, With<ControlUI>>) {
+    for ui_element in query.iter() {
+        commands.entity(ui_element).despawn();
+    }
+}
+
 // handlers
 
 fn execute_handler(
-    mut commands: Commands,
     mut interaction_query: Query<&Interaction, (Changed<Interaction>, With<ExecuteButton>)>,
     mut events: EventWriter<ActionListExecute>,
     mut next_state: ResMut<NextState<GameState>>,
     action_list: Res<ActionList>,
     keyboard_input: Res<ButtonInput<KeyCode>>,
     game_state: Res<State<GameState>>,
-    asset_server: Res<AssetServer>,
-    mut help_button_query: Query<&mut HelpButton>,
-    dialog_query: Query<Entity, With<HelpDialog>>,
+    mut help_triggers: EventWriter<HelpTriggerEvent>,
+    save_data: Res<SaveData>,
+    gamepads: Query<&Gamepad>,
 ) {
     let has_no_actions = action_list.actions.iter().all(|v| v.is_empty());
 
@@ -783,8 +964,14 @@ fn execute_handler(
         }
     }
 
-    // Check space key press (only in Programming state)
-    if *game_state.get() == GameState::Programming && keyboard_input.just_pressed(KeyCode::Space) {
+    // Check execute key or gamepad button press (only in Programming state)
+    if *game_state.get() == GameState::Programming
+        && (save_data
+            .settings
+            .keymap
+            .just_pressed(KeyAction::Execute, &keyboard_input)
+            || gamepad_just_pressed_actions(gamepads.iter()).contains(&KeyAction::Execute))
+    {
         tried_to_execute = true;
         if !has_no_actions {
             should_execute = true;
@@ -792,12 +979,7 @@ fn execute_handler(
     }
 
     if tried_to_execute && has_no_actions {
-        show_help_for_empty_actions(
-            &mut commands,
-            &asset_server,
-            &mut help_button_query,
-            &dialog_query,
-        );
+        help_triggers.write(HelpTriggerEvent(HelpTrigger::EmptyProgram));
         return;
     }
 
@@ -809,6 +991,36 @@ fn execute_handler(
     }
 }
 
+fn play_command_sound(commands: &mut Commands, asset_server: &AssetServer, action: ActionType) {
+    let sound = match action {
+        ActionType::MoveUp | ActionType::Forward => "sfx/up.ogg",
+        ActionType::MoveDown | ActionType::Reverse => "sfx/down.ogg",
+        ActionType::MoveRight | ActionType::TurnRight => "sfx/right.ogg",
+        ActionType::MoveLeft | ActionType::TurnLeft => "sfx/left.ogg",
+        ActionType::Wait | ActionType::Scout | ActionType::DropPack => "sfx/wait.ogg",
+    };
+    commands.spawn((
+        AudioPlayer::new(asset_server.load(sound)),
+        PlaybackSettings::DESPAWN,
+    ));
+}
+
+/// Adds `action` to the program of the selected unit, unless it is full.
+fn push_command(action_list: &mut ActionList, action: ActionType) -> bool {
+    let selection = action_list.current_selection;
+    let Some(actions) = action_list.actions.get_mut(selection) else {
+        return false;
+    };
+    if actions.len() >= MAX_COMMANDS as usize {
+        return false;
+    }
+
+    actions.push(Action {
+        moves: (action, selection),
+    });
+    true
+}
+
 fn command_button_handler(
     mut commands: Commands,
     mut interaction_query: Query<
@@ -825,57 +1037,17 @@ fn command_button_handler(
 
         match *interaction {
             Interaction::Pressed => {
-                match command.0 {
-                    ActionType::MoveUp => {
-                        commands.spawn((
-                            AudioPlayer::new(asset_server.load("sfx/up.ogg")),
-                            PlaybackSettings::DESPAWN,
-                        ));
-                    }
+                play_command_sound(&mut commands, &asset_server, command.0);
 
-                    ActionType::MoveDown => {
-                        commands.spawn((
-                            AudioPlayer::new(asset_server.load("sfx/down.ogg")),
-                            PlaybackSettings::DESPAWN,
-                        ));
-                    }
+                image.color = colors.color(action_list_selection);
 
-                    ActionType::MoveRight => {
-                        commands.spawn((
-                            AudioPlayer::new(asset_server.load("sfx/right.ogg")),
-                            PlaybackSettings::DESPAWN,
-                        ));
-                    }
-
-                    ActionType::MoveLeft => {
-                        commands.spawn((
-                            AudioPlayer::new(asset_server.load("sfx/left.ogg")),
-                            PlaybackSettings::DESPAWN,
-                        ));
-                    }
-
-                    ActionType::Wait => {
-                        commands.spawn((
-                            AudioPlayer::new(asset_server.load("sfx/wait.ogg")),
-                            PlaybackSettings::DESPAWN,
-                        ));
-                    }
-                }
-
-                image.color = *colors.0.get(action_list_selection).unwrap();
-
-                if action_list.actions.get(action_list_selection).is_some()
-                    && action_list.actions[action_list_selection].len() < MAX_COMMANDS as usize
-                {
-                    action_list.actions[action_list_selection].push(Action {
-                        moves: (command.0.clone(), action_list_selection),
-                    });
+                if push_command(&mut action_list, command.0) {
                     action_writer.write(action_list.clone());
                 }
                 trans.scale = Vec3::new(0.9, 0.9, 0.9);
             }
             Interaction::Hovered => {
-                image.color = *colors.0.get(action_list_selection).unwrap();
+                image.color = colors.color(action_list_selection);
                 trans.scale = Vec3::new(1.1, 1.1, 1.1);
             }
             Interaction::None => {
@@ -886,6 +1058,77 @@ fn command_button_handler(
     }
 }
 
+/// Programs the selected unit from the keyboard, with the keys of the keymap in the settings, and
+/// from the shortcuts of the gamepad.
+fn keyboard_command_handler(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    save_data: Res<SaveData>,
+    mut action_list: ResMut<ActionList>,
+    mut action_writer: EventWriter<ActionList>,
+    active_level: Res<ActiveLevel>,
+    levels: Res<Assets<GRADVM>>,
+    asset_server: Res<AssetServer>,
+) {
+    let Some(level) = active_level
+        .0
+        .as_ref()
+        .and_then(|handle| levels.get(handle))
+    else {
+        return;
+    };
+
+    let mut has_to_update = false;
+    let key_actions = save_data
+        .settings
+        .keymap
+        .just_pressed_actions(&keyboard_input)
+        .into_iter()
+        .chain(gamepad_just_pressed_actions(gamepads.iter()));
+    for key_action in key_actions {
+        let unit_count = action_list.actions.len();
+        match key_action {
+            KeyAction::RemoveLast => {
+                let selection = action_list.current_selection;
+                if let Some(actions) = action_list.actions.get_mut(selection) {
+                    has_to_update |= actions.pop().is_some();
+                }
+            }
+            KeyAction::ClearAll => {
+                for actions in action_list.actions.iter_mut() {
+                    actions.clear();
+                }
+                has_to_update = true;
+            }
+            KeyAction::NextUnit if unit_count > 0 => {
+                action_list.current_selection = (action_list.current_selection + 1) % unit_count;
+                has_to_update = true;
+            }
+            KeyAction::SelectUnit(number) if (1..=unit_count).contains(&(number as usize)) => {
+                action_list.current_selection = number as usize - 1;
+                has_to_update = true;
+            }
+            _ => {
+                let Some(action) = key_action.command(level.IMPERIA) else {
+                    continue;
+                };
+                // the same commands as the buttons shown for the selected unit
+                let kind = level.GENVS(action_list.current_selection as u8);
+                if kind.can_perform(level.IMPERIA, action) && push_command(&mut action_list, action)
+                {
+                    play_command_sound(&mut commands, &asset_server, action);
+                    has_to_update = true;
+                }
+            }
+        }
+    }
+
+    if has_to_update {
+        action_writer.write(action_list.clone());
+    }
+}
+
 fn robot_button_handler(
     mut interaction_query: Query<
         (&Interaction, &RobotButton),
@@ -915,23 +1158,42 @@ fn delete_action_handler(
     mut interaction_query: Query<(&Interaction, &ActionDeleteButton), Changed<Interaction>>,
     mut action_list: ResMut<ActionList>,
     mut action_writer: EventWriter<ActionList>,
+    touches: Res<Touches>,
+    mut touched: Local<Option<ActionDeleteButton>>,
 ) {
     let mut has_to_update: bool = false;
+    let mut remove = |button: &ActionDeleteButton| {
+        action_list
+            .actions
+            .get_mut(button.rover_index)
+            .unwrap()
+            .remove(button.action_index);
+        has_to_update = true;
+    };
+
     for (interaction, button) in interaction_query.iter_mut() {
         match *interaction {
-            Interaction::Pressed => {
-                action_list
-                    .actions
-                    .get_mut(button.rover_index)
-                    .unwrap()
-                    .remove(button.action_index);
-                has_to_update = true;
-            }
+            // a finger touching the command may be swiping the list, it is only removed once the
+            // finger is lifted without moving
+            Interaction::Pressed if touches.iter().next().is_some() => *touched = Some(*button),
+            Interaction::Pressed => remove(button),
             Interaction::Hovered => {}
             Interaction::None => {}
         }
     }
 
+    if touches.iter().any(|touch| !is_tap(touch)) {
+        *touched = None;
+    }
+    if let Some(button) = touched.as_ref()
+        && touches.any_just_released()
+    {
+        if touches.iter_just_released().all(is_tap) {
+            remove(button);
+        }
+        *touched = None;
+    }
+
     if has_to_update {
         action_writer.write(action_list.clone());
     }
@@ -967,7 +1229,23 @@ pub fn update_scroll_position(
     hover_map: Res<HoverMap>,
     mut scrolled_node_query: Query<&mut ScrollPosition>,
     keyboard_input: Res<ButtonInput<KeyCode>>,
+    touches: Res<Touches>,
 ) {
+    // swiping with one finger, two are pinching to zoom the camera
+    let mut fingers = touches.iter();
+    if let (Some(finger), None) = (fingers.next(), fingers.next())
+        && !is_tap(finger)
+        && let Some(pointer_map) = hover_map.get(&PointerId::Touch(finger.id()))
+    {
+        let delta = finger.delta();
+        for (entity, _hit) in pointer_map.iter() {
+            if let Ok(mut scroll_position) = scrolled_node_query.get_mut(*entity) {
+                scroll_position.offset_x -= delta.x;
+                scroll_position.offset_y -= delta.y;
+            }
+        }
+    }
+
     for mouse_wheel_event in mouse_wheel_events.read() {
         let (mut dx, mut dy) = match mouse_wheel_event.unit {
             MouseScrollUnit::Line => (
@@ -994,6 +1272,73 @@ pub fn update_scroll_position(
     }
 }
 
+/// Lays the control panel out again when the window turns between portrait and landscape.
+fn rebuild_on_orientation_change(
+    mut resized: EventReader<WindowResized>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    action_list: Res<ActionList>,
+    mut action_writer: EventWriter<ActionList>,
+    mut was_portrait: Local<Option<bool>>,
+) {
+    if resized.is_empty() {
+        return;
+    }
+    resized.clear();
+
+    let Ok(window) = windows.single() else {
+        return;
+    };
+    let portrait = is_portrait(window);
+    if was_portrait
+        .replace(portrait)
+        .is_some_and(|was| was != portrait)
+    {
+        action_writer.write(action_list.clone());
+    }
+}
+
+/// The tooltips are only localized as the panel gets built.
+fn rebuild_on_language_change(
+    action_list: Res<ActionList>,
+    mut action_writer: EventWriter<ActionList>,
+) {
+    action_writer.write(action_list.clone());
+}
+
+/// Recolours the units, their buttons and their programs when the palette in the settings changes.
+#[allow(clippy::too_many_arguments)]
+fn apply_palette(
+    mut commands: Commands,
+    save_data: Res<SaveData>,
+    mut applied: Local<Option<Palette>>,
+    mut ui_colors: ResMut<UIRoverColors>,
+    mut model_colors: ResMut<RoverColors>,
+    rovers: Query<(Entity, &RoverEntity, &SceneRoot)>,
+    action_list: Res<ActionList>,
+    mut action_writer: EventWriter<ActionList>,
+    game_state: Res<State<GameState>>,
+) {
+    let palette = save_data.settings.palette;
+    if *applied == Some(palette) {
+        return;
+    }
+    *applied = Some(palette);
+
+    ui_colors.0 = palette.ui_colors();
+    model_colors.0 = palette.model_colors();
+    for (entity, rover, scene) in rovers.iter() {
+        let color = model_colors.color(rover.identifier as usize);
+        commands
+            .entity(entity)
+            .insert(SceneColorOverride(Some((scene.0.clone(), color))));
+    }
+
+    // the control panel is only there during a level
+    if *game_state.get() != GameState::TitleScreen {
+        action_writer.write(action_list.clone());
+    }
+}
+
 pub const SELECTION_LIGHT_INTENSITY: f32 = 500_000.0;
 
 /// Spawns a selection light if one doesn't exist yet
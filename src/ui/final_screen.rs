@@ -1,12 +1,19 @@
+use crate::daily_challenge::{DailyChallenge, record_daily_result};
+use crate::game_mode::GameMode;
 use crate::help::help::{HelpButton, HelpDialog};
 use crate::level::{GRADVM, GRADVM_ONVSTVS};
-use crate::level_spawner::{ActiveLevel, LevelElement};
+use crate::locale::Strings;
+use crate::gameplay::{ActiveLevel, GameState};
+use crate::level_spawner::LevelElement;
 use crate::particle::particle::Particle;
 use crate::puzzle_evaluation::PuzzleResponseEvent;
-use crate::title_screen::GameState;
+use crate::save::SaveData;
+use crate::score::{LastScore, record_score};
 use crate::ui::battery_ui::BatteryUIElement;
 use crate::ui::control_ui::ControlUI;
 use crate::ui::interactive_button::InteractiveButton;
+use crate::ui::localized_text::LocalizedText;
+use crate::ui::score_ui::spawn_score;
 use bevy::color::Srgba;
 use bevy::prelude::*;
 
@@ -23,11 +30,16 @@ impl Plugin for FinalScreenPlugin {
         app.add_systems(
             Update,
             (
-                show_final_screen.run_if(in_state(GameState::Execution)),
+                show_final_screen
+                    .run_if(in_state(GameState::Execution))
+                    .after(record_daily_result)
+                    .after(record_score),
                 return_to_title_click_handler,
                 adjust_camera_for_final_screen,
             ),
         );
+        // restarting or leaving the level from the pause menu
+        app.add_systems(OnExit(GameState::Execution), cleanup_final_screen);
     }
 }
 
@@ -41,10 +53,20 @@ fn show_final_screen(
     level_handles: Res<GRADVM_ONVSTVS>,
     level_elements: Query<Entity, With<LevelElement>>,
     particles: Query<Entity, (With<Particle>, Without<LevelElement>)>,
-    control_ui: Query<Entity, With<ControlUI>>,
-    battery_ui: Query<Entity, With<BatteryUIElement>>,
-    help_button: Query<Entity, With<HelpButton>>,
-    help_dialog: Query<Entity, With<HelpDialog>>,
+    in_game_ui: Query<
+        Entity,
+        Or<(
+            With<ControlUI>,
+            With<BatteryUIElement>,
+            With<HelpButton>,
+            With<HelpDialog>,
+        )>,
+    >,
+    game_mode: Res<GameMode>,
+    daily_challenge: Option<Res<DailyChallenge>>,
+    save_data: Res<SaveData>,
+    last_score: Res<LastScore>,
+    strings: Res<Strings>,
 ) {
     for event in puzzle_response_events.read() {
         if *event == PuzzleResponseEvent::Solved && existing_ui.is_empty() {
@@ -56,8 +78,7 @@ fn show_final_screen(
                 continue;
             };
 
-            let final_level_index = level_handles.GRADVS.len() - 1;
-            if current_level.INDEX as usize != final_level_index {
+            if !game_mode.is_final_level(current_level, &level_handles) {
                 continue;
             }
 
@@ -68,19 +89,26 @@ fn show_final_screen(
             for particle in particles.iter() {
                 commands.entity(particle).despawn();
             }
-            for ui_element in control_ui.iter() {
-                commands.entity(ui_element).despawn();
-            }
-            for ui_element in battery_ui.iter() {
-                commands.entity(ui_element).despawn();
-            }
-            for ui_element in help_button.iter() {
-                commands.entity(ui_element).despawn();
-            }
-            for ui_element in help_dialog.iter() {
+            for ui_element in in_game_ui.iter() {
                 commands.entity(ui_element).despawn();
             }
 
+            let (title, message) = match (*game_mode, &daily_challenge) {
+                (GameMode::Daily, Some(challenge)) => (
+                    strings.get("final.daily_title"),
+                    save_data
+                        .daily
+                        .get(&challenge.date.to_string())
+                        .cloned()
+                        .unwrap_or_default()
+                        .share_text(challenge.date, &strings),
+                ),
+                _ => (
+                    strings.get("final.title"),
+                    strings.get("final.message").to_string(),
+                ),
+            };
+
             commands
                 .spawn((
                     FinalScreenUI,
@@ -99,7 +127,7 @@ fn show_final_screen(
                 ))
                 .with_children(|parent| {
                     parent.spawn((
-                        Text::new("Mission Completed!"),
+                        Text::new(title),
                         TextFont {
                             font: asset_server.load("fonts/SpaceGrotesk-Bold.ttf"),
                             font_size: 80.0,
@@ -114,7 +142,7 @@ fn show_final_screen(
                     ));
 
                     parent.spawn((
-                        Text::new("All minerals have been successfully collected.\nThe mission was a success!"),
+                        Text::new(message.clone()),
                         TextFont {
                             font: asset_server.load("fonts/SpaceGrotesk-Regular.ttf"),
                             font_size: 32.0,
@@ -128,6 +156,14 @@ fn show_final_screen(
                         },
                     ));
 
+                    spawn_score(
+                        parent,
+                        &last_score,
+                        &asset_server,
+                        &strings,
+                        Color::Srgba(Srgba::hex("3a312e").unwrap()),
+                    );
+
                     parent
                         .spawn((
                             Button,
@@ -150,7 +186,7 @@ fn show_final_screen(
                         ))
                         .with_children(|parent| {
                             parent.spawn((
-                                Text::new("Play Again"),
+                                LocalizedText::new("final.play_again"),
                                 TextFont {
                                     font: asset_server.load("fonts/SpaceGrotesk-Light.ttf"),
                                     font_size: 40.0,
@@ -205,3 +241,9 @@ fn adjust_camera_for_final_screen(
         transform.look_at(Vec3::new(0.0, 5.0, 0.0), Vec3::Y);
     }
 }
+
+fn cleanup_final_screen(mut commands: Commands, query: Query<Entity, With<FinalScreenUI>>) {
+    for ui_element in query.iter() {
+        commands.entity(ui_element).despawn();
+    }
+}
@@ -0,0 +1,216 @@
+use bevy::prelude::*;
+use bevy::ui::UiSystem;
+use bevy::window::CursorMoved;
+
+pub struct FocusPlugin;
+
+/// How far the left stick has to be pushed to move the focus
+const STICK_THRESHOLD: f32 = 0.5;
+
+/// Button a gamepad moves between and presses. The focused button is shown as hovered, and
+/// pressing it sets its `Interaction` like a click would, so every button handler works with it.
+#[derive(Resource, Default)]
+pub struct ButtonFocus {
+    pub entity: Option<Entity>,
+    /// Where the focused button was, to focus the closest one once the UI is rebuilt
+    position: Option<Vec2>,
+    /// Button pressed on the previous frame, to be released
+    pressed: Option<Entity>,
+}
+
+impl Plugin for FocusPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ButtonFocus>();
+        // right after the mouse updated the interactions, so the handlers see the presses this frame
+        app.add_systems(
+            PreUpdate,
+            (clear_focus_on_mouse_move, move_focus, press_focused_button)
+                .chain()
+                .after(UiSystem::Focus),
+        );
+    }
+}
+
+fn clear_focus_on_mouse_move(
+    mut cursor_moved: EventReader<CursorMoved>,
+    mut focus: ResMut<ButtonFocus>,
+    mut interactions: Query<&mut Interaction>,
+) {
+    if cursor_moved.is_empty() {
+        return;
+    }
+    cursor_moved.clear();
+
+    if let Some(entity) = focus.entity.take()
+        && let Ok(mut interaction) = interactions.get_mut(entity)
+        && *interaction == Interaction::Hovered
+    {
+        *interaction = Interaction::None;
+    }
+    focus.position = None;
+}
+
+/// Direction the d-pad or the left stick was just pushed in, with y going down like the UI.
+fn navigation_direction(gamepads: &Query<&Gamepad>, stick_pushed: &mut bool) -> Option<Vec2> {
+    let mut direction = None;
+    let mut stick = Vec2::ZERO;
+    for gamepad in gamepads.iter() {
+        for (button, towards) in [
+            (GamepadButton::DPadUp, Vec2::NEG_Y),
+            (GamepadButton::DPadDown, Vec2::Y),
+            (GamepadButton::DPadLeft, Vec2::NEG_X),
+            (GamepadButton::DPadRight, Vec2::X),
+        ] {
+            if gamepad.just_pressed(button) {
+                direction = Some(towards);
+            }
+        }
+        if gamepad.left_stick().length() > stick.length() {
+            stick = gamepad.left_stick();
+        }
+    }
+
+    // the stick moves the focus once each time it is pushed
+    if stick.length() < STICK_THRESHOLD {
+        *stick_pushed = false;
+    } else if !*stick_pushed {
+        *stick_pushed = true;
+        let towards = if stick.x.abs() > stick.y.abs() {
+            Vec2::new(stick.x.signum(), 0.0)
+        } else {
+            Vec2::new(0.0, -stick.y.signum())
+        };
+        direction = direction.or(Some(towards));
+    }
+
+    direction
+}
+
+/// The button from `candidates` closest to `from` in `direction`, favouring the ones in line with
+/// it.
+fn next_in_direction(
+    candidates: &[(Entity, Vec2)],
+    from: Vec2,
+    direction: Vec2,
+) -> Option<(Entity, Vec2)> {
+    candidates
+        .iter()
+        .filter_map(|(entity, position)| {
+            let offset = *position - from;
+            let along = offset.dot(direction);
+            let across = offset.perp_dot(direction).abs();
+            (along > 1.0).then_some((along + 2.0 * across, (*entity, *position)))
+        })
+        .min_by(|(a, _), (b, _)| a.total_cmp(b))
+        .map(|(_, candidate)| candidate)
+}
+
+fn move_focus(
+    gamepads: Query<&Gamepad>,
+    mut focus: ResMut<ButtonFocus>,
+    buttons: Query<
+        (
+            Entity,
+            &GlobalTransform,
+            &ComputedNode,
+            &InheritedVisibility,
+        ),
+        With<Button>,
+    >,
+    mut interactions: Query<&mut Interaction>,
+    mut stick_pushed: Local<bool>,
+) {
+    let candidates: Vec<(Entity, Vec2)> = buttons
+        .iter()
+        .filter(|(_, _, node, visibility)| visibility.get() && node.size().cmpgt(Vec2::ZERO).all())
+        .map(|(entity, transform, _, _)| (entity, transform.translation().truncate()))
+        .collect();
+
+    let direction = navigation_direction(&gamepads, &mut stick_pushed);
+    let any_pressed = gamepads
+        .iter()
+        .any(|gamepad| gamepad.just_pressed(GamepadButton::South));
+
+    let current = focus
+        .entity
+        .and_then(|entity| {
+            candidates
+                .iter()
+                .find(|(candidate, _)| *candidate == entity)
+        })
+        .copied();
+    let next = match (current, focus.position, direction) {
+        (Some((_, from)), _, Some(direction)) => next_in_direction(&candidates, from, direction),
+        (Some(_), _, None) => None,
+        // the focused button is gone, most likely the UI was rebuilt around it
+        (None, Some(from), _) => candidates
+            .iter()
+            .min_by(|(_, a), (_, b)| a.distance(from).total_cmp(&b.distance(from)))
+            .copied(),
+        // nothing is focused yet, the first press focuses the top left button
+        (None, None, Some(_)) => top_left(&candidates),
+        (None, None, None) if any_pressed => top_left(&candidates),
+        (None, None, None) => None,
+    };
+
+    let Some((entity, position)) = next else {
+        if let Some((_, position)) = current {
+            focus.position = Some(position);
+        }
+        return;
+    };
+
+    if let Some(previous) = focus.entity
+        && previous != entity
+        && let Ok(mut interaction) = interactions.get_mut(previous)
+        && *interaction == Interaction::Hovered
+    {
+        *interaction = Interaction::None;
+    }
+    focus.entity = Some(entity);
+    focus.position = Some(position);
+}
+
+fn top_left(candidates: &[(Entity, Vec2)]) -> Option<(Entity, Vec2)> {
+    candidates
+        .iter()
+        .min_by(|(_, a), (_, b)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)))
+        .copied()
+}
+
+fn press_focused_button(
+    gamepads: Query<&Gamepad>,
+    mut focus: ResMut<ButtonFocus>,
+    mut interactions: Query<&mut Interaction>,
+    mut focus_just_moved: Local<Option<Entity>>,
+) {
+    if let Some(pressed) = focus.pressed.take()
+        && let Ok(mut interaction) = interactions.get_mut(pressed)
+    {
+        interaction.set_if_neq(Interaction::None);
+    }
+
+    let Some(entity) = focus.entity else {
+        *focus_just_moved = None;
+        return;
+    };
+    let Ok(mut interaction) = interactions.get_mut(entity) else {
+        focus.entity = None;
+        return;
+    };
+
+    // the press that focused the first button doesn't press it as well
+    let newly_focused = *focus_just_moved != Some(entity);
+    *focus_just_moved = Some(entity);
+    let pressed = gamepads
+        .iter()
+        .any(|gamepad| gamepad.just_pressed(GamepadButton::South));
+
+    if pressed && !newly_focused {
+        *interaction = Interaction::Pressed;
+        focus.pressed = Some(entity);
+    } else {
+        // the mouse leaves the interaction of the buttons it isn't over at None
+        interaction.set_if_neq(Interaction::Hovered);
+    }
+}
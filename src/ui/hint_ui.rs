@@ -0,0 +1,301 @@
+use crate::game_control::actions::{ActionList, ActionType};
+use crate::hint::{Hint, HintState, hint};
+use crate::level::GRADVM;
+use crate::gameplay::{ActiveLevel, GameState};
+use crate::locale::Strings;
+use crate::ui::Px_dynamic;
+use crate::ui::control_ui::CONTROL_UI_SECONDARY_BACKGROUND_COLOR;
+use crate::ui::interactive_button::InteractiveButton;
+use crate::ui::localized_text::LocalizedText;
+use crate::unit::UnitKind;
+use bevy::ecs::relationship::RelatedSpawnerCommands;
+use bevy::prelude::*;
+use bevy::tasks::futures::check_ready;
+use bevy::tasks::{AsyncComputeTaskPool, Task};
+
+pub struct HintUIPlugin;
+
+#[derive(Component)]
+pub struct HintButton;
+
+#[derive(Component)]
+struct HintPanel;
+
+#[derive(Component)]
+struct HintText;
+
+/// A hint being worked out, the solver can take a while on the bigger levels.
+#[derive(Component)]
+struct HintTask(Task<Hint>);
+
+/// The hint on screen or on its way, both gone once the hint no longer applies.
+type HintFilter = Or<(With<HintPanel>, With<HintTask>)>;
+
+impl Plugin for HintUIPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HintState>();
+        app.add_systems(
+            Update,
+            (
+                reset_hints_on_level_change,
+                hint_button_handler.run_if(in_state(GameState::Programming)),
+                show_finished_hint,
+                clear_outdated_hint,
+            )
+                .chain()
+                .run_if(not(in_state(GameState::TitleScreen))),
+        );
+        app.add_systems(OnEnter(GameState::TitleScreen), cleanup_hints);
+    }
+}
+
+pub fn build_hint_button(
+    parent: &mut RelatedSpawnerCommands<ChildOf>,
+    asset_server: &Res<AssetServer>,
+) {
+    parent
+        .spawn((
+            HintButton,
+            Button,
+            Node {
+                width: Val::Percent(100.0),
+                height: Px_dynamic(40.0),
+                min_height: Px_dynamic(40.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                margin: UiRect::bottom(Px_dynamic(8.0)),
+                ..default()
+            },
+            Transform::default(),
+            BackgroundColor::from(CONTROL_UI_SECONDARY_BACKGROUND_COLOR),
+            InteractiveButton::simple(
+                CONTROL_UI_SECONDARY_BACKGROUND_COLOR,
+                Color::srgba(0.9, 0.9, 0.9, 1.0),
+                true,
+            ),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                LocalizedText::new("hint.button"),
+                TextFont {
+                    font: asset_server.load("fonts/SpaceGrotesk-Light.ttf"),
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(Color::srgba(0.9, 0.9, 0.9, 1.0)),
+            ));
+        });
+}
+
+fn reset_hints_on_level_change(
+    mut commands: Commands,
+    active_level: Res<ActiveLevel>,
+    mut hint_state: ResMut<HintState>,
+    panels: Query<Entity, HintFilter>,
+) {
+    let level = active_level.0.as_ref().map(|handle| handle.id());
+    if hint_state.level == level {
+        return;
+    }
+
+    *hint_state = HintState { level, ..default() };
+    for entity in panels.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn hint_button_handler(
+    mut commands: Commands,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<HintButton>)>,
+    tasks: Query<(), With<HintTask>>,
+    mut hint_state: ResMut<HintState>,
+    action_list: Res<ActionList>,
+    active_level: Res<ActiveLevel>,
+    levels: Res<Assets<GRADVM>>,
+    asset_server: Res<AssetServer>,
+    panels: Query<Entity, With<HintPanel>>,
+    strings: Res<Strings>,
+) {
+    if !interaction_query
+        .iter()
+        .any(|interaction| *interaction == Interaction::Pressed)
+        || !tasks.is_empty()
+    {
+        return;
+    }
+
+    let Some(level) = active_level
+        .0
+        .as_ref()
+        .and_then(|handle| levels.get(handle))
+    else {
+        return;
+    };
+
+    let program = program(&action_list);
+    if program != hint_state.program {
+        hint_state.program = program.clone();
+        hint_state.stage = 0;
+    }
+    hint_state.stage += 1;
+
+    let level = level.clone();
+    let stage = hint_state.stage;
+    let task = AsyncComputeTaskPool::get().spawn(async move { hint(&level, &program, stage) });
+    commands.spawn(HintTask(task));
+
+    for panel in panels.iter() {
+        commands.entity(panel).despawn();
+    }
+    spawn_hint_panel(&mut commands, &asset_server, strings.get("hint.looking"));
+}
+
+#[allow(clippy::too_many_arguments)]
+fn show_finished_hint(
+    mut commands: Commands,
+    mut tasks: Query<(Entity, &mut HintTask)>,
+    mut texts: Query<&mut Text, With<HintText>>,
+    mut action_list: ResMut<ActionList>,
+    mut action_writer: EventWriter<ActionList>,
+    mut hint_state: ResMut<HintState>,
+    active_level: Res<ActiveLevel>,
+    levels: Res<Assets<GRADVM>>,
+    strings: Res<Strings>,
+) {
+    let Some(level) = active_level
+        .0
+        .as_ref()
+        .and_then(|handle| levels.get(handle))
+    else {
+        return;
+    };
+
+    for (entity, mut task) in tasks.iter_mut() {
+        let Some(hint) = check_ready(&mut task.0) else {
+            continue;
+        };
+        commands.entity(entity).despawn();
+
+        // only the hints giving a command away cost the player
+        if let Hint::Command(..) | Hint::Commands(..) = hint {
+            hint_state.used += 1;
+        }
+
+        for mut text in texts.iter_mut() {
+            text.0 = hint_text(level, &hint, &strings);
+        }
+
+        // select the unit the hint is about, as if the player clicked it
+        if let Hint::Unit(unit) | Hint::Command(unit, _) | Hint::Commands(unit, _) = hint
+            && action_list.current_selection != unit
+        {
+            action_list.current_selection = unit;
+            action_writer.write(action_list.clone());
+        }
+    }
+}
+
+/// A hint is only good for the program it was given for.
+fn clear_outdated_hint(
+    mut commands: Commands,
+    mut action_lists: EventReader<ActionList>,
+    hint_state: Res<HintState>,
+    panels: Query<Entity, HintFilter>,
+) {
+    let Some(action_list) = action_lists.read().last() else {
+        return;
+    };
+
+    if program(action_list) != hint_state.program {
+        for entity in panels.iter() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn cleanup_hints(
+    mut commands: Commands,
+    mut hint_state: ResMut<HintState>,
+    panels: Query<Entity, HintFilter>,
+) {
+    *hint_state = HintState::default();
+    for entity in panels.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn spawn_hint_panel(commands: &mut Commands, asset_server: &Res<AssetServer>, text: &str) {
+    commands
+        .spawn((
+            HintPanel,
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Px_dynamic(16.0),
+                left: Px_dynamic(320.0),
+                max_width: Px_dynamic(600.0),
+                padding: UiRect::all(Px_dynamic(12.0)),
+                ..default()
+            },
+            BackgroundColor::from(Color::srgba(0.0, 0.0, 0.0, 0.5)),
+            BorderRadius::all(Px_dynamic(8.0)),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(text),
+                TextFont {
+                    font: asset_server.load("fonts/SpaceGrotesk-Medium.ttf"),
+                    font_size: 22.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                HintText,
+            ));
+        });
+}
+
+fn program(action_list: &ActionList) -> Vec<Vec<ActionType>> {
+    action_list
+        .actions
+        .iter()
+        .map(|actions| actions.iter().map(|action| action.moves.0).collect())
+        .collect()
+}
+
+fn hint_text(level: &GRADVM, hint: &Hint, strings: &Strings) -> String {
+    let unit_name = |unit: usize| {
+        let key = match level.GENVS(unit as u8) {
+            UnitKind::Rover => "hint.rover",
+            UnitKind::Helicopter => "hint.helicopter",
+        };
+        strings.format(key, &[("number", &(unit + 1))])
+    };
+
+    match hint {
+        Hint::Solved => strings.get("hint.solved").to_string(),
+        Hint::DeadEnd => strings.get("hint.dead_end").to_string(),
+        Hint::NotFound => strings.get("hint.not_found").to_string(),
+        Hint::Unit(unit) => strings.format("hint.unit", &[("unit", &unit_name(*unit))]),
+        Hint::Command(unit, action) => strings.format(
+            "hint.command",
+            &[
+                ("unit", &unit_name(*unit)),
+                ("command", &strings.get(action.key())),
+            ],
+        ),
+        Hint::Commands(unit, actions) => strings.format(
+            "hint.commands",
+            &[
+                ("unit", &unit_name(*unit)),
+                (
+                    "commands",
+                    &actions
+                        .iter()
+                        .map(|action| strings.get(action.key()))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                ),
+            ],
+        ),
+    }
+}
@@ -1,11 +1,11 @@
-use crate::title_screen::GameState;
-use crate::Transform;
+use crate::gameplay::GameState;
+use bevy::prelude::Transform;
 use bevy::app::{App, Plugin, Update};
 use bevy::color::{Color, Luminance, Srgba};
 use bevy::math::{Vec2, Vec3};
 use bevy::prelude::{
-    default, in_state, not, BackgroundColor, BorderColor, Changed, Children,
-    Component, ImageNode, Interaction, IntoScheduleConfigs, Query, Text, TextColor, Without,
+    default, in_state, not, BackgroundColor, BorderColor, Changed, Children, Component, ImageNode,
+    Interaction, IntoScheduleConfigs, Query, Text, TextColor, Without,
 };
 
 #[derive(Component)]
@@ -1,6 +1,9 @@
+use crate::game_mode::GameMode;
 use crate::level::GRADVM;
-use crate::level_spawner::ActiveLevel;
-use crate::title_screen::GameState;
+use crate::gameplay::{ActiveLevel, GameState};
+use crate::locale::Strings;
+use crate::rover::RoverEntity;
+use crate::simulation::FailureReason;
 use crate::ui::Px_dynamic;
 use bevy::app::{App, Plugin, Update};
 use bevy::asset::{AssetServer, Assets};
@@ -18,7 +21,14 @@ impl Plugin for LevelIndicatorPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
-            (spawn_level_indicator, update_level_indicator)
+            (
+                spawn_level_indicator,
+                update_level_indicator.run_if(
+                    rover_failure_changed
+                        .or(resource_changed::<ActiveLevel>)
+                        .or(resource_changed::<Strings>),
+                ),
+            )
                 .run_if(not(in_state(GameState::TitleScreen))),
         );
         app.add_systems(OnEnter(GameState::TitleScreen), cleanup_level_indicator);
@@ -31,6 +41,8 @@ fn spawn_level_indicator(
     active_level: Res<ActiveLevel>,
     levels: Res<Assets<GRADVM>>,
     query: Query<Entity, With<LevelIndicatorText>>,
+    game_mode: Res<GameMode>,
+    strings: Res<Strings>,
 ) {
     if active_level.0.is_none() {
         return;
@@ -63,7 +75,7 @@ fn spawn_level_indicator(
         ))
         .with_children(|parent| {
             parent.spawn((
-                Text::new(format!("Level {}", level.INDEX + 1)),
+                Text::new(game_mode.level_label(level, &strings)),
                 TextFont {
                     font: asset_server.load("fonts/SpaceGrotesk-Medium.ttf"),
                     font_size: 24.0,
@@ -84,17 +96,31 @@ fn cleanup_level_indicator(
     }
 }
 
+/// Whether the failure told by the indicator, that of the first unit to fail, changed.
+fn rover_failure_changed(
+    rovers: Query<&RoverEntity>,
+    mut shown: Local<Option<FailureReason>>,
+) -> bool {
+    let failure = rovers.iter().find_map(|rover| rover.failure);
+    if *shown == failure {
+        return false;
+    }
+    *shown = failure;
+    true
+}
+
+/// Follows the level being played, and tells why the last attempt failed until it restarts.
+#[allow(clippy::too_many_arguments)]
 fn update_level_indicator(
     mut commands: Commands,
     active_level: Res<ActiveLevel>,
     levels: Res<Assets<GRADVM>>,
     container_query: Query<Entity, With<LevelIndicatorContainer>>,
-    text_query: Query<&Text, With<LevelIndicatorText>>,
+    mut text_query: Query<&mut Text, With<LevelIndicatorText>>,
+    game_mode: Res<GameMode>,
+    strings: Res<Strings>,
+    rovers: Query<&RoverEntity>,
 ) {
-    if !active_level.is_changed() {
-        return;
-    }
-
     if active_level.0.is_none() {
         for entity in container_query.iter() {
             commands.entity(entity).despawn();
@@ -110,14 +136,18 @@ fn update_level_indicator(
         return;
     };
 
-    let expected_text = format!("Level {}", level.INDEX + 1);
+    let label = game_mode.level_label(level, &strings);
+    let expected_text = match rovers.iter().find_map(|rover| rover.failure) {
+        Some(failure) => strings.format(
+            "level.failed",
+            &[("level", &label), ("reason", &strings.get(failure.key()))],
+        ),
+        None => label,
+    };
 
-    for text in text_query.iter() {
+    for mut text in text_query.iter_mut() {
         if text.0 != expected_text {
-            for entity in container_query.iter() {
-                commands.entity(entity).despawn();
-            }
+            text.0 = expected_text.clone();
         }
     }
 }
-
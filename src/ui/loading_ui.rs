@@ -0,0 +1,71 @@
+use crate::game_mode::LevelGeneration;
+use crate::ui::Px_dynamic;
+use crate::ui::localized_text::LocalizedText;
+use bevy::prelude::*;
+
+pub struct LoadingUIPlugin;
+
+#[derive(Component)]
+struct LoadingIndicator;
+
+impl Plugin for LoadingUIPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, update_loading_indicator);
+    }
+}
+
+/// Tells the player a level is being generated, for as long as it takes.
+fn update_loading_indicator(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    generations: Query<(), With<LevelGeneration>>,
+    indicators: Query<Entity, With<LoadingIndicator>>,
+) {
+    if generations.is_empty() {
+        for entity in indicators.iter() {
+            commands.entity(entity).despawn();
+        }
+        return;
+    }
+
+    if !indicators.is_empty() {
+        return;
+    }
+
+    commands
+        .spawn((
+            LoadingIndicator,
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor::from(Color::srgba(0.0, 0.0, 0.0, 0.5)),
+            GlobalZIndex(10),
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        padding: UiRect::all(Px_dynamic(16.0)),
+                        ..default()
+                    },
+                    BackgroundColor::from(Color::Srgba(Srgba::hex("3a312e").unwrap())),
+                    BorderRadius::all(Px_dynamic(15.0)),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        LocalizedText::new("loading.generating"),
+                        TextFont {
+                            font: asset_server.load("fonts/SpaceGrotesk-Medium.ttf"),
+                            font_size: 32.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                });
+        });
+}
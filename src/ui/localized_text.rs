@@ -0,0 +1,36 @@
+use crate::locale::Strings;
+use bevy::prelude::*;
+use bevy::ui::UiSystem;
+
+pub struct LocalizedTextPlugin;
+
+/// Text read from the string tables by its key, it follows the language of the settings.
+#[derive(Component, Clone, Debug)]
+#[require(Text)]
+pub struct LocalizedText(pub String);
+
+impl LocalizedText {
+    pub fn new(key: impl Into<String>) -> Self {
+        LocalizedText(key.into())
+    }
+}
+
+impl Plugin for LocalizedTextPlugin {
+    fn build(&self, app: &mut App) {
+        // before the text gets measured, so that it never shows its key
+        app.add_systems(PostUpdate, localize_texts.before(UiSystem::Prepare));
+    }
+}
+
+fn localize_texts(strings: Res<Strings>, mut texts: Query<(Ref<LocalizedText>, &mut Text)>) {
+    for (localized, mut text) in texts.iter_mut() {
+        if !strings.is_changed() && !localized.is_changed() {
+            continue;
+        }
+
+        let localized = strings.get(&localized.0);
+        if text.0 != localized {
+            text.0 = localized.to_string();
+        }
+    }
+}
@@ -1,12 +1,24 @@
 use bevy::prelude::Val;
 
-pub(crate) mod battery_ui;
-pub(crate) mod control_ui;
-pub(crate) mod final_screen;
-pub(crate) mod interactive_button;
-pub(crate) mod level_indicator_ui;
-pub(crate) mod win_screen;
+pub mod battery_ui;
+pub mod control_ui;
+pub mod final_screen;
+pub mod focus;
+pub mod hint_ui;
+pub mod interactive_button;
+pub mod level_indicator_ui;
+pub mod loading_ui;
+pub mod localized_text;
+pub mod pause_menu;
+pub mod rover_label;
+pub mod score_ui;
+pub mod settings_menu;
+pub mod tooltip;
+pub mod touch;
+pub mod win_screen;
 
+/// Size `i` on a 1600 pixel wide window, relative to the longer side so that it keeps the same size
+/// when a phone is held upright. The settings menu scales it by `UiScale` once the node is spawned.
 pub fn Px_dynamic(i: f32) -> Val {
-    Val::Vw(i / 1600.0 * 100.0)
+    Val::VMax(i / 1600.0 * 100.0)
 }
@@ -0,0 +1,473 @@
+use crate::game_control::actions::ActionList;
+use crate::game_control::keymap::{KeyAction, gamepad_just_pressed_actions};
+use crate::game_mode::GameMode;
+use crate::gameplay::{ActiveLevel, GameState, LevelSpawnRequestEvent};
+use crate::help::help::OpenHelp;
+use crate::help::tutorial::{ActiveWalkthrough, finish_walkthrough};
+use crate::level::{GRADVM, GRADVM_ONVSTVS};
+use crate::save::SaveData;
+use crate::ui::Px_dynamic;
+use crate::ui::interactive_button::InteractiveButton;
+use crate::ui::localized_text::LocalizedText;
+use crate::ui::settings_menu::OpenSettings;
+use bevy::color::Srgba;
+use bevy::prelude::*;
+
+pub struct PauseMenuPlugin;
+
+/// Page of the pause menu shown over the level, the level is paused while it is open.
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PauseMenu {
+    #[default]
+    Closed,
+    Main,
+    LevelSelect,
+    ConfirmQuit,
+}
+
+impl PauseMenu {
+    pub fn is_open(&self) -> bool {
+        *self != PauseMenu::Closed
+    }
+}
+
+/// Run condition of the systems taking input for the level, which the pause menu holds back.
+pub fn not_paused(pause_menu: Res<PauseMenu>) -> bool {
+    !pause_menu.is_open()
+}
+
+#[derive(Component)]
+pub struct PauseMenuUI;
+
+/// Button over the level opening the pause menu, for players without a keyboard.
+#[derive(Component)]
+struct OpenPauseMenuButton;
+
+#[derive(Component, Clone, Copy)]
+enum PauseButton {
+    Open,
+    Resume,
+    Restart,
+    Settings,
+    LevelSelect,
+    /// Campaign level to play, by its index
+    Level(usize),
+    ReturnToTitle,
+    Quit,
+    ConfirmQuit,
+    Back,
+}
+
+impl Plugin for PauseMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PauseMenu>();
+        app.add_systems(
+            Update,
+            (
+                escape_handler,
+                pause_button_handler.run_if(not_on_title_screen),
+                (render_pause_menu, pause_time).run_if(resource_changed::<PauseMenu>),
+            )
+                .chain(),
+        );
+        app.add_systems(OnExit(GameState::TitleScreen), spawn_open_pause_menu_button);
+        app.add_systems(
+            OnEnter(GameState::TitleScreen),
+            (close_pause_menu, despawn_open_pause_menu_button),
+        );
+    }
+}
+
+fn not_on_title_screen(game_state: Res<State<GameState>>) -> bool {
+    *game_state.get() != GameState::TitleScreen
+}
+
+/// The pause key or the start button closes whatever is open on top, and pauses the level when
+/// nothing is. A walkthrough is skipped.
+#[allow(clippy::too_many_arguments)]
+fn escape_handler(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut save_data: ResMut<SaveData>,
+    mut open_settings: ResMut<OpenSettings>,
+    mut pause_menu: ResMut<PauseMenu>,
+    mut open_help: ResMut<OpenHelp>,
+    mut walkthrough: ResMut<ActiveWalkthrough>,
+    game_state: Res<State<GameState>>,
+) {
+    let pressed = save_data
+        .settings
+        .keymap
+        .just_pressed(KeyAction::Pause, &keyboard_input)
+        || gamepad_just_pressed_actions(gamepads.iter()).contains(&KeyAction::Pause);
+    if !pressed {
+        return;
+    }
+
+    if open_settings.0 {
+        open_settings.0 = false;
+        return;
+    }
+    if *game_state.get() == GameState::TitleScreen {
+        return;
+    }
+
+    if !pause_menu.is_open() {
+        if open_help.is_open() {
+            open_help.close();
+            return;
+        }
+        if walkthrough.is_playing() {
+            finish_walkthrough(&mut walkthrough, &mut save_data);
+            return;
+        }
+    }
+
+    *pause_menu = match *pause_menu {
+        PauseMenu::Closed => PauseMenu::Main,
+        PauseMenu::Main => PauseMenu::Closed,
+        PauseMenu::LevelSelect | PauseMenu::ConfirmQuit => PauseMenu::Main,
+    };
+}
+
+#[allow(clippy::too_many_arguments)]
+fn pause_button_handler(
+    interaction_query: Query<(&Interaction, &PauseButton), Changed<Interaction>>,
+    mut pause_menu: ResMut<PauseMenu>,
+    mut open_settings: ResMut<OpenSettings>,
+    mut action_list: ResMut<ActionList>,
+    mut active_level: ResMut<ActiveLevel>,
+    level_handles: Res<GRADVM_ONVSTVS>,
+    mut level_spawn_request_writer: EventWriter<LevelSpawnRequestEvent>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut app_exit_writer: EventWriter<AppExit>,
+) {
+    for (interaction, button) in interaction_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        match *button {
+            PauseButton::Open => *pause_menu = PauseMenu::Main,
+            PauseButton::Resume => *pause_menu = PauseMenu::Closed,
+            PauseButton::Restart => {
+                // the program is kept, starting the level only adds lists for new units
+                if let Some(level) = active_level.0.clone() {
+                    level_spawn_request_writer.write(LevelSpawnRequestEvent { level });
+                }
+                *pause_menu = PauseMenu::Closed;
+            }
+            PauseButton::Settings => open_settings.0 = true,
+            PauseButton::LevelSelect => *pause_menu = PauseMenu::LevelSelect,
+            PauseButton::Level(index) => {
+                let Some(level) = level_handles.GRADVS.get(index) else {
+                    continue;
+                };
+                // the program of another level makes no sense here
+                clear_program(&mut action_list);
+                level_spawn_request_writer.write(LevelSpawnRequestEvent {
+                    level: level.clone(),
+                });
+                *pause_menu = PauseMenu::Closed;
+            }
+            PauseButton::ReturnToTitle => {
+                clear_program(&mut action_list);
+                active_level.0 = Some(level_handles.GRADVS[0].clone());
+                next_state.set(GameState::TitleScreen);
+                *pause_menu = PauseMenu::Closed;
+            }
+            PauseButton::Quit => *pause_menu = PauseMenu::ConfirmQuit,
+            PauseButton::ConfirmQuit => {
+                app_exit_writer.write(AppExit::Success);
+            }
+            PauseButton::Back => *pause_menu = PauseMenu::Main,
+        }
+    }
+}
+
+fn clear_program(action_list: &mut ActionList) {
+    for actions in action_list.actions.iter_mut() {
+        actions.clear();
+    }
+    action_list.current_selection = 0;
+}
+
+/// Stops the units, the timers and the particles while the menu is open.
+fn pause_time(pause_menu: Res<PauseMenu>, mut time: ResMut<Time<Virtual>>) {
+    if pause_menu.is_open() {
+        time.pause();
+    } else {
+        time.unpause();
+    }
+}
+
+fn close_pause_menu(mut pause_menu: ResMut<PauseMenu>) {
+    pause_menu.set_if_neq(PauseMenu::Closed);
+}
+
+/// Next to the help button, in the top left corner.
+fn spawn_open_pause_menu_button(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let color = Color::Srgba(Srgba::hex("3a312e").unwrap());
+    commands
+        .spawn((
+            Button,
+            OpenPauseMenuButton,
+            PauseButton::Open,
+            Node {
+                position_type: PositionType::Absolute,
+                top: Px_dynamic(12.0),
+                left: Px_dynamic(72.0),
+                width: Px_dynamic(48.0),
+                height: Px_dynamic(48.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor::from(color),
+            BorderRadius::all(Px_dynamic(12.0)),
+            InteractiveButton::simple(color, Color::WHITE, true),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("II"),
+                TextFont {
+                    font: asset_server.load("fonts/SpaceGrotesk-Bold.ttf"),
+                    font_size: 24.0,
+                    ..default()
+                },
+                TextColor(Color::srgba(0.9, 0.9, 0.9, 1.0)),
+            ));
+        });
+}
+
+fn despawn_open_pause_menu_button(
+    mut commands: Commands,
+    button_query: Query<Entity, With<OpenPauseMenuButton>>,
+) {
+    for entity in button_query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn render_pause_menu(
+    mut commands: Commands,
+    pause_menu: Res<PauseMenu>,
+    menu_query: Query<Entity, With<PauseMenuUI>>,
+    asset_server: Res<AssetServer>,
+    game_mode: Res<GameMode>,
+    active_level: Res<ActiveLevel>,
+    levels: Res<Assets<GRADVM>>,
+    level_handles: Res<GRADVM_ONVSTVS>,
+) {
+    for entity in menu_query.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    if !pause_menu.is_open() {
+        return;
+    }
+
+    let current_index = active_level
+        .0
+        .as_ref()
+        .and_then(|handle| levels.get(handle))
+        .map(|level| level.INDEX as usize);
+
+    commands
+        .spawn((
+            PauseMenuUI,
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(0.0),
+                left: Val::Percent(0.0),
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                flex_direction: FlexDirection::Column,
+                row_gap: Px_dynamic(16.0),
+                ..default()
+            },
+            BackgroundColor::from(Color::srgba(0.0, 0.0, 0.0, 0.9)),
+            // above the help and the end of level screens, below the settings
+            ZIndex(2100),
+        ))
+        .with_children(|parent| {
+            let title = match *pause_menu {
+                PauseMenu::LevelSelect => "pause.level_select",
+                PauseMenu::ConfirmQuit => "pause.quit_title",
+                _ => "pause.title",
+            };
+            parent.spawn((
+                LocalizedText::new(title),
+                TextFont {
+                    font: asset_server.load("fonts/SpaceGrotesk-Bold.ttf"),
+                    font_size: 48.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                Node {
+                    margin: UiRect::bottom(Px_dynamic(20.0)),
+                    ..default()
+                },
+            ));
+
+            match *pause_menu {
+                PauseMenu::Main => {
+                    spawn_pause_button(parent, &asset_server, "pause.resume", PauseButton::Resume);
+                    spawn_pause_button(
+                        parent,
+                        &asset_server,
+                        "pause.restart",
+                        PauseButton::Restart,
+                    );
+                    spawn_pause_button(
+                        parent,
+                        &asset_server,
+                        "pause.settings",
+                        PauseButton::Settings,
+                    );
+                    // the other modes make up their levels as they go
+                    if *game_mode == GameMode::Campaign {
+                        spawn_pause_button(
+                            parent,
+                            &asset_server,
+                            "pause.level_select",
+                            PauseButton::LevelSelect,
+                        );
+                    }
+                    spawn_pause_button(
+                        parent,
+                        &asset_server,
+                        "pause.return_to_title",
+                        PauseButton::ReturnToTitle,
+                    );
+                    // a web page can't be quit
+                    if !cfg!(target_arch = "wasm32") {
+                        spawn_pause_button(parent, &asset_server, "pause.quit", PauseButton::Quit);
+                    }
+                }
+                PauseMenu::LevelSelect => {
+                    parent
+                        .spawn((Node {
+                            width: Px_dynamic(720.0),
+                            flex_direction: FlexDirection::Row,
+                            flex_wrap: FlexWrap::Wrap,
+                            justify_content: JustifyContent::Center,
+                            row_gap: Px_dynamic(12.0),
+                            column_gap: Px_dynamic(12.0),
+                            ..default()
+                        },))
+                        .with_children(|parent| {
+                            for index in 0..level_handles.GRADVS.len() {
+                                spawn_level_button(
+                                    parent,
+                                    &asset_server,
+                                    index,
+                                    current_index == Some(index),
+                                );
+                            }
+                        });
+                    spawn_pause_button(parent, &asset_server, "pause.back", PauseButton::Back);
+                }
+                PauseMenu::ConfirmQuit => {
+                    parent.spawn((
+                        LocalizedText::new("pause.quit_warning"),
+                        TextFont {
+                            font: asset_server.load("fonts/SpaceGrotesk-Light.ttf"),
+                            font_size: 28.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                    spawn_pause_button(
+                        parent,
+                        &asset_server,
+                        "pause.quit",
+                        PauseButton::ConfirmQuit,
+                    );
+                    spawn_pause_button(parent, &asset_server, "pause.cancel", PauseButton::Back);
+                }
+                PauseMenu::Closed => {}
+            }
+        });
+}
+
+fn spawn_pause_button(
+    parent: &mut ChildSpawnerCommands,
+    asset_server: &AssetServer,
+    label_key: &str,
+    button: PauseButton,
+) {
+    parent
+        .spawn((
+            Button,
+            button,
+            Node {
+                width: Px_dynamic(300.0),
+                height: Px_dynamic(60.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor::from(Color::Srgba(Srgba::hex("3a312e").unwrap())),
+            BorderRadius::all(Px_dynamic(15.0)),
+            InteractiveButton::simple(
+                Color::Srgba(Srgba::hex("3a312e").unwrap()),
+                Color::WHITE,
+                true,
+            ),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                LocalizedText::new(label_key),
+                TextFont {
+                    font: asset_server.load("fonts/SpaceGrotesk-Light.ttf"),
+                    font_size: 32.0,
+                    ..default()
+                },
+                TextColor(Color::srgba(0.9, 0.9, 0.9, 1.0)),
+            ));
+        });
+}
+
+fn spawn_level_button(
+    parent: &mut ChildSpawnerCommands,
+    asset_server: &AssetServer,
+    index: usize,
+    is_current: bool,
+) {
+    let color = if is_current {
+        Color::Srgba(Srgba::hex("6b5a54").unwrap())
+    } else {
+        Color::Srgba(Srgba::hex("3a312e").unwrap())
+    };
+
+    parent
+        .spawn((
+            Button,
+            PauseButton::Level(index),
+            Node {
+                width: Px_dynamic(60.0),
+                height: Px_dynamic(60.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor::from(color),
+            BorderRadius::all(Px_dynamic(12.0)),
+            InteractiveButton::simple(color, Color::WHITE, true),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new((index + 1).to_string()),
+                TextFont {
+                    font: asset_server.load("fonts/SpaceGrotesk-Medium.ttf"),
+                    font_size: 28.0,
+                    ..default()
+                },
+                TextColor(Color::srgba(0.9, 0.9, 0.9, 1.0)),
+            ));
+        });
+}
@@ -0,0 +1,123 @@
+use crate::gameplay::GameState;
+use crate::palette::unit_label;
+use crate::rover::RoverEntity;
+use crate::ui::Px_dynamic;
+use crate::ui::control_ui::UIRoverColors;
+use bevy::color::Luminance;
+use bevy::prelude::*;
+
+pub struct RoverLabelPlugin;
+
+/// Height above a unit its badge floats at
+const LABEL_HEIGHT: f32 = 0.6;
+
+/// Badge with the number of a unit, following it around the board so that units can be told
+/// apart without relying on their colour.
+#[derive(Component)]
+struct RoverLabel(Entity);
+
+impl Plugin for RoverLabelPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (spawn_rover_labels, move_rover_labels)
+                .chain()
+                .run_if(not(in_state(GameState::TitleScreen))),
+        );
+        app.add_systems(OnEnter(GameState::TitleScreen), cleanup_rover_labels);
+    }
+}
+
+fn spawn_rover_labels(
+    mut commands: Commands,
+    rovers: Query<(Entity, &RoverEntity)>,
+    labels: Query<(Entity, &RoverLabel)>,
+    colors: Res<UIRoverColors>,
+    asset_server: Res<AssetServer>,
+) {
+    // labels are made again when the palette changes
+    let recolor = colors.is_changed();
+    for (entity, label) in labels.iter() {
+        if recolor || !rovers.contains(label.0) {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    for (rover_entity, rover) in rovers.iter() {
+        if !recolor && labels.iter().any(|(_, label)| label.0 == rover_entity) {
+            continue;
+        }
+
+        let color = colors.color(rover.identifier as usize);
+        let text_color = if color.luminance() > 0.5 {
+            Color::BLACK
+        } else {
+            Color::WHITE
+        };
+
+        commands
+            .spawn((
+                RoverLabel(rover_entity),
+                Node {
+                    position_type: PositionType::Absolute,
+                    width: Px_dynamic(26.0),
+                    height: Px_dynamic(26.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    border: UiRect::all(Px_dynamic(2.0)),
+                    ..default()
+                },
+                BackgroundColor(color),
+                BorderColor(Color::BLACK),
+                BorderRadius::MAX,
+                // hidden until it is placed over its unit
+                Visibility::Hidden,
+                Pickable::IGNORE,
+            ))
+            .with_child((
+                Text::new(unit_label(rover.identifier as usize)),
+                TextFont {
+                    font: asset_server.load("fonts/SpaceGrotesk-Bold.ttf"),
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(text_color),
+                Pickable::IGNORE,
+            ));
+    }
+}
+
+fn move_rover_labels(
+    mut labels: Query<(&RoverLabel, &mut Node, &mut Visibility, &ComputedNode)>,
+    rovers: Query<&GlobalTransform, With<RoverEntity>>,
+    cameras: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    ui_scale: Res<UiScale>,
+) {
+    let Ok((camera, camera_transform)) = cameras.single() else {
+        return;
+    };
+
+    for (label, mut node, mut visibility, computed) in labels.iter_mut() {
+        let position = rovers.get(label.0).ok().and_then(|transform| {
+            let above = transform.translation() + Vec3::Y * LABEL_HEIGHT;
+            camera.world_to_viewport(camera_transform, above).ok()
+        });
+        let Some(position) = position else {
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        };
+
+        // centred over the unit, `Val::Px` gets multiplied by the UI scale
+        let position = position / ui_scale.0;
+        let size = computed.size() * computed.inverse_scale_factor();
+        node.left = Val::Px(position.x - size.x / 2.0);
+        node.top = Val::Px(position.y - size.y / 2.0);
+        visibility.set_if_neq(Visibility::Inherited);
+    }
+}
+
+fn cleanup_rover_labels(mut commands: Commands, labels: Query<Entity, With<RoverLabel>>) {
+    for entity in labels.iter() {
+        commands.entity(entity).despawn();
+    }
+}
@@ -0,0 +1,91 @@
+use crate::locale::Strings;
+use crate::score::{LastScore, MAX_STARS};
+use bevy::prelude::*;
+
+/// Adds the stars earned on the last solved level and how its score compares to the par.
+pub fn spawn_score(
+    parent: &mut ChildSpawnerCommands,
+    last_score: &LastScore,
+    asset_server: &AssetServer,
+    strings: &Strings,
+    text_color: Color,
+) {
+    let Some(score) = last_score.score else {
+        return;
+    };
+
+    if last_score.par.is_some() {
+        parent
+            .spawn(Node {
+                flex_direction: FlexDirection::Row,
+                column_gap: Val::Px(10.0),
+                margin: UiRect::bottom(Val::Px(20.0)),
+                ..default()
+            })
+            .with_children(|parent| {
+                for star in 0..MAX_STARS {
+                    let color = if star < last_score.stars {
+                        Color::WHITE
+                    } else {
+                        Color::srgba(0.3, 0.3, 0.3, 0.6)
+                    };
+
+                    parent.spawn((
+                        ImageNode {
+                            image: asset_server.load("mineral_icon.png"),
+                            color,
+                            ..default()
+                        },
+                        Node {
+                            width: Val::Px(64.0),
+                            height: Val::Px(64.0),
+                            ..default()
+                        },
+                    ));
+                }
+            });
+    }
+
+    let mut lines = vec![match last_score.par {
+        Some(par) => strings.format(
+            "score.with_par",
+            &[
+                ("commands", &score.commands),
+                ("commands_par", &par.MANDATA),
+                ("turns", &score.turns),
+                ("turns_par", &par.VICES),
+                ("battery", &score.battery_used),
+                ("battery_par", &par.PILA),
+            ],
+        ),
+        None => strings.format(
+            "score.without_par",
+            &[
+                ("commands", &score.commands),
+                ("turns", &score.turns),
+                ("battery", &score.battery_used),
+            ],
+        ),
+    }];
+    if score.hints > 0 {
+        lines.push(strings.plural("score.hints", score.hints as usize));
+    }
+    if last_score.is_new_best {
+        lines.push(strings.get("score.new_best").to_string());
+    }
+
+    parent.spawn((
+        Text::new(lines.join("\n")),
+        TextFont {
+            font: asset_server.load("fonts/SpaceGrotesk-Regular.ttf"),
+            font_size: 28.0,
+            ..default()
+        },
+        TextColor(text_color),
+        TextLayout::new_with_justify(JustifyText::Center),
+        Node {
+            margin: UiRect::bottom(Val::Px(40.0)),
+            ..default()
+        },
+    ));
+}
@@ -0,0 +1,595 @@
+use crate::game_control::keymap::KeyAction;
+use crate::locale::{Language, Strings};
+use crate::palette::Palette;
+use crate::save::SaveData;
+use crate::settings::{GraphicsQuality, MAX_UI_SCALE, MIN_UI_SCALE, Settings};
+use crate::ui::interactive_button::InteractiveButton;
+use crate::ui::localized_text::LocalizedText;
+use crate::ui::Px_dynamic;
+use bevy::color::Srgba;
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+use bevy::ui::UiSystem;
+
+pub struct SettingsMenuPlugin;
+
+/// Whether the settings dialog is open, from the title screen or the pause menu.
+#[derive(Resource, Default)]
+pub struct OpenSettings(pub bool);
+
+#[derive(Component)]
+pub struct SettingsDialog;
+
+#[derive(Component)]
+struct CloseSettingsButton;
+
+/// Moves `setting` by `step`, to the previous value below 0 and to the next one above.
+#[derive(Component)]
+struct SettingButton {
+    setting: Setting,
+    step: i8,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Setting {
+    Language,
+    MasterVolume,
+    MusicVolume,
+    SfxVolume,
+    Graphics,
+    UiScale,
+    ReducedMotion,
+    Palette,
+}
+
+/// Picks the action of the keys row, `step` actions away.
+#[derive(Component)]
+struct KeyActionButton {
+    step: i8,
+}
+
+/// Binds the next key pressed to the action of the keys row.
+#[derive(Component)]
+struct RebindButton;
+
+/// Action shown on the keys row, as an index of `REBINDABLE_ACTIONS`, and whether the next key
+/// pressed is bound to it.
+#[derive(Resource, Default)]
+struct KeyRebinding {
+    action: usize,
+    listening: bool,
+}
+
+/// Actions that can be bound to another key, along with their name. Units keep their number, and
+/// the pause menu keeps Escape, which gives up on rebinding.
+const REBINDABLE_ACTIONS: [(KeyAction, &str); 11] = [
+    (KeyAction::Up, "keys.up"),
+    (KeyAction::Down, "keys.down"),
+    (KeyAction::Left, "keys.left"),
+    (KeyAction::Right, "keys.right"),
+    (KeyAction::Wait, "command.wait"),
+    (KeyAction::Scout, "command.scout"),
+    (KeyAction::DropPack, "command.drop_pack"),
+    (KeyAction::RemoveLast, "keys.remove_last"),
+    (KeyAction::ClearAll, "keys.clear_all"),
+    (KeyAction::Execute, "control.execute"),
+    (KeyAction::NextUnit, "keys.next_unit"),
+];
+
+const VOLUME_STEP: f32 = 0.1;
+const UI_SCALE_STEP: f32 = 0.125;
+
+impl Setting {
+    const ALL: [Setting; 8] = [
+        Setting::Language,
+        Setting::MasterVolume,
+        Setting::MusicVolume,
+        Setting::SfxVolume,
+        Setting::Graphics,
+        Setting::UiScale,
+        Setting::ReducedMotion,
+        Setting::Palette,
+    ];
+
+    fn label_key(&self) -> &'static str {
+        match self {
+            Setting::Language => "settings.language",
+            Setting::MasterVolume => "settings.master_volume",
+            Setting::MusicVolume => "settings.music_volume",
+            Setting::SfxVolume => "settings.sfx_volume",
+            Setting::Graphics => "settings.graphics",
+            Setting::UiScale => "settings.ui_scale",
+            Setting::ReducedMotion => "settings.reduced_motion",
+            Setting::Palette => "settings.palette",
+        }
+    }
+
+    fn value(&self, settings: &Settings, strings: &Strings) -> String {
+        let percent = |value: f32| format!("{}%", (value * 100.0).round());
+        match self {
+            // always in its own language
+            Setting::Language => settings.language.name().to_string(),
+            Setting::MasterVolume => percent(settings.master_volume),
+            Setting::MusicVolume => percent(settings.music_volume),
+            Setting::SfxVolume => percent(settings.sfx_volume),
+            Setting::Graphics => strings.get(settings.graphics.key()).to_string(),
+            Setting::UiScale => percent(settings.ui_scale),
+            Setting::ReducedMotion => {
+                let key = if settings.reduced_motion {
+                    "settings.on"
+                } else {
+                    "settings.off"
+                };
+                strings.get(key).to_string()
+            }
+            Setting::Palette => strings.get(settings.palette.key()).to_string(),
+        }
+    }
+
+    fn change(&self, settings: &mut Settings, step: i8) {
+        let step_volume = |volume: &mut f32| {
+            *volume = (*volume + step as f32 * VOLUME_STEP).clamp(0.0, 1.0);
+        };
+        match self {
+            Setting::Language => {
+                settings.language = cycle(&Language::ALL, settings.language, step);
+            }
+            Setting::MasterVolume => step_volume(&mut settings.master_volume),
+            Setting::MusicVolume => step_volume(&mut settings.music_volume),
+            Setting::SfxVolume => step_volume(&mut settings.sfx_volume),
+            Setting::Graphics => {
+                settings.graphics = cycle(&GraphicsQuality::ALL, settings.graphics, step);
+            }
+            Setting::UiScale => {
+                settings.ui_scale = (settings.ui_scale + step as f32 * UI_SCALE_STEP)
+                    .clamp(MIN_UI_SCALE, MAX_UI_SCALE);
+            }
+            Setting::ReducedMotion => settings.reduced_motion = !settings.reduced_motion,
+            Setting::Palette => settings.palette = cycle(&Palette::ALL, settings.palette, step),
+        }
+    }
+}
+
+/// The value `step` places away from `current` in `values`, wrapping around.
+fn cycle<T: Copy + PartialEq>(values: &[T], current: T, step: i8) -> T {
+    let index = values
+        .iter()
+        .position(|value| *value == current)
+        .unwrap_or(0) as isize;
+    values[(index + step as isize).rem_euclid(values.len() as isize) as usize]
+}
+
+impl Plugin for SettingsMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<OpenSettings>();
+        app.init_resource::<KeyRebinding>();
+        // the key is taken before anything else in the game can act on it
+        app.add_systems(PreUpdate, capture_rebound_key.after(InputSystem));
+        app.add_systems(
+            Update,
+            (
+                setting_button_handler,
+                key_rebinding_handler,
+                close_settings_handler,
+                apply_ui_scale.run_if(resource_changed::<SaveData>),
+                render_settings_dialog.run_if(
+                    resource_changed::<OpenSettings>
+                        .or(resource_changed::<SaveData>)
+                        .or(resource_changed::<Strings>)
+                        .or(resource_changed::<KeyRebinding>),
+                ),
+            )
+                .chain(),
+        );
+        app.add_systems(PostUpdate, scale_dynamic_sizes.before(UiSystem::Layout));
+    }
+}
+
+fn setting_button_handler(
+    interaction_query: Query<(&Interaction, &SettingButton), Changed<Interaction>>,
+    mut save_data: ResMut<SaveData>,
+) {
+    for (interaction, button) in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            button.setting.change(&mut save_data.settings, button.step);
+        }
+    }
+}
+
+fn key_rebinding_handler(
+    action_buttons: Query<(&Interaction, &KeyActionButton), Changed<Interaction>>,
+    rebind_buttons: Query<&Interaction, (Changed<Interaction>, With<RebindButton>)>,
+    mut key_rebinding: ResMut<KeyRebinding>,
+) {
+    for (interaction, button) in action_buttons.iter() {
+        if *interaction == Interaction::Pressed {
+            key_rebinding.action = (key_rebinding.action as isize + button.step as isize)
+                .rem_euclid(REBINDABLE_ACTIONS.len() as isize)
+                as usize;
+            key_rebinding.listening = false;
+        }
+    }
+
+    if rebind_buttons
+        .iter()
+        .any(|interaction| *interaction == Interaction::Pressed)
+    {
+        key_rebinding.listening = true;
+    }
+}
+
+/// Binds the first key pressed while listening, escape giving up instead.
+fn capture_rebound_key(
+    mut keyboard_input: ResMut<ButtonInput<KeyCode>>,
+    mut key_rebinding: ResMut<KeyRebinding>,
+    mut save_data: ResMut<SaveData>,
+    open_settings: Res<OpenSettings>,
+) {
+    if !key_rebinding.listening {
+        return;
+    }
+    if !open_settings.0 {
+        key_rebinding.listening = false;
+        return;
+    }
+
+    let Some(key) = keyboard_input.get_just_pressed().next().copied() else {
+        return;
+    };
+    keyboard_input.clear_just_pressed(key);
+    key_rebinding.listening = false;
+    if key != KeyCode::Escape {
+        let (action, _) = REBINDABLE_ACTIONS[key_rebinding.action];
+        save_data.settings.keymap.rebind(action, key);
+    }
+}
+
+fn close_settings_handler(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<CloseSettingsButton>)>,
+    mut open_settings: ResMut<OpenSettings>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            open_settings.0 = false;
+        }
+    }
+}
+
+/// Scales the text and the `Val::Px` sizes through `UiScale`, `scale_dynamic_sizes` takes care of
+/// the `Px_dynamic` ones.
+fn apply_ui_scale(save_data: Res<SaveData>, mut ui_scale: ResMut<UiScale>) {
+    let scale = save_data.settings.ui_scale;
+    if ui_scale.0 != scale {
+        ui_scale.0 = scale;
+    }
+}
+
+/// Scales the `Px_dynamic` sizes by `UiScale`, which leaves viewport units alone: those of new
+/// nodes, and those of every node when the scale changes.
+fn scale_dynamic_sizes(
+    ui_scale: Res<UiScale>,
+    mut applied_scale: Local<Option<f32>>,
+    mut nodes: Query<(&mut Node, Option<&mut BorderRadius>, Option<&mut Outline>)>,
+) {
+    let previous = applied_scale.replace(ui_scale.0).unwrap_or(1.0);
+    if previous == ui_scale.0 && ui_scale.0 == 1.0 {
+        return;
+    }
+
+    for (mut node, border_radius, outline) in nodes.iter_mut() {
+        let factor = if node.is_added() {
+            ui_scale.0
+        } else if previous != ui_scale.0 {
+            ui_scale.0 / previous
+        } else {
+            continue;
+        };
+
+        let node = node.as_mut();
+        for val in [
+            &mut node.left,
+            &mut node.right,
+            &mut node.top,
+            &mut node.bottom,
+            &mut node.width,
+            &mut node.height,
+            &mut node.min_width,
+            &mut node.min_height,
+            &mut node.max_width,
+            &mut node.max_height,
+            &mut node.flex_basis,
+            &mut node.row_gap,
+            &mut node.column_gap,
+        ] {
+            scale_dynamic_size(val, factor);
+        }
+        for rect in [&mut node.margin, &mut node.padding, &mut node.border] {
+            for val in [
+                &mut rect.left,
+                &mut rect.right,
+                &mut rect.top,
+                &mut rect.bottom,
+            ] {
+                scale_dynamic_size(val, factor);
+            }
+        }
+        if let Some(mut border_radius) = border_radius {
+            let border_radius = border_radius.as_mut();
+            for val in [
+                &mut border_radius.top_left,
+                &mut border_radius.top_right,
+                &mut border_radius.bottom_left,
+                &mut border_radius.bottom_right,
+            ] {
+                scale_dynamic_size(val, factor);
+            }
+        }
+        if let Some(mut outline) = outline {
+            scale_dynamic_size(&mut outline.width, factor);
+            scale_dynamic_size(&mut outline.offset, factor);
+        }
+    }
+}
+
+fn scale_dynamic_size(val: &mut Val, factor: f32) {
+    if let Val::VMax(size) = val {
+        *size *= factor;
+    }
+}
+
+fn render_settings_dialog(
+    mut commands: Commands,
+    open_settings: Res<OpenSettings>,
+    save_data: Res<SaveData>,
+    key_rebinding: Res<KeyRebinding>,
+    dialog_query: Query<Entity, With<SettingsDialog>>,
+    asset_server: Res<AssetServer>,
+    strings: Res<Strings>,
+) {
+    for entity in dialog_query.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    if open_settings.0 {
+        spawn_settings_dialog(
+            &mut commands,
+            &asset_server,
+            &save_data.settings,
+            &key_rebinding,
+            &strings,
+        );
+    }
+}
+
+fn spawn_settings_dialog(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    settings: &Settings,
+    key_rebinding: &KeyRebinding,
+    strings: &Strings,
+) {
+    let font = asset_server.load("fonts/SpaceGrotesk-Light.ttf");
+    commands
+        .spawn((
+            SettingsDialog,
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(0.0),
+                left: Val::Percent(0.0),
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                flex_direction: FlexDirection::Column,
+                row_gap: Px_dynamic(16.0),
+                ..default()
+            },
+            BackgroundColor::from(Color::srgba(0.0, 0.0, 0.0, 0.9)),
+            // above the help and the pause menu it can be opened from
+            ZIndex(2200),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                LocalizedText::new("settings.title"),
+                TextFont {
+                    font: asset_server.load("fonts/SpaceGrotesk-Bold.ttf"),
+                    font_size: 48.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+
+            for setting in Setting::ALL {
+                parent
+                    .spawn((Node {
+                        width: Px_dynamic(640.0),
+                        flex_direction: FlexDirection::Row,
+                        align_items: AlignItems::Center,
+                        column_gap: Px_dynamic(12.0),
+                        ..default()
+                    },))
+                    .with_children(|parent| {
+                        parent.spawn((
+                            LocalizedText::new(setting.label_key()),
+                            TextFont {
+                                font: font.clone(),
+                                font_size: 28.0,
+                                ..default()
+                            },
+                            TextColor(Color::WHITE),
+                            Node {
+                                flex_grow: 1.0,
+                                ..default()
+                            },
+                        ));
+                        spawn_settings_button(
+                            parent,
+                            asset_server,
+                            Text::new("<"),
+                            SettingButton { setting, step: -1 },
+                        );
+                        parent.spawn((
+                            Text::new(setting.value(settings, strings)),
+                            TextFont {
+                                font: font.clone(),
+                                font_size: 28.0,
+                                ..default()
+                            },
+                            TextColor(Color::WHITE),
+                            TextLayout::new_with_justify(JustifyText::Center),
+                            Node {
+                                width: Px_dynamic(200.0),
+                                justify_content: JustifyContent::Center,
+                                ..default()
+                            },
+                        ));
+                        spawn_settings_button(
+                            parent,
+                            asset_server,
+                            Text::new(">"),
+                            SettingButton { setting, step: 1 },
+                        );
+                    });
+            }
+
+            let (action, action_name) = REBINDABLE_ACTIONS[key_rebinding.action];
+            let keys = if key_rebinding.listening {
+                strings.get("settings.press_key").to_string()
+            } else {
+                settings.keymap.describe(action)
+            };
+            parent
+                .spawn((Node {
+                    width: Px_dynamic(640.0),
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
+                    column_gap: Px_dynamic(12.0),
+                    ..default()
+                },))
+                .with_children(|parent| {
+                    parent.spawn((
+                        LocalizedText::new("settings.keys"),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 28.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                        Node {
+                            flex_grow: 1.0,
+                            ..default()
+                        },
+                    ));
+                    spawn_settings_button(
+                        parent,
+                        asset_server,
+                        Text::new("<"),
+                        KeyActionButton { step: -1 },
+                    );
+                    parent.spawn((
+                        Text::new(strings.get(action_name)),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 28.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                        TextLayout::new_with_justify(JustifyText::Center),
+                        Node {
+                            width: Px_dynamic(200.0),
+                            justify_content: JustifyContent::Center,
+                            ..default()
+                        },
+                    ));
+                    spawn_settings_button(
+                        parent,
+                        asset_server,
+                        Text::new(">"),
+                        KeyActionButton { step: 1 },
+                    );
+                });
+            parent
+                .spawn((Node {
+                    width: Px_dynamic(640.0),
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
+                    column_gap: Px_dynamic(12.0),
+                    ..default()
+                },))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new(keys),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 28.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                        TextLayout::new_with_justify(JustifyText::Right),
+                        Node {
+                            flex_grow: 1.0,
+                            ..default()
+                        },
+                    ));
+                    spawn_settings_button(
+                        parent,
+                        asset_server,
+                        LocalizedText::new("settings.rebind"),
+                        RebindButton,
+                    );
+                });
+
+            parent
+                .spawn((Node {
+                    margin: UiRect::top(Px_dynamic(20.0)),
+                    ..default()
+                },))
+                .with_children(|parent| {
+                    spawn_settings_button(
+                        parent,
+                        asset_server,
+                        LocalizedText::new("settings.close"),
+                        CloseSettingsButton,
+                    );
+                });
+        });
+}
+
+fn spawn_settings_button(
+    parent: &mut ChildSpawnerCommands,
+    asset_server: &AssetServer,
+    label: impl Bundle,
+    marker: impl Bundle,
+) {
+    parent
+        .spawn((
+            Button,
+            marker,
+            Node {
+                min_width: Px_dynamic(60.0),
+                height: Px_dynamic(50.0),
+                padding: UiRect::horizontal(Px_dynamic(15.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor::from(Color::Srgba(Srgba::hex("3a312e").unwrap())),
+            BorderRadius::all(Px_dynamic(12.0)),
+            InteractiveButton::simple(
+                Color::Srgba(Srgba::hex("3a312e").unwrap()),
+                Color::WHITE,
+                true,
+            ),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                label,
+                TextFont {
+                    font: asset_server.load("fonts/SpaceGrotesk-Light.ttf"),
+                    font_size: 28.0,
+                    ..default()
+                },
+                TextColor(Color::srgba(0.9, 0.9, 0.9, 1.0)),
+            ));
+        });
+}
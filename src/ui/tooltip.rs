@@ -0,0 +1,96 @@
+use crate::ui::Px_dynamic;
+use crate::ui::focus::ButtonFocus;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+pub struct TooltipPlugin;
+
+/// Text shown next to the pointer while it hovers the button.
+#[derive(Component)]
+pub struct Tooltip(pub String);
+
+#[derive(Component)]
+struct TooltipPanel;
+
+impl Plugin for TooltipPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, show_tooltips);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn show_tooltips(
+    mut commands: Commands,
+    tooltips: Query<(Entity, &Interaction, &Tooltip)>,
+    focus: Res<ButtonFocus>,
+    mut panels: Query<(Entity, &mut Node, &Children), With<TooltipPanel>>,
+    mut texts: Query<&mut Text>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    ui_scale: Res<UiScale>,
+    asset_server: Res<AssetServer>,
+) {
+    let hovered = tooltips
+        .iter()
+        // the button focused with a gamepad isn't where the pointer is
+        .find(|(entity, interaction, _)| {
+            **interaction != Interaction::None && focus.entity != Some(*entity)
+        })
+        .map(|(_, _, tooltip)| tooltip);
+    let cursor = windows
+        .single()
+        .ok()
+        .and_then(|window| window.cursor_position());
+
+    let (Some(tooltip), Some(cursor)) = (hovered, cursor) else {
+        for (entity, _, _) in panels.iter() {
+            commands.entity(entity).despawn();
+        }
+        return;
+    };
+
+    // below and to the right of the pointer, `Val::Px` gets multiplied by the UI scale
+    let cursor = cursor / ui_scale.0;
+    let left = Val::Px(cursor.x + 16.0);
+    let top = Val::Px(cursor.y + 16.0);
+
+    if let Some((_, mut node, children)) = panels.iter_mut().next() {
+        node.left = left;
+        node.top = top;
+        for child in children.iter() {
+            if let Ok(mut text) = texts.get_mut(child)
+                && text.0 != tooltip.0
+            {
+                text.0 = tooltip.0.clone();
+            }
+        }
+        return;
+    }
+
+    commands
+        .spawn((
+            TooltipPanel,
+            Node {
+                position_type: PositionType::Absolute,
+                left,
+                top,
+                padding: UiRect::axes(Px_dynamic(8.0), Px_dynamic(4.0)),
+                ..default()
+            },
+            BackgroundColor::from(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+            BorderRadius::all(Px_dynamic(4.0)),
+            GlobalZIndex(2000),
+            Pickable::IGNORE,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(tooltip.0.clone()),
+                TextFont {
+                    font: asset_server.load("fonts/SpaceGrotesk-Light.ttf"),
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                Pickable::IGNORE,
+            ));
+        });
+}
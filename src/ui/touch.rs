@@ -0,0 +1,64 @@
+use crate::game_control::actions::ActionList;
+use crate::gameplay::{GameState, TILE_SIZE};
+use crate::rover::RoverEntity;
+use bevy::input::touch::Touch;
+use bevy::picking::events::{Click, Pointer};
+use bevy::prelude::*;
+
+pub struct TouchPlugin;
+
+/// How far a finger can move and still tap rather than swipe, in logical pixels
+pub const TAP_DISTANCE: f32 = 12.0;
+
+impl Plugin for TouchPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(select_rover_near_tap);
+    }
+}
+
+pub fn is_tap(touch: &Touch) -> bool {
+    touch.distance().length() < TAP_DISTANCE
+}
+
+/// Whether the window is taller than wide, as a phone held upright. The control panel goes at the
+/// bottom of the screen then.
+pub fn is_portrait(window: &Window) -> bool {
+    window.height() > window.width()
+}
+
+/// Selects the rover closest to where the board was tapped, rovers are small targets for a finger.
+fn select_rover_near_tap(
+    tap: Trigger<Pointer<Click>>,
+    rovers: Query<(&RoverEntity, &Transform)>,
+    mut action_list: ResMut<ActionList>,
+    mut action_writer: EventWriter<ActionList>,
+    game_state: Res<State<GameState>>,
+) {
+    // only once per tap, not for each entity it bubbles up to
+    if !tap.pointer_id.is_touch()
+        || tap.target() != tap.event().target
+        || *game_state.get() != GameState::Programming
+    {
+        return;
+    }
+    // taps on the UI have no position in the world
+    let Some(position) = tap.hit.position else {
+        return;
+    };
+
+    let nearest = rovers
+        .iter()
+        .map(|(rover, transform)| {
+            let distance = transform.translation.xz().distance(position.xz());
+            (rover.identifier as usize, distance)
+        })
+        .filter(|(_, distance)| *distance < TILE_SIZE)
+        .min_by(|(_, a), (_, b)| a.total_cmp(b));
+
+    if let Some((rover_index, _)) = nearest
+        && action_list.current_selection != rover_index
+    {
+        action_list.current_selection = rover_index;
+        action_writer.write(action_list.clone());
+    }
+}
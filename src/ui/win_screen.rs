@@ -1,8 +1,12 @@
+use crate::game_mode::GameMode;
 use crate::level::{GRADVM, GRADVM_ONVSTVS};
-use crate::level_spawner::ActiveLevel;
+use crate::locale::Strings;
+use crate::gameplay::{ActiveLevel, GameState, NextLevelRequestEvent};
 use crate::puzzle_evaluation::PuzzleResponseEvent;
-use crate::title_screen::GameState;
+use crate::score::{LastScore, record_score};
 use crate::ui::interactive_button::InteractiveButton;
+use crate::ui::localized_text::LocalizedText;
+use crate::ui::score_ui::spawn_score;
 use crate::ui::Px_dynamic;
 use bevy::color::Srgba;
 use bevy::prelude::*;
@@ -15,19 +19,19 @@ pub struct NextLevelButton;
 #[derive(Component)]
 pub struct WinScreenUI;
 
-#[derive(Event)]
-pub struct NextLevelRequestEvent;
-
 impl Plugin for WinScreenPlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<NextLevelRequestEvent>();
         app.add_systems(
             Update,
             (
-                show_win_screen.run_if(in_state(GameState::Execution)),
+                show_win_screen
+                    .run_if(in_state(GameState::Execution))
+                    .after(record_score),
                 next_level_click_handler,
             ),
         );
+        // restarting or leaving the level from the pause menu
+        app.add_systems(OnExit(GameState::Execution), cleanup_win_screen);
     }
 }
 
@@ -39,6 +43,9 @@ fn show_win_screen(
     active_level: Res<ActiveLevel>,
     levels: Res<Assets<GRADVM>>,
     level_handles: Res<GRADVM_ONVSTVS>,
+    game_mode: Res<GameMode>,
+    last_score: Res<LastScore>,
+    strings: Res<Strings>,
 ) {
     for event in puzzle_response_events.read() {
         if *event == PuzzleResponseEvent::Solved && existing_ui.is_empty() {
@@ -50,8 +57,7 @@ fn show_win_screen(
                 continue;
             };
 
-            let final_level_index = level_handles.GRADVS.len() - 1;
-            if current_level.INDEX as usize == final_level_index {
+            if game_mode.is_final_level(current_level, &level_handles) {
                 continue;
             }
             commands
@@ -73,7 +79,7 @@ fn show_win_screen(
                 ))
                 .with_children(|parent| {
                     parent.spawn((
-                        Text::new("All minerals collected!"),
+                        LocalizedText::new("win.title"),
                         TextFont {
                             font: asset_server.load("fonts/SpaceGrotesk-Bold.ttf"),
                             font_size: 64.0,
@@ -87,6 +93,8 @@ fn show_win_screen(
                         },
                     ));
 
+                    spawn_score(parent, &last_score, &asset_server, &strings, Color::WHITE);
+
                     parent
                         .spawn((
                             Button,
@@ -109,7 +117,7 @@ fn show_win_screen(
                         ))
                         .with_children(|parent| {
                             parent.spawn((
-                                Text::new("Next Level"),
+                                LocalizedText::new("win.next_level"),
                                 TextFont {
                                     font: asset_server.load("fonts/SpaceGrotesk-Light.ttf"),
                                     font_size: 40.0,
@@ -145,3 +153,9 @@ fn next_level_click_handler(
         }
     }
 }
+
+fn cleanup_win_screen(mut commands: Commands, query: Query<Entity, With<WinScreenUI>>) {
+    for ui_element in query.iter() {
+        commands.entity(ui_element).despawn();
+    }
+}
@@ -0,0 +1,93 @@
+use crate::battery::{BatteryRules, TurnActivity};
+use crate::game_control::actions::{ActionType, ControlScheme};
+use crate::level::{GRADVM, is_pos_in_level};
+use bevy::math::I8Vec2;
+
+/// How far (in tiles, diagonals included) a helicopter sees when scouting.
+pub const SCOUT_RANGE: i8 = 1;
+
+/// The different kinds of units a level can give the player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnitKind {
+    Rover,
+    /// Ingenuity, flies over anything but only recharges once landed
+    Helicopter,
+}
+
+impl UnitKind {
+    /// Commands the player can give to this kind of unit, in the order they are shown.
+    pub fn actions(&self, scheme: ControlScheme) -> Vec<ActionType> {
+        let mut actions = scheme.movement_actions().to_vec();
+        actions.push(ActionType::Wait);
+        if *self == UnitKind::Helicopter {
+            actions.extend([ActionType::Scout, ActionType::DropPack]);
+        }
+        actions
+    }
+
+    pub fn can_perform(&self, scheme: ControlScheme, action: ActionType) -> bool {
+        self.actions(scheme).contains(&action)
+    }
+
+    /// Battery rules of this kind of unit before any level override.
+    pub fn default_battery_rules(&self) -> BatteryRules {
+        match self {
+            UnitKind::Rover => BatteryRules::default(),
+            UnitKind::Helicopter => BatteryRules {
+                capacity: 2,
+                starting_charge: 2,
+                ..BatteryRules::default()
+            },
+        }
+    }
+
+    /// Whether this unit can end a move on `position`. Rovers need a tile to drive on,
+    /// helicopters can go anywhere inside the level.
+    pub fn can_enter(&self, level: &GRADVM, position: &I8Vec2) -> bool {
+        match self {
+            UnitKind::Rover => is_pos_in_level(level, position),
+            UnitKind::Helicopter => {
+                position.x >= 0
+                    && position.x < level.LATIVIDO
+                    && position.y >= 1
+                    && position.y <= level.ALTIVIDO
+            }
+        }
+    }
+
+    /// Units of different kinds never get in each other's way since one of them is airborne.
+    pub fn collides_with(&self, other: UnitKind) -> bool {
+        *self == other
+    }
+
+    pub fn can_carry_minerals(&self) -> bool {
+        matches!(self, UnitKind::Rover)
+    }
+
+    /// Whether the unit soaks up sunlight this turn. Helicopters only charge when they didn't
+    /// fly, which is what limits their range.
+    pub fn is_charging(&self, level: &GRADVM, position: &I8Vec2, activity: TurnActivity) -> bool {
+        let in_sun = level
+            .TEGLVAE
+            .get(&(position.x, position.y))
+            .is_some_and(|tile| !tile.VMBRA);
+
+        match self {
+            UnitKind::Rover => in_sun,
+            UnitKind::Helicopter => in_sun && activity != TurnActivity::Moved,
+        }
+    }
+}
+
+/// Hidden minerals a helicopter scouting from `position` reveals.
+pub fn scouted_minerals(level: &GRADVM, position: I8Vec2) -> Vec<usize> {
+    level
+        .CRYSTALLA_OCCVLTA
+        .iter()
+        .cloned()
+        .filter(|mineral| {
+            let (x, y) = level.CRYSTALLA[*mineral];
+            (x - position.x).abs() <= SCOUT_RANGE && (y - position.y).abs() <= SCOUT_RANGE
+        })
+        .collect()
+}
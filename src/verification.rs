@@ -0,0 +1,181 @@
+use crate::game_control::actions::{Action, ActionType};
+use crate::gameplay::{ActiveLevel, GameState, spawn_units};
+use crate::level::{GRADVM, MANDATVM_LITTERAE};
+use crate::puzzle_evaluation::{PuzzleEvaluationPlugin, PuzzleResponseEvent};
+use crate::rover::{ActionListExecute, RoverEntity, RoverPlugin};
+use crate::score::ExecutionStats;
+use crate::simulation::{FailureReason, TurnOutcome, simulate};
+use bevy::asset::AssetPlugin;
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+
+/// Frames a program gets to play out before giving up.
+const MAX_FRAMES: usize = 10_000;
+const FRAME_TIME: Duration = Duration::from_millis(50);
+
+/// Commands of each unit along with what they are expected to do, read from a solution file:
+///
+/// ```text
+/// # level 4, both rovers
+/// 0 UURR
+/// 1 LW
+/// expect solved
+/// ```
+///
+/// Every line gives the commands of a unit as the letters of a @SOLVTIO directive. Units left
+/// out have no commands. `expect failed` marks programs that must fail, `expect solved` is the
+/// default.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolutionProgram {
+    pub actions: Vec<Vec<ActionType>>,
+    pub expect_solved: bool,
+}
+
+impl SolutionProgram {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut program = SolutionProgram {
+            actions: vec![],
+            expect_solved: true,
+        };
+
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = || format!("line {}: {line}", number + 1);
+            let mut words = line.split_whitespace();
+            let first = words.next().ok_or_else(invalid)?;
+            if first == "expect" {
+                program.expect_solved = match words.next() {
+                    Some("solved") => true,
+                    Some("failed") => false,
+                    _ => return Err(invalid()),
+                };
+                continue;
+            }
+
+            let unit: usize = first.parse().map_err(|_| invalid())?;
+            let actions = words
+                .next()
+                .unwrap_or("")
+                .chars()
+                .map(MANDATVM_LITTERAE)
+                .collect::<Option<Vec<ActionType>>>()
+                .ok_or_else(invalid)?;
+            if words.next().is_some() {
+                return Err(invalid());
+            }
+
+            if program.actions.len() <= unit {
+                program.actions.resize(unit + 1, vec![]);
+            }
+            program.actions[unit] = actions;
+        }
+
+        Ok(program)
+    }
+}
+
+/// What happened to a program played through the game's own plugins.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Verification {
+    pub solved: bool,
+    /// Why the program failed, as the units in the game saw it
+    pub failure: Option<FailureReason>,
+    pub turns: u32,
+    /// Charge left in every unit once the program stopped
+    pub batteries: Vec<u8>,
+    /// Whether the simulation used by the solver came to the same outcome as the game, down to
+    /// the reason of a failure
+    pub simulation_agrees: bool,
+}
+
+/// The gameplay systems playing out turns, without a window or a GPU.
+pub fn headless_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        StatesPlugin,
+        InputPlugin,
+    ));
+    app.init_asset::<GRADVM>();
+    app.add_plugins((RoverPlugin, PuzzleEvaluationPlugin));
+    app.insert_state(GameState::Programming);
+    app.insert_resource(ActiveLevel(None));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(FRAME_TIME));
+    app
+}
+
+/// Plays `actions` (one command list per unit) on `level` until it is solved or failed.
+/// Returns `None` if the program never stopped.
+pub fn verify(level: &GRADVM, actions: &[Vec<ActionType>]) -> Option<Verification> {
+    let mut app = headless_app();
+    let handle = app
+        .world_mut()
+        .resource_mut::<Assets<GRADVM>>()
+        .add(level.clone());
+    app.insert_resource(ActiveLevel(Some(handle)));
+    spawn_units(&mut app.world_mut().commands(), level);
+    app.world_mut().flush();
+
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Execution);
+    app.world_mut().send_event(ActionListExecute {
+        action_list: actions
+            .iter()
+            .enumerate()
+            .map(|(unit, actions)| {
+                actions
+                    .iter()
+                    .map(|action| Action {
+                        moves: (*action, unit),
+                    })
+                    .collect()
+            })
+            .collect(),
+    });
+
+    let mut responses = app
+        .world()
+        .resource::<Events<PuzzleResponseEvent>>()
+        .get_cursor();
+    let solved = (0..MAX_FRAMES).find_map(|_| {
+        app.update();
+        responses
+            .read(app.world().resource::<Events<PuzzleResponseEvent>>())
+            .find(|response| **response != PuzzleResponseEvent::InProgress)
+            .map(|response| *response == PuzzleResponseEvent::Solved)
+    })?;
+
+    let mut query = app.world_mut().query::<&RoverEntity>();
+    let mut units: Vec<&RoverEntity> = query.iter(app.world()).collect();
+    units.sort_by_key(|unit| unit.identifier);
+    let batteries = units.iter().map(|unit| unit.battery_level).collect();
+
+    // the game reports the first unit to fail, when none did every unit ran out of commands
+    let failure = (!solved).then(|| {
+        units
+            .iter()
+            .find_map(|unit| unit.failure)
+            .unwrap_or(FailureReason::OutOfCommands)
+    });
+    let outcome = match failure {
+        Some(reason) => TurnOutcome::Failed(reason),
+        None => TurnOutcome::Solved,
+    };
+
+    Some(Verification {
+        solved,
+        failure,
+        turns: app.world().resource::<ExecutionStats>().turns,
+        batteries,
+        simulation_agrees: simulate(level, actions).0 == outcome,
+    })
+}
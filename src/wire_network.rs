@@ -0,0 +1,318 @@
+use bevy::platform::collections::{HashMap, HashSet};
+
+/// A unit standing on a tile, as seen by the power exchange between wired tiles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WireParticipant {
+    pub identifier: u8,
+    pub position: (i8, i8),
+    pub in_shadow: bool,
+    pub battery_level: u8,
+    pub capacity: u8,
+}
+
+/// Splits the wire connections of a level into networks: groups of tiles that are wired
+/// together, directly or through other wired tiles. Tiles are sorted within each network and
+/// networks are sorted by their first tile, so the result doesn't depend on hashing order.
+pub fn wire_networks(connections: &HashMap<(i8, i8), Vec<(i8, i8)>>) -> Vec<Vec<(i8, i8)>> {
+    let mut tiles: Vec<(i8, i8)> = connections.keys().cloned().collect();
+    tiles.sort();
+
+    let mut visited = HashSet::new();
+    let mut networks = Vec::new();
+
+    for tile in tiles {
+        if !visited.insert(tile) {
+            continue;
+        }
+
+        let mut network = vec![];
+        let mut to_visit = vec![tile];
+        while let Some(current) = to_visit.pop() {
+            network.push(current);
+            for neighbour in connections.get(&current).into_iter().flatten() {
+                if visited.insert(*neighbour) {
+                    to_visit.push(*neighbour);
+                }
+            }
+        }
+
+        network.sort();
+        networks.push(network);
+    }
+
+    networks
+}
+
+/// Exchanges power between the units standing on the same wire network. This happens once per
+/// turn, for every network at the same time, and energy is always conserved:
+/// - if some of the units are in the sun, each unit in the shadow that isn't full receives one
+///   unit of charge, taken from the sunlit unit with the most charge left
+/// - if all the units are in the shadow, the one with the most charge gives one unit to the one
+///   with the least, as long as that brings them closer to each other
+/// - if all the units are in the sun, nothing happens since the sun already charges them
+pub fn exchange_power(networks: &[Vec<(i8, i8)>], participants: &mut [WireParticipant]) {
+    for network in networks {
+        let mut members: Vec<usize> = (0..participants.len())
+            .filter(|idx| network.contains(&participants[*idx].position))
+            .collect();
+
+        if members.len() < 2 {
+            continue;
+        }
+
+        // ties are always broken by identifier, independently of the order of the participants
+        members.sort_by_key(|idx| participants[*idx].identifier);
+
+        let (sunlit, shadowed): (Vec<usize>, Vec<usize>) = members
+            .iter()
+            .partition(|idx| !participants[**idx].in_shadow);
+
+        if shadowed.is_empty() {
+            continue;
+        }
+
+        if sunlit.is_empty() {
+            equalise(&shadowed, participants);
+        } else {
+            flow_to_shadow(&sunlit, &shadowed, participants);
+        }
+    }
+}
+
+fn flow_to_shadow(sunlit: &[usize], shadowed: &[usize], participants: &mut [WireParticipant]) {
+    let mut recipients: Vec<usize> = shadowed
+        .iter()
+        .cloned()
+        .filter(|idx| participants[*idx].battery_level < participants[*idx].capacity)
+        .collect();
+    recipients.sort_by_key(|idx| participants[*idx].battery_level);
+
+    for recipient in recipients {
+        // max_by_key returns the last maximum, reversing keeps the lowest identifier on ties
+        let Some(donor) = sunlit
+            .iter()
+            .rev()
+            .cloned()
+            .filter(|idx| participants[*idx].battery_level > 0)
+            .max_by_key(|idx| participants[*idx].battery_level)
+        else {
+            return;
+        };
+
+        participants[donor].battery_level -= 1;
+        participants[recipient].battery_level += 1;
+    }
+}
+
+fn equalise(shadowed: &[usize], participants: &mut [WireParticipant]) {
+    let Some(richest) = shadowed
+        .iter()
+        .rev()
+        .cloned()
+        .max_by_key(|idx| participants[*idx].battery_level)
+    else {
+        return;
+    };
+
+    let Some(poorest) = shadowed
+        .iter()
+        .cloned()
+        .filter(|idx| participants[*idx].battery_level < participants[*idx].capacity)
+        .min_by_key(|idx| participants[*idx].battery_level)
+    else {
+        return;
+    };
+
+    // giving away a unit when the difference is one would only swap the two levels around
+    if participants[richest].battery_level < participants[poorest].battery_level + 2 {
+        return;
+    }
+
+    participants[richest].battery_level -= 1;
+    participants[poorest].battery_level += 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CAPACITY: u8 = 3;
+
+    fn participant(
+        identifier: u8,
+        position: (i8, i8),
+        in_shadow: bool,
+        battery: u8,
+    ) -> WireParticipant {
+        WireParticipant {
+            identifier,
+            position,
+            in_shadow,
+            battery_level: battery,
+            capacity: CAPACITY,
+        }
+    }
+
+    fn pair_network() -> Vec<Vec<(i8, i8)>> {
+        vec![vec![(0, 0), (2, 0)]]
+    }
+
+    fn total(participants: &[WireParticipant]) -> u32 {
+        participants.iter().map(|p| p.battery_level as u32).sum()
+    }
+
+    /// Expected battery levels of a wired pair after the exchange, written out by hand from the
+    /// rules rather than derived from the implementation.
+    fn expected_pair(first: (bool, u8), second: (bool, u8)) -> (u8, u8) {
+        let ((first_shadow, a), (second_shadow, b)) = (first, second);
+        match (first_shadow, second_shadow) {
+            (false, false) => (a, b),
+            (true, false) if a < CAPACITY && b > 0 => (a + 1, b - 1),
+            (false, true) if b < CAPACITY && a > 0 => (a - 1, b + 1),
+            (true, true) if a >= b + 2 => (a - 1, b + 1),
+            (true, true) if b >= a + 2 => (a + 1, b - 1),
+            _ => (a, b),
+        }
+    }
+
+    #[test]
+    fn every_pair_combination() {
+        for first_shadow in [false, true] {
+            for second_shadow in [false, true] {
+                for a in 0..=CAPACITY {
+                    for b in 0..=CAPACITY {
+                        let mut participants = [
+                            participant(0, (0, 0), first_shadow, a),
+                            participant(1, (2, 0), second_shadow, b),
+                        ];
+                        exchange_power(&pair_network(), &mut participants);
+
+                        let expected = expected_pair((first_shadow, a), (second_shadow, b));
+                        assert_eq!(
+                            (participants[0].battery_level, participants[1].battery_level),
+                            expected,
+                            "shadow: ({first_shadow}, {second_shadow}), battery: ({a}, {b})"
+                        );
+                        assert_eq!(total(&participants), (a + b) as u32);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn exchange_does_not_depend_on_participant_order() {
+        for first_shadow in [false, true] {
+            for second_shadow in [false, true] {
+                for a in 0..=CAPACITY {
+                    for b in 0..=CAPACITY {
+                        let mut forward = [
+                            participant(0, (0, 0), first_shadow, a),
+                            participant(1, (2, 0), second_shadow, b),
+                        ];
+                        let mut backward = [forward[1], forward[0]];
+                        exchange_power(&pair_network(), &mut forward);
+                        exchange_power(&pair_network(), &mut backward);
+
+                        assert_eq!(forward[0], backward[1]);
+                        assert_eq!(forward[1], backward[0]);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn unwired_units_are_left_alone() {
+        let mut participants = [
+            participant(0, (0, 0), true, 0),
+            participant(1, (1, 0), false, 3),
+        ];
+        exchange_power(&pair_network(), &mut participants);
+
+        assert_eq!(participants[0].battery_level, 0);
+        assert_eq!(participants[1].battery_level, 3);
+    }
+
+    #[test]
+    fn sun_feeds_every_shadowed_unit_of_a_network() {
+        let network = vec![vec![(0, 0), (1, 0), (2, 0)]];
+        let mut participants = [
+            participant(0, (0, 0), true, 0),
+            participant(1, (1, 0), false, 3),
+            participant(2, (2, 0), true, 1),
+        ];
+        exchange_power(&network, &mut participants);
+
+        assert_eq!(participants[0].battery_level, 1);
+        assert_eq!(participants[1].battery_level, 1);
+        assert_eq!(participants[2].battery_level, 2);
+        assert_eq!(total(&participants), 4);
+    }
+
+    #[test]
+    fn sun_runs_out_before_every_shadowed_unit_is_fed() {
+        let network = vec![vec![(0, 0), (1, 0), (2, 0)]];
+        let mut participants = [
+            participant(0, (0, 0), true, 2),
+            participant(1, (1, 0), false, 1),
+            participant(2, (2, 0), true, 0),
+        ];
+        exchange_power(&network, &mut participants);
+
+        // the emptiest unit is served first
+        assert_eq!(participants[0].battery_level, 2);
+        assert_eq!(participants[1].battery_level, 0);
+        assert_eq!(participants[2].battery_level, 1);
+    }
+
+    #[test]
+    fn shadowed_network_moves_one_unit_from_richest_to_poorest() {
+        let network = vec![vec![(0, 0), (1, 0), (2, 0)]];
+        let mut participants = [
+            participant(0, (0, 0), true, 1),
+            participant(1, (1, 0), true, 3),
+            participant(2, (2, 0), true, 0),
+        ];
+        exchange_power(&network, &mut participants);
+
+        assert_eq!(participants[0].battery_level, 1);
+        assert_eq!(participants[1].battery_level, 2);
+        assert_eq!(participants[2].battery_level, 1);
+    }
+
+    #[test]
+    fn networks_are_connected_components() {
+        let mut connections = HashMap::new();
+        // a chain 0 - 1 - 2 and a separate pair 5 - 6
+        connections.insert((0, 0), vec![(1, 0)]);
+        connections.insert((1, 0), vec![(0, 0), (2, 0)]);
+        connections.insert((2, 0), vec![(1, 0)]);
+        connections.insert((5, 5), vec![(6, 5)]);
+        connections.insert((6, 5), vec![(5, 5)]);
+
+        assert_eq!(
+            wire_networks(&connections),
+            vec![vec![(0, 0), (1, 0), (2, 0)], vec![(5, 5), (6, 5)]]
+        );
+    }
+
+    #[test]
+    fn power_travels_across_multiple_hops() {
+        let mut connections = HashMap::new();
+        connections.insert((0, 0), vec![(1, 0)]);
+        connections.insert((1, 0), vec![(0, 0), (2, 0)]);
+        connections.insert((2, 0), vec![(1, 0)]);
+        let networks = wire_networks(&connections);
+
+        // nobody stands on the middle tile, the ends are still connected through it
+        let mut participants = [
+            participant(0, (0, 0), false, 3),
+            participant(1, (2, 0), true, 0),
+        ];
+        exchange_power(&networks, &mut participants);
+
+        assert_eq!(participants[0].battery_level, 2);
+        assert_eq!(participants[1].battery_level, 1);
+    }
+}
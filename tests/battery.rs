@@ -0,0 +1,68 @@
+//! Checks how batteries drain with each command and recharge in the sun.
+
+use mission_ares::battery::{BatteryRules, BatteryRulesOverride, TurnActivity};
+
+fn rules() -> BatteryRules {
+    BatteryRules {
+        capacity: 4,
+        move_cost: 2,
+        wait_cost: 1,
+        turn_cost: 1,
+        sun_recharge: 1,
+        shadow_drain: 1,
+        starting_charge: 4,
+    }
+}
+
+#[test]
+fn commands_drain_their_cost() {
+    let rules = rules();
+
+    assert_eq!(rules.apply_turn(4, TurnActivity::Moved, true), 1);
+    assert_eq!(rules.apply_turn(4, TurnActivity::Waited, true), 2);
+    assert_eq!(rules.apply_turn(4, TurnActivity::Turned, true), 2);
+    assert_eq!(rules.apply_turn(4, TurnActivity::Idle, true), 3);
+    // the drain stops at an empty battery
+    assert_eq!(rules.apply_turn(1, TurnActivity::Moved, true), 0);
+    assert_eq!(rules.charge_spent(1, TurnActivity::Moved), 1);
+}
+
+#[test]
+fn sunlight_recharges_up_to_the_capacity() {
+    let rules = rules();
+
+    assert_eq!(rules.apply_turn(2, TurnActivity::Moved, false), 1);
+    assert_eq!(rules.apply_turn(2, TurnActivity::Idle, false), 3);
+    assert_eq!(rules.apply_turn(4, TurnActivity::Idle, false), 4);
+}
+
+#[test]
+fn moves_need_their_cost_in_charge() {
+    let rules = rules();
+
+    assert!(rules.can_move(2));
+    assert!(!rules.can_move(1));
+    assert!(rules.can_turn(1));
+    assert!(!rules.can_turn(0));
+
+    let free_moves = BatteryRules {
+        move_cost: 0,
+        ..rules
+    };
+    assert!(free_moves.can_move(0));
+}
+
+#[test]
+fn overrides_replace_only_their_fields() {
+    let rules = BatteryRules::default().with_override(&BatteryRulesOverride {
+        capacity: Some(2),
+        move_cost: Some(0),
+        ..Default::default()
+    });
+
+    assert_eq!(rules.capacity, 2);
+    assert_eq!(rules.move_cost, 0);
+    assert_eq!(rules.sun_recharge, BatteryRules::default().sun_recharge);
+    // the starting charge can't exceed the smaller capacity
+    assert_eq!(rules.starting_charge, 2);
+}
@@ -0,0 +1,128 @@
+//! Plays the shipped levels turn by turn, the way an agent would.
+
+use mission_ares::environment::{
+    Environment, EnvironmentError, FAILED_REWARD, SOLVED_REWARD, Status, Step,
+};
+use mission_ares::game_control::actions::ActionType;
+use mission_ares::level::{GRADVM, LEGE_FASCICVLVM_GRADVS};
+use mission_ares::simulation::FailureReason;
+use mission_ares::verification::verify;
+use std::path::PathBuf;
+
+const LEVEL_COUNT: u32 = 9;
+
+fn level(number: u32) -> GRADVM {
+    LEGE_FASCICVLVM_GRADVS(
+        &PathBuf::from(format!("assets/levels/{number}.lvl")),
+        number - 1,
+    )
+    .unwrap()
+}
+
+#[test]
+fn reference_solutions_solve_every_level_step_by_step() {
+    for number in 1..=LEVEL_COUNT {
+        let level = level(number);
+        let solution = level.SOLVTIO.clone().unwrap();
+        let turns = solution.iter().map(Vec::len).max().unwrap();
+        let mut environment = Environment::new(level);
+        environment.reset();
+
+        let mut last = None;
+        for turn in 0..turns {
+            let actions: Vec<Option<ActionType>> = solution
+                .iter()
+                .map(|actions| actions.get(turn).cloned())
+                .collect();
+            last = Some(environment.step(&actions).unwrap());
+            if last.as_ref().unwrap().done {
+                break;
+            }
+        }
+
+        let last = last.unwrap();
+        assert_eq!(last.status, Status::Solved, "level {number}");
+        assert!(last.reward > SOLVED_REWARD / 2.0, "level {number}");
+        assert!(last.observation.minerals.is_empty(), "level {number}");
+        assert!(matches!(
+            environment.step(&vec![None; last.observation.units.len()]),
+            Err(EnvironmentError::Done)
+        ));
+    }
+}
+
+/// Plays `program` turn by turn until the level is over, returning the last step.
+fn play(level: &GRADVM, program: &[Vec<ActionType>]) -> Step {
+    let mut environment = Environment::new(level.clone());
+    let mut step = environment.reset();
+    for turn in 0.. {
+        let actions: Vec<Option<ActionType>> = (0..step.observation.units.len())
+            .map(|unit| {
+                program
+                    .get(unit)
+                    .and_then(|actions| actions.get(turn))
+                    .cloned()
+            })
+            .collect();
+        step = environment.step(&actions).unwrap();
+        if step.done {
+            break;
+        }
+    }
+    step
+}
+
+fn failure_name(reason: FailureReason) -> &'static str {
+    reason.key().trim_start_matches("failure.")
+}
+
+#[test]
+fn environment_and_game_agree_on_every_level() {
+    for number in 1..=LEVEL_COUNT {
+        let level = level(number);
+        let solution = level.SOLVTIO.clone().unwrap();
+
+        // the solution, the solution stopped short, and every unit heading straight on until
+        // something stops it
+        let mut unfinished = solution.clone();
+        for actions in unfinished.iter_mut() {
+            actions.pop();
+        }
+        let ahead = level.IMPERIA.movement_actions()[0];
+        let straight_on = vec![vec![ahead; 4]; solution.len()];
+
+        for program in [solution, unfinished, straight_on] {
+            let step = play(&level, &program);
+            let game = verify(&level, &program).unwrap();
+
+            assert_eq!(
+                step.status == Status::Solved,
+                game.solved,
+                "level {number}: {program:?}"
+            );
+            assert_eq!(
+                step.failure,
+                game.failure.map(failure_name),
+                "level {number}: {program:?}"
+            );
+            let batteries: Vec<u8> = step
+                .observation
+                .units
+                .iter()
+                .map(|unit| unit.battery)
+                .collect();
+            assert_eq!(batteries, game.batteries, "level {number}: {program:?}");
+        }
+    }
+}
+
+#[test]
+fn idle_units_fail_the_level() {
+    let mut environment = Environment::new(level(1));
+    let units = environment.reset().observation.units.len();
+
+    let step = environment.step(&vec![None; units]).unwrap();
+    assert_eq!(step.status, Status::Failed);
+    assert_eq!(step.failure, Some("out_of_commands"));
+    assert!(step.reward < FAILED_REWARD / 2.0);
+}
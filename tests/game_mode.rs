@@ -0,0 +1,78 @@
+//! Starts generated games through the gameplay plugins, their levels being generated in the
+//! background.
+
+use bevy::asset::AssetPlugin;
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use mission_ares::game_mode::{EndlessRun, GameMode, LevelGeneration};
+use mission_ares::gameplay::{ActiveLevel, BareUnitsPlugin, GameState, GameplayPlugins};
+use mission_ares::level::{GRADVM, GRADVM_ONVSTVS};
+use mission_ares::save::SavePath;
+use std::time::Duration;
+
+/// Frames a level gets to be generated before giving up, a few milliseconds apart
+const MAX_FRAMES: usize = 2_000;
+
+/// The gameplay plugins with a save of their own, away from the one of the player.
+fn headless_app(name: &str) -> App {
+    let save_path =
+        std::env::temp_dir().join(format!("mission_ares_{}_{name}.ron", std::process::id()));
+    let _ = std::fs::remove_file(&save_path);
+
+    let mut app = App::new();
+    app.insert_resource(SavePath(save_path));
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        StatesPlugin,
+        InputPlugin,
+        GameplayPlugins,
+        BareUnitsPlugin,
+    ));
+    app
+}
+
+#[test]
+fn endless_runs_start_once_their_first_level_is_generated() {
+    let mut app = headless_app("endless");
+    app.update();
+
+    *app.world_mut().resource_mut::<GameMode>() = GameMode::Endless;
+    let run = EndlessRun::new(7);
+    app.world_mut().spawn(run.generate_level(true));
+    app.insert_resource(run);
+
+    for _ in 0..MAX_FRAMES {
+        app.update();
+
+        let Some(handle) = app.world().resource::<ActiveLevel>().0.clone() else {
+            std::thread::sleep(Duration::from_millis(10));
+            continue;
+        };
+
+        assert_eq!(
+            *app.world().resource::<State<GameState>>().get(),
+            GameState::Programming
+        );
+        // the generated level, not the first one of the campaign
+        assert!(
+            !app.world()
+                .resource::<GRADVM_ONVSTVS>()
+                .GRADVS
+                .contains(&handle)
+        );
+        let level = app
+            .world()
+            .resource::<Assets<GRADVM>>()
+            .get(&handle)
+            .unwrap();
+        assert!(level.SOLVTIO.is_some());
+
+        let mut generations = app.world_mut().query::<&LevelGeneration>();
+        assert_eq!(generations.iter(app.world()).count(), 0);
+        return;
+    }
+
+    panic!("the first level of the run was never generated");
+}
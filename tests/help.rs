@@ -0,0 +1,101 @@
+//! Checks the shipped help topics against the campaign levels.
+
+use mission_ares::help::topic::{HelpTopics, HelpTrigger};
+use mission_ares::help::walkthrough::{WalkthroughTarget, Walkthroughs};
+use mission_ares::level::LEGE_FASCICVLVM_GRADVS;
+use mission_ares::simulation::{TurnOutcome, simulate};
+use std::collections::BTreeSet;
+use std::path::Path;
+
+const LEVEL_COUNT: u32 = 9;
+
+fn shipped_topics() -> HelpTopics {
+    let text = std::fs::read_to_string("assets/help/tutorial.help.ron").unwrap();
+    ron::from_str(&text).unwrap()
+}
+
+#[test]
+fn shipped_topics_are_valid() {
+    let topics = shipped_topics();
+    for id in &topics.button {
+        assert!(topics.topic(id).is_some(), "unknown button topic {id}");
+    }
+
+    let mut ids = BTreeSet::new();
+    for topic in &topics.topics {
+        assert!(ids.insert(&topic.id), "duplicate topic {}", topic.id);
+        assert!(!topic.pages.is_empty(), "topic {} has no pages", topic.id);
+        for image in topic
+            .pages
+            .iter()
+            .flat_map(|page| &page.sections)
+            .flat_map(|section| &section.images)
+        {
+            assert!(
+                Path::new("assets").join(image).exists(),
+                "missing image {image}"
+            );
+        }
+    }
+}
+
+#[test]
+fn campaign_shows_each_level_topic_once() {
+    let topics = shipped_topics();
+    let mut seen = BTreeSet::new();
+    let mut shown = vec![];
+    for index in 0..LEVEL_COUNT {
+        let level = LEGE_FASCICVLVM_GRADVS(
+            &Path::new("assets/levels").join(format!("{}.lvl", index + 1)),
+            index,
+        )
+        .unwrap();
+        for topic in topics.level_topics(&level, &seen) {
+            shown.push((index + 1, topic.id.clone()));
+        }
+        seen.extend(shown.iter().map(|(_, id)| id.clone()));
+    }
+
+    assert_eq!(shown.first(), Some(&(1, "basics".to_string())));
+    assert!(shown.iter().any(|(_, id)| id == "wires"));
+
+    // the empty program help keeps showing up, the failure ones only the first time
+    assert!(
+        !topics
+            .triggered_topics(HelpTrigger::EmptyProgram, &seen)
+            .is_empty()
+    );
+    seen.insert("battery_failure".to_string());
+    assert!(
+        topics
+            .triggered_topics(HelpTrigger::BatteryFailure, &seen)
+            .is_empty()
+    );
+}
+
+#[test]
+fn first_walkthrough_solves_its_level() {
+    let text = std::fs::read_to_string("assets/help/campaign.walkthrough.ron").unwrap();
+    let walkthroughs: Walkthroughs = ron::from_str(&text).unwrap();
+
+    for walkthrough in &walkthroughs.walkthroughs {
+        assert!(walkthrough.level >= 1 && walkthrough.level <= LEVEL_COUNT);
+        for step in &walkthrough.steps {
+            if let WalkthroughTarget::Command(letter) = step.target {
+                assert!(step.target.action().is_some(), "unknown command {letter}");
+            }
+        }
+    }
+
+    // following every step of the first level's walkthrough is enough to solve it
+    let level = LEGE_FASCICVLVM_GRADVS(Path::new("assets/levels/1.lvl"), 0).unwrap();
+    let mut program = vec![vec![]; level.VEHICVLA.len()];
+    let mut selected = 0;
+    for step in &walkthroughs.for_level(1).unwrap().steps {
+        match step.target {
+            WalkthroughTarget::Robot(unit) => selected = unit,
+            target => program[selected].extend(target.action()),
+        }
+    }
+    assert_eq!(simulate(&level, &program).0, TurnOutcome::Solved);
+}
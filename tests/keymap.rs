@@ -0,0 +1,44 @@
+//! The keymap players program the rovers with, and how it is kept in the save.
+
+use bevy::prelude::{GamepadButton, KeyCode};
+use mission_ares::game_control::keymap::{GAMEPAD_BINDINGS, KeyAction, Keymap};
+use mission_ares::save::SaveData;
+
+#[test]
+fn rebinding_takes_the_key_away_from_other_actions() {
+    let mut keymap = Keymap::default();
+    keymap.rebind(KeyAction::Wait, KeyCode::KeyW);
+
+    assert_eq!(keymap.keys(KeyAction::Wait), vec![KeyCode::KeyW]);
+    assert_eq!(keymap.keys(KeyAction::Up), vec![KeyCode::ArrowUp]);
+    assert_eq!(keymap.describe(KeyAction::Execute), "Space / Enter");
+}
+
+#[test]
+fn saves_without_settings_keep_the_default_keymap() {
+    let save: SaveData = ron::from_str("(best_scores: {})").unwrap();
+    assert_eq!(save.settings.keymap, Keymap::default());
+
+    let mut save = save;
+    save.settings
+        .keymap
+        .rebind(KeyAction::Execute, KeyCode::KeyG);
+    let restored: SaveData = ron::from_str(&ron::to_string(&save).unwrap()).unwrap();
+    assert_eq!(
+        restored.settings.keymap.keys(KeyAction::Execute),
+        vec![KeyCode::KeyG]
+    );
+}
+
+#[test]
+fn escape_and_start_pause_the_game() {
+    assert_eq!(
+        Keymap::default().keys(KeyAction::Pause),
+        vec![KeyCode::Escape]
+    );
+    let start = GAMEPAD_BINDINGS
+        .iter()
+        .find(|(button, _)| *button == GamepadButton::Start)
+        .map(|(_, action)| *action);
+    assert_eq!(start, Some(KeyAction::Pause));
+}
@@ -0,0 +1,163 @@
+//! Reads level texts the way the asset loader does, directives included.
+
+use bevy::prelude::*;
+use mission_ares::game_control::actions::{ActionType, ControlScheme};
+use mission_ares::level::{DESTINATIO, GRADVM, GRADVM_ORENATOR_ERROR, LEGE_GRADVM, PAR};
+
+fn parse(text: &str) -> Result<GRADVM, GRADVM_ORENATOR_ERROR> {
+    LEGE_GRADVM(text, 0, Handle::default())
+}
+
+#[test]
+fn directives_are_read() {
+    let level = parse(
+        "@CAPACITAS 2\n\
+         @REDDE SATVRNALIA\n\
+         @ORDO 1 0\n\
+         @DOMINVS 0 1\n\
+         @SARCINA 2\n\
+         @IMPERIA RELATIVA\n\
+         @PAR MANDATA=4 VICES=3 PILA=2\n\
+         @SOLVTIO 1 UW\n\
+         EE\n\
+         SS\n\
+         ID",
+    )
+    .unwrap();
+
+    assert_eq!(level.OBIECTVM.CAPACITAS, Some(2));
+    assert_eq!(level.OBIECTVM.DESTINATIO, Some(DESTINATIO::SATVRNALIA));
+    assert_eq!(level.OBIECTVM.ORDO, Some(vec![1, 0]));
+    assert_eq!(level.OBIECTVM.DOMINI.get(&0), Some(&1));
+    assert_eq!(level.SARCINA, 2);
+    assert_eq!(level.IMPERIA, ControlScheme::Relative);
+    assert_eq!(
+        level.PAR,
+        Some(PAR {
+            MANDATA: 4,
+            VICES: 3,
+            PILA: 2
+        })
+    );
+    // padded with empty programs up to the three units, the helicopter included
+    assert_eq!(
+        level.SOLVTIO,
+        Some(vec![
+            vec![],
+            vec![ActionType::MoveUp, ActionType::Wait],
+            vec![]
+        ])
+    );
+}
+
+#[test]
+fn bare_order_is_the_reading_order() {
+    let level = parse("@ORDO\nEPE\nPSP").unwrap();
+
+    assert_eq!(level.OBIECTVM.ORDO, Some(vec![0, 1]));
+}
+
+#[test]
+fn units_are_numbered_rovers_first() {
+    let level = parse("IS\nSI").unwrap();
+
+    assert_eq!(level.VEHICVLA.len(), 4);
+    assert_eq!(level.NVMERVS_VEHICVLORVM_MOBILIVM, 4);
+    // rovers in reading order, then the helicopters
+    assert_eq!(level.VEHICVLA[0].0, 1);
+    assert_eq!(level.VEHICVLA[1].0, 0);
+    assert_eq!(level.VEHICVLA[2].0, 0);
+    assert_eq!(level.VEHICVLA[3].0, 1);
+}
+
+#[test]
+fn tiles_sharing_a_digit_are_wired_in_reading_order() {
+    // rows count up from the bottom of the map
+    let level = parse("S1P1\n2P2P2\nE").unwrap();
+
+    // a pair shares one wire
+    assert_eq!(level.NEXVS[&(1, 3)], vec![(3, 3)]);
+    assert_eq!(level.NEXVS[&(3, 3)], vec![(1, 3)]);
+    // a third tile is wired to the one before it, making a chain
+    assert_eq!(level.NEXVS[&(0, 2)], vec![(2, 2)]);
+    assert_eq!(level.NEXVS[&(2, 2)], vec![(0, 2), (4, 2)]);
+    assert_eq!(level.NEXVS[&(4, 2)], vec![(2, 2)]);
+    assert_eq!(level.NEXVS.len(), 5);
+}
+
+#[test]
+fn malformed_directives_are_rejected() {
+    for text in [
+        "@CAPACITAS\nSE",
+        "@CAPACITAS two\nSE",
+        "@REDDE SOMEWHERE\nSE",
+        "@IMPERIA DIAGONALIS\nSE",
+        "@PAR MANDATA=4 VICES=3\nSE",
+        "@SOLVTIO 0 UX\nSE",
+        "@VOLA 1\nSE",
+    ] {
+        assert!(
+            matches!(parse(text), Err(GRADVM_ORENATOR_ERROR::DIRECTIVA(_))),
+            "{text:?} was accepted"
+        );
+    }
+}
+
+#[test]
+fn carry_capacity_must_hold_a_mineral() {
+    assert!(matches!(
+        parse("@CAPACITAS 0\nSE"),
+        Err(GRADVM_ORENATOR_ERROR::DIRECTIVA(_))
+    ));
+    assert_eq!(
+        parse("@CAPACITAS 1\nSE").unwrap().OBIECTVM.CAPACITAS,
+        Some(1)
+    );
+}
+
+#[test]
+fn batteries_hold_some_charge() {
+    for text in ["@PILA CAPACITAS=0\nSE", "@PILA_VEHICVLI 0 CAPACITAS=0\nSE"] {
+        assert!(
+            matches!(parse(text), Err(GRADVM_ORENATOR_ERROR::DIRECTIVA(_))),
+            "{text:?} was accepted"
+        );
+    }
+}
+
+#[test]
+fn numbers_too_large_for_a_byte_are_rejected() {
+    for text in [
+        "@CAPACITAS 300\nSE",
+        "@SARCINA 256\nSE",
+        "@DOMINVS 0 257\nSE",
+        "@PILA_VEHICVLI 256 CAPACITAS=4\nSE",
+        "@SOLVTIO 4000000000 U\nSE",
+    ] {
+        assert!(
+            matches!(parse(text), Err(GRADVM_ORENATOR_ERROR::DIRECTIVA(_))),
+            "{text:?} was accepted"
+        );
+    }
+}
+
+#[test]
+fn directives_must_name_existing_minerals_and_rovers() {
+    for text in [
+        // only minerals 0 and 1 exist
+        "@ORDO 0 2\nSEE",
+        "@DOMINVS 2 0\nSEE",
+        // unit 1 is a helicopter, it can't collect minerals
+        "@DOMINVS 0 1\nSEI",
+        "@DOMINVS 0 3\nSEE",
+        // units 0 and 1 only, helicopters included
+        "@SOLVTIO 2 U\nSEI",
+    ] {
+        assert!(
+            matches!(parse(text), Err(GRADVM_ORENATOR_ERROR::INDEX_DIRECTIVAE(_))),
+            "{text:?} was accepted"
+        );
+    }
+    assert!(parse("@ORDO 1 0\n@DOMINVS 1 1\nSEES").is_ok());
+    assert!(parse("@SOLVTIO 1 U\nSEI").is_ok());
+}
@@ -0,0 +1,211 @@
+//! Plays the shipped levels through the game's own plugins, without a window or a GPU.
+
+use bevy::asset::AssetPlugin;
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+use mission_ares::game_control::actions::{Action, ActionType};
+use mission_ares::gameplay::{
+    ActiveLevel, BareUnitsPlugin, GameState, GameplayPlugins, LevelSpawnRequestEvent,
+};
+use mission_ares::level::{GRADVM, GRADVM_ONVSTVS, LEGE_FASCICVLVM_GRADVS};
+use mission_ares::puzzle_evaluation::PuzzleResponseEvent;
+use mission_ares::rover::ActionListExecute;
+use mission_ares::save::SavePath;
+use mission_ares::simulation::FailureReason;
+use mission_ares::verification::{Verification, verify};
+use std::path::PathBuf;
+use std::time::Duration;
+
+const LEVEL_COUNT: u32 = 9;
+/// Frames a level gets to load and then to play out before giving up
+const MAX_FRAMES: usize = 10_000;
+const FRAME_TIME: Duration = Duration::from_millis(50);
+
+/// Reads level `number` along with its shadow map.
+fn level(number: u32) -> GRADVM {
+    LEGE_FASCICVLVM_GRADVS(
+        &PathBuf::from(format!("assets/levels/{number}.lvl")),
+        number - 1,
+    )
+    .unwrap()
+}
+
+/// Executes `program` on `level` until the puzzle is solved or failed.
+fn play(level: &GRADVM, program: &[Vec<ActionType>]) -> Verification {
+    verify(level, program)
+        .unwrap_or_else(|| panic!("level {} never finished executing", level.INDEX + 1))
+}
+
+fn reference_solution(level: &GRADVM) -> Vec<Vec<ActionType>> {
+    level
+        .SOLVTIO
+        .clone()
+        .unwrap_or_else(|| panic!("level {} has no @SOLVTIO", level.INDEX + 1))
+}
+
+#[test]
+fn shipped_levels_are_solved_by_their_solution() {
+    for number in 1..=LEVEL_COUNT {
+        let level = level(number);
+        let verification = play(&level, &reference_solution(&level));
+        assert!(
+            verification.solved,
+            "level {number} is not solved by its reference solution"
+        );
+        assert!(verification.simulation_agrees, "level {number}");
+    }
+}
+
+#[test]
+fn unfinished_program_fails() {
+    let level = level(1);
+    let mut program = reference_solution(&level);
+    for actions in program.iter_mut() {
+        actions.pop();
+    }
+
+    let verification = play(&level, &program);
+    assert!(!verification.solved);
+    assert_eq!(verification.failure, Some(FailureReason::OutOfCommands));
+}
+
+/// The game without its presentation, reading the levels through the asset server.
+fn asset_app(name: &str) -> App {
+    let save_path =
+        std::env::temp_dir().join(format!("mission_ares_{}_{name}.ron", std::process::id()));
+    let _ = std::fs::remove_file(&save_path);
+
+    let mut app = App::new();
+    app.insert_resource(SavePath(save_path));
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        StatesPlugin,
+        InputPlugin,
+        GameplayPlugins,
+        BareUnitsPlugin,
+    ));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(FRAME_TIME));
+    app
+}
+
+/// Waits for the asset server to load every level, their shadow maps included.
+fn load_levels(app: &mut App) -> Vec<Handle<GRADVM>> {
+    for _ in 0..MAX_FRAMES {
+        app.update();
+
+        let handles = app.world().resource::<GRADVM_ONVSTVS>().GRADVS.clone();
+        let asset_server = app.world().resource::<AssetServer>();
+        if handles
+            .iter()
+            .all(|handle| asset_server.is_loaded_with_dependencies(handle))
+        {
+            // one more frame for the shadows to be applied
+            app.update();
+            return handles;
+        }
+        std::thread::sleep(Duration::from_millis(1));
+    }
+
+    panic!("the levels did not load");
+}
+
+fn shadows(level: &GRADVM) -> Vec<(i8, i8)> {
+    let mut shadows: Vec<(i8, i8)> = level
+        .TEGLVAE
+        .iter()
+        .filter(|(_, tile)| tile.VMBRA)
+        .map(|(position, _)| *position)
+        .collect();
+    shadows.sort();
+    shadows
+}
+
+/// Starts `level` the way the game does, then executes `program` until the puzzle is solved or
+/// failed.
+fn play_loaded(app: &mut App, level: Handle<GRADVM>, program: &[Vec<ActionType>]) -> bool {
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Programming);
+    app.update();
+    app.world_mut().send_event(LevelSpawnRequestEvent {
+        level: level.clone(),
+    });
+    app.update();
+    app.update();
+    assert_eq!(app.world().resource::<ActiveLevel>().0, Some(level));
+
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Execution);
+    app.world_mut().send_event(ActionListExecute {
+        action_list: program
+            .iter()
+            .enumerate()
+            .map(|(unit, actions)| {
+                actions
+                    .iter()
+                    .map(|action| Action {
+                        moves: (*action, unit),
+                    })
+                    .collect()
+            })
+            .collect(),
+    });
+
+    let mut responses = app
+        .world()
+        .resource::<Events<PuzzleResponseEvent>>()
+        .get_cursor();
+    (0..MAX_FRAMES)
+        .find_map(|_| {
+            app.update();
+            responses
+                .read(app.world().resource::<Events<PuzzleResponseEvent>>())
+                .find(|response| **response != PuzzleResponseEvent::InProgress)
+                .map(|response| *response == PuzzleResponseEvent::Solved)
+        })
+        .expect("the level never finished executing")
+}
+
+#[test]
+fn shipped_levels_load_through_the_asset_server() {
+    let level_files = std::fs::read_dir("assets/levels")
+        .unwrap()
+        .filter(|entry| {
+            entry
+                .as_ref()
+                .unwrap()
+                .path()
+                .extension()
+                .is_some_and(|extension| extension == "lvl")
+        })
+        .count();
+
+    let mut app = asset_app("levels");
+    let handles = load_levels(&mut app);
+    assert_eq!(handles.len(), level_files);
+
+    let mut shadowed_levels = 0;
+    for (index, handle) in handles.into_iter().enumerate() {
+        let number = index as u32 + 1;
+        let loaded = app
+            .world()
+            .resource::<Assets<GRADVM>>()
+            .get(&handle)
+            .unwrap()
+            .clone();
+        // the shadow map went through the image loader, not the file reader of the tools
+        assert_eq!(shadows(&loaded), shadows(&level(number)), "level {number}");
+        shadowed_levels += usize::from(!shadows(&loaded).is_empty());
+
+        let solution = reference_solution(&loaded);
+        assert!(
+            play_loaded(&mut app, handle, &solution),
+            "level {number} is not solved by its reference solution"
+        );
+    }
+    assert!(shadowed_levels > 0);
+}
@@ -0,0 +1,147 @@
+//! Checks the shipped string tables against the English one, and how the text falls back to it.
+
+use mission_ares::help::topic::HelpTopics;
+use mission_ares::help::walkthrough::Walkthroughs;
+use mission_ares::locale::{Language, StringTable, StringTableError, Strings};
+use std::collections::BTreeSet;
+
+fn shipped_table(language: Language) -> StringTable {
+    let text = std::fs::read_to_string(format!("assets/{}", language.path())).unwrap();
+    StringTable::parse(&text).unwrap_or_else(|error| panic!("{language:?}: {error}"))
+}
+
+/// Keys translating the help pages and the walkthroughs, whose English lives in assets/help.
+fn asset_keys() -> BTreeSet<String> {
+    let topics: HelpTopics =
+        ron::from_str(&std::fs::read_to_string("assets/help/tutorial.help.ron").unwrap()).unwrap();
+    let walkthroughs: Walkthroughs =
+        ron::from_str(&std::fs::read_to_string("assets/help/campaign.walkthrough.ron").unwrap())
+            .unwrap();
+
+    let mut keys = BTreeSet::new();
+    for topic in &topics.topics {
+        for (page_index, page) in topic.pages.iter().enumerate() {
+            let page_key = format!("help.{}.{}", topic.id, page_index);
+            if page.title.is_some() {
+                keys.insert(format!("{page_key}.title"));
+            }
+            for section_index in 0..page.sections.len() {
+                keys.insert(format!("{page_key}.{section_index}"));
+            }
+        }
+    }
+    for walkthrough in &walkthroughs.walkthroughs {
+        for step in 0..walkthrough.steps.len() {
+            keys.insert(format!("walkthrough.{}.{}", walkthrough.id, step));
+        }
+    }
+    keys
+}
+
+fn placeholders(text: &str) -> BTreeSet<&str> {
+    text.split('{')
+        .skip(1)
+        .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+        .collect()
+}
+
+#[test]
+fn shipped_tables_only_translate_known_keys() {
+    let english = Strings::english();
+    let asset_keys = asset_keys();
+    for language in Language::ALL {
+        let table = shipped_table(language);
+        for key in table.keys() {
+            assert!(
+                english.get(key).is_some() || asset_keys.contains(key),
+                "{language:?} has unknown key {key}"
+            );
+        }
+    }
+}
+
+#[test]
+fn shipped_tables_keep_the_placeholders() {
+    let english = Strings::english();
+    for language in Language::ALL {
+        let table = shipped_table(language);
+        for key in english.keys() {
+            let Some(text) = table.get(key) else {
+                continue;
+            };
+            assert_eq!(
+                placeholders(text),
+                placeholders(english.get(key).unwrap()),
+                "{language:?} changes the placeholders of {key}"
+            );
+        }
+    }
+}
+
+#[test]
+fn shipped_tables_translate_every_key() {
+    let english = Strings::english();
+    let asset_keys = asset_keys();
+    for language in Language::ALL {
+        let table = shipped_table(language);
+        let mut missing: Vec<_> = english
+            .keys()
+            .filter(|key| table.get(key).is_none())
+            .collect();
+        // the English of the help and the walkthroughs is in their own files
+        if language != Language::English {
+            missing.extend(
+                asset_keys
+                    .iter()
+                    .map(String::as_str)
+                    .filter(|key| table.get(key).is_none()),
+            );
+        }
+        assert!(missing.is_empty(), "{language:?} lacks {missing:?}");
+    }
+}
+
+#[test]
+fn missing_keys_fall_back_to_english() {
+    let table = StringTable::parse("title.start = Commencer").unwrap();
+    let strings = Strings::new(Language::French, table);
+
+    assert_eq!(strings.get("title.start"), "Commencer");
+    assert_eq!(strings.get("title.endless"), "Endless");
+    assert_eq!(strings.get("no.such.key"), "no.such.key");
+    assert_eq!(
+        strings.get_or("help.basics.0.0", "Plan a path"),
+        "Plan a path"
+    );
+}
+
+#[test]
+fn formats_and_plurals_fill_in_the_values() {
+    let strings = Strings::default();
+
+    assert_eq!(
+        strings.format("level.campaign", &[("number", &3)]),
+        "Level 3"
+    );
+    assert_eq!(strings.plural("score.hints", 1), "1 hint used");
+    assert_eq!(strings.plural("score.hints", 2), "2 hints used");
+    assert_eq!(
+        strings.get("final.message"),
+        "All minerals have been successfully collected.\nThe mission was a success!"
+    );
+}
+
+#[test]
+fn parse_errors_tell_the_line() {
+    assert_eq!(
+        StringTable::parse("# comment\n\nno separator").unwrap_err(),
+        StringTableError::MissingSeparator { line: 3 }
+    );
+    assert_eq!(
+        StringTable::parse("a = 1\nb = 2\na = 3").unwrap_err(),
+        StringTableError::DuplicateKey {
+            line: 3,
+            key: "a".to_string()
+        }
+    );
+}
@@ -0,0 +1,89 @@
+//! Plays small levels through the simulation to check the collection and delivery rules.
+
+use bevy::prelude::*;
+use mission_ares::game_control::actions::ActionType;
+use mission_ares::level::{GRADVM, LEGE_GRADVM, MANDATVM_LITTERAE};
+use mission_ares::simulation::{SimulationState, TurnOutcome, simulate};
+
+fn parse(text: &str) -> GRADVM {
+    LEGE_GRADVM(text, 0, Handle::default()).unwrap()
+}
+
+/// Commands of each unit, one letter each as in a @SOLVTIO directive
+fn programs(units: &[&str]) -> Vec<Vec<ActionType>> {
+    units
+        .iter()
+        .map(|letters| {
+            letters
+                .chars()
+                .map(|c| MANDATVM_LITTERAE(c).unwrap())
+                .collect()
+        })
+        .collect()
+}
+
+fn play(level: &GRADVM, units: &[&str]) -> (TurnOutcome, SimulationState) {
+    simulate(level, &programs(units))
+}
+
+#[test]
+fn full_rovers_leave_minerals_behind() {
+    let level = parse("@CAPACITAS 1\n@REDDE SATVRNALIA\nEE\nPD\nS");
+
+    let (_, state) = play(&level, &["UUR"]);
+    assert_eq!(state.progress.collected, vec![0]);
+    assert_eq!(state.units[0].cargo, vec![0]);
+
+    let (outcome, state) = play(&level, &["UURDUD"]);
+    assert_eq!(outcome, TurnOutcome::Solved);
+    assert_eq!(state.progress.delivered, vec![0, 1]);
+    assert!(state.units[0].cargo.is_empty());
+}
+
+#[test]
+fn minerals_count_once_delivered() {
+    let level = parse("@REDDE INITIVM\nE\nP\nS");
+
+    let (outcome, state) = play(&level, &["UU"]);
+    assert!(matches!(outcome, TurnOutcome::Failed(_)));
+    assert_eq!(state.progress.collected, vec![0]);
+    assert!(state.progress.delivered.is_empty());
+
+    // a dish doesn't count when the minerals go back to the start tile
+    let (outcome, _) = play(&level, &["UUDD"]);
+    assert_eq!(outcome, TurnOutcome::Solved);
+}
+
+#[test]
+fn dishes_take_deliveries_only_when_asked_to() {
+    let level = parse("@REDDE INITIVM\nED\nSP");
+
+    let (outcome, state) = play(&level, &["UR"]);
+    assert!(matches!(outcome, TurnOutcome::Failed(_)));
+    assert!(state.progress.delivered.is_empty());
+}
+
+#[test]
+fn owned_minerals_wait_for_their_rover() {
+    let level = parse("@DOMINVS 0 1\nEE\nSS");
+
+    let (outcome, state) = play(&level, &["U", "U"]);
+    assert!(matches!(outcome, TurnOutcome::Failed(_)));
+    assert_eq!(state.progress.collected, vec![1]);
+
+    let (outcome, state) = play(&level, &["W", "UL"]);
+    assert_eq!(outcome, TurnOutcome::Solved);
+    assert_eq!(state.progress.collected, vec![1, 0]);
+}
+
+#[test]
+fn minerals_are_collected_in_order() {
+    let level = parse("@ORDO 1 0\nEPE\nPSP");
+
+    let (_, state) = play(&level, &["LU"]);
+    assert!(state.progress.collected.is_empty());
+
+    let (outcome, state) = play(&level, &["RULL"]);
+    assert_eq!(outcome, TurnOutcome::Solved);
+    assert_eq!(state.progress.collected, vec![1, 0]);
+}
@@ -0,0 +1,31 @@
+//! The colour palettes telling the units apart.
+
+use mission_ares::palette::Palette;
+
+/// Levels have up to this many units
+const UNIT_COUNT: usize = 6;
+
+#[test]
+fn every_palette_tells_all_units_apart() {
+    for palette in Palette::ALL {
+        let colors = palette.ui_colors();
+        assert_eq!(colors.len(), UNIT_COUNT, "{palette:?}");
+        for (index, color) in colors.iter().enumerate() {
+            assert!(
+                !colors[..index].contains(color),
+                "{palette:?} repeats the colour of unit {}",
+                index + 1
+            );
+        }
+        assert_eq!(palette.model_colors().len(), UNIT_COUNT, "{palette:?}");
+    }
+}
+
+#[test]
+fn cycling_goes_through_every_palette() {
+    let mut palette = Palette::default();
+    for expected in Palette::ALL.iter().cycle().skip(1).take(Palette::ALL.len()) {
+        palette = palette.next();
+        assert_eq!(palette, *expected);
+    }
+}
@@ -0,0 +1,39 @@
+//! The settings of the settings menu, and how older saves pick them up.
+
+use mission_ares::save::SaveData;
+use mission_ares::settings::{GraphicsQuality, Settings};
+
+#[test]
+fn saves_from_before_the_settings_menu_keep_the_defaults() {
+    let save: SaveData =
+        ron::from_str("(best_scores: {}, settings: (palette: Tritanopia))").unwrap();
+
+    assert_eq!(save.settings.master_volume, 1.0);
+    assert_eq!(save.settings.graphics, GraphicsQuality::Ultra);
+    assert_eq!(save.settings.ui_scale, 1.0);
+    assert!(!save.settings.reduced_motion);
+}
+
+#[test]
+fn settings_survive_a_save() {
+    let save = SaveData {
+        settings: Settings {
+            music_volume: 0.3,
+            graphics: GraphicsQuality::Low,
+            ui_scale: 1.25,
+            reduced_motion: true,
+            ..Settings::default()
+        },
+        ..SaveData::default()
+    };
+
+    let restored: SaveData = ron::from_str(&ron::to_string(&save).unwrap()).unwrap();
+    assert_eq!(restored.settings, save.settings);
+}
+
+#[test]
+fn low_graphics_leave_out_the_costly_effects() {
+    assert!(!GraphicsQuality::Low.bloom());
+    assert!(!GraphicsQuality::Medium.temporal_anti_aliasing());
+    assert!(GraphicsQuality::Ultra.shadow_map_size() > GraphicsQuality::Low.shadow_map_size());
+}
@@ -0,0 +1,65 @@
+//! Checks the solutions the solver finds and the par taken from them.
+
+use bevy::prelude::*;
+use mission_ares::level::{LEGE_FASCICVLVM_GRADVS, LEGE_GRADVM};
+use mission_ares::score::solution_par;
+use mission_ares::simulation::{TurnOutcome, simulate};
+use mission_ares::solver::{
+    Solution, SolverFailure, SolverLimits, solve, solve_fewest_commands, solve_with_prefix,
+};
+use std::path::Path;
+
+fn commands(solution: &Solution) -> usize {
+    solution.actions.iter().map(|actions| actions.len()).sum()
+}
+
+#[test]
+fn command_par_comes_from_the_program_with_the_fewest_commands() {
+    // the units of level 4 wait for each other to go as fast as possible, but don't have to
+    let level = LEGE_FASCICVLVM_GRADVS(Path::new("assets/levels/4.lvl"), 0).unwrap();
+    let limits = SolverLimits::default();
+
+    let fastest = solve(&level, limits).unwrap();
+    let fewest_commands = solve_fewest_commands(&level, &fastest, limits);
+    assert_eq!(
+        simulate(&level, &fewest_commands.actions).0,
+        TurnOutcome::Solved
+    );
+    assert!(commands(&fewest_commands) < commands(&fastest));
+    assert!(fewest_commands.turns >= fastest.turns);
+
+    let par = solution_par(&level, &fastest, &fewest_commands).unwrap();
+    assert_eq!(par.MANDATA as usize, commands(&fewest_commands));
+    assert_eq!(par.VICES as usize, fastest.turns);
+}
+
+#[test]
+fn fastest_program_is_kept_when_nothing_is_shorter() {
+    let level = LEGE_FASCICVLVM_GRADVS(Path::new("assets/levels/1.lvl"), 0).unwrap();
+    let limits = SolverLimits::default();
+
+    let fastest = solve(&level, limits).unwrap();
+    let fewest_commands = solve_fewest_commands(&level, &fastest, limits);
+    assert_eq!(fewest_commands.actions, fastest.actions);
+}
+
+#[test]
+fn giving_up_is_told_apart_from_no_solution() {
+    let level = LEGE_GRADVM("S P E", 0, Handle::default()).unwrap();
+    let limits = SolverLimits::default();
+    assert_eq!(
+        solve_with_prefix(&level, &[], limits).unwrap_err(),
+        SolverFailure::NoSolution
+    );
+
+    let level = LEGE_GRADVM("SPPE", 0, Handle::default()).unwrap();
+    let short = SolverLimits {
+        max_turns: 2,
+        ..limits
+    };
+    assert_eq!(
+        solve_with_prefix(&level, &[], short).unwrap_err(),
+        SolverFailure::LimitsReached
+    );
+    assert!(solve_with_prefix(&level, &[], limits).is_ok());
+}
@@ -0,0 +1,114 @@
+//! Plays small levels with helicopters through the simulation to check how they differ from
+//! rovers.
+
+use bevy::prelude::*;
+use mission_ares::game_control::actions::ActionType;
+use mission_ares::level::{GRADVM, LEGE_GRADVM, MANDATVM_LITTERAE};
+use mission_ares::simulation::{FailureReason, SimulationState, TurnOutcome, simulate};
+use mission_ares::unit::UnitKind;
+
+fn parse(text: &str) -> GRADVM {
+    LEGE_GRADVM(text, 0, Handle::default()).unwrap()
+}
+
+/// Commands of each unit, one letter each as in a @SOLVTIO directive
+fn programs(units: &[&str]) -> Vec<Vec<ActionType>> {
+    units
+        .iter()
+        .map(|letters| {
+            letters
+                .chars()
+                .map(|c| MANDATVM_LITTERAE(c).unwrap())
+                .collect()
+        })
+        .collect()
+}
+
+fn play(level: &GRADVM, units: &[&str]) -> (TurnOutcome, SimulationState) {
+    simulate(level, &programs(units))
+}
+
+#[test]
+fn helicopters_fly_over_gaps_but_stay_in_the_level() {
+    let level = parse("S I\nPPPE");
+    assert_eq!(level.GENVS(1), UnitKind::Helicopter);
+
+    let (outcome, _) = play(&level, &["R"]);
+    assert_eq!(outcome, TurnOutcome::Failed(FailureReason::BlockedMove));
+
+    let (outcome, state) = play(&level, &["W", "L"]);
+    assert_eq!(outcome, TurnOutcome::Failed(FailureReason::OutOfCommands));
+    assert_eq!(state.units[1].position.x, 1);
+
+    let (outcome, _) = play(&level, &["W", "U"]);
+    assert_eq!(outcome, TurnOutcome::Failed(FailureReason::BlockedMove));
+}
+
+#[test]
+fn rovers_drive_over_craters() {
+    let level = parse("SCE");
+
+    let (outcome, _) = play(&level, &["RR"]);
+    assert_eq!(outcome, TurnOutcome::Solved);
+}
+
+#[test]
+fn rovers_and_helicopters_never_collide() {
+    let level = parse("SIE");
+
+    let (outcome, state) = play(&level, &["R", "L"]);
+    assert_eq!(outcome, TurnOutcome::Failed(FailureReason::OutOfCommands));
+    assert_eq!(state.units[0].position.x, 1);
+    assert_eq!(state.units[1].position.x, 0);
+
+    let level = parse("SSE");
+    let (outcome, _) = play(&level, &["R", "L"]);
+    assert_eq!(outcome, TurnOutcome::Failed(FailureReason::Collision));
+}
+
+#[test]
+fn helicopters_leave_minerals_alone() {
+    let level = parse("IE\nS");
+
+    let (_, state) = play(&level, &["W", "R"]);
+    assert!(state.progress.collected.is_empty());
+}
+
+#[test]
+fn helicopters_only_recharge_on_the_ground() {
+    let level = parse("IPPPE\nS");
+    let mut state = SimulationState::new(&level);
+    assert_eq!(state.units[1].battery_level, 2);
+
+    state.step(&level, &[None, Some(ActionType::MoveRight)]);
+    assert_eq!(state.units[1].battery_level, 1);
+    state.step(&level, &[None, Some(ActionType::Wait)]);
+    assert_eq!(state.units[1].battery_level, 2);
+
+    let (outcome, _) = play(&level, &["WWW", "RRR"]);
+    assert_eq!(outcome, TurnOutcome::Failed(FailureReason::OutOfBattery));
+}
+
+#[test]
+fn scouting_reveals_the_hidden_minerals_around() {
+    let level = parse("SH\nPI");
+
+    let (_, state) = play(&level, &["R", "W"]);
+    assert!(state.progress.collected.is_empty());
+
+    let (outcome, state) = play(&level, &["WR", "V"]);
+    assert_eq!(outcome, TurnOutcome::Solved);
+    assert_eq!(state.progress.revealed, vec![0]);
+}
+
+#[test]
+fn battery_packs_go_to_the_rover_below() {
+    let level = parse("@PILA INITIVM=0 SOL=0\nES\n I");
+
+    let (outcome, _) = play(&level, &["WWL", "UW"]);
+    assert_eq!(outcome, TurnOutcome::Failed(FailureReason::OutOfBattery));
+
+    let (outcome, state) = play(&level, &["WWL", "UP"]);
+    assert_eq!(outcome, TurnOutcome::Solved);
+    assert_eq!(state.units[1].battery_pack, 0);
+}
@@ -0,0 +1,46 @@
+//! Checks solution programs the way the verify_solutions tool does.
+
+use bevy::prelude::*;
+use mission_ares::game_control::actions::ActionType;
+use mission_ares::level::{LEGE_FASCICVLVM_GRADVS, LEGE_GRADVM};
+use mission_ares::simulation::FailureReason;
+use mission_ares::verification::{SolutionProgram, verify};
+use std::path::Path;
+
+#[test]
+fn solution_files_are_parsed() {
+    let program = SolutionProgram::parse("# comment\n1 UR\n\n0 W\nexpect failed\n").unwrap();
+
+    assert_eq!(
+        program.actions,
+        vec![
+            vec![ActionType::Wait],
+            vec![ActionType::MoveUp, ActionType::MoveRight],
+        ]
+    );
+    assert!(!program.expect_solved);
+    assert!(SolutionProgram::parse("0 UX").is_err());
+    assert!(SolutionProgram::parse("expect maybe").is_err());
+}
+
+#[test]
+fn game_and_simulation_agree_on_the_first_level() {
+    let level = LEGE_FASCICVLVM_GRADVS(Path::new("assets/levels/1.lvl"), 0).unwrap();
+
+    let solved = verify(&level, level.SOLVTIO.as_ref().unwrap()).unwrap();
+    assert!(solved.solved && solved.simulation_agrees);
+    assert_eq!(solved.turns, 3);
+
+    let failed = verify(&level, &[vec![ActionType::MoveUp]]).unwrap();
+    assert!(!failed.solved && failed.simulation_agrees);
+    assert_eq!(failed.failure, Some(FailureReason::OutOfCommands));
+}
+
+#[test]
+fn failures_are_the_ones_the_game_ran_into() {
+    let level = LEGE_GRADVM("SPE", 0, Handle::default()).unwrap();
+
+    let blocked = verify(&level, &[vec![ActionType::MoveUp]]).unwrap();
+    assert!(!blocked.solved && blocked.simulation_agrees);
+    assert_eq!(blocked.failure, Some(FailureReason::BlockedMove));
+}